@@ -0,0 +1,4 @@
+-- 登录安全：连续失败次数与临时锁定
+ALTER TABLE i18n_users
+    ADD COLUMN failed_attempts INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '连续登录失败次数' AFTER last_login,
+    ADD COLUMN locked_until DATETIME DEFAULT NULL COMMENT '锁定截止时间' AFTER failed_attempts;
@@ -0,0 +1,18 @@
+-- API令牌表（CI流水线、SDK构建工具使用）
+CREATE TABLE i18n_api_tokens (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    user_id INT UNSIGNED NOT NULL COMMENT '所属用户id',
+    name VARCHAR(100) NOT NULL COMMENT '令牌名称',
+    token_prefix CHAR(8) NOT NULL UNIQUE COMMENT '令牌前缀，用于查找',
+    token_hash VARCHAR(255) NOT NULL COMMENT '令牌哈希（argon2）',
+    project_ids JSON DEFAULT NULL COMMENT '可访问的项目id列表，NULL表示不限',
+    permission ENUM('read', 'write') NOT NULL DEFAULT 'read' COMMENT '读写权限',
+    expires_at DATETIME DEFAULT NULL COMMENT '过期时间，NULL表示永不过期',
+    last_used_at DATETIME DEFAULT NULL COMMENT '最后使用时间',
+    revoked_at DATETIME DEFAULT NULL COMMENT '吊销时间',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    INDEX idx_api_tokens_user (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
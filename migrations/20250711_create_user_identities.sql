@@ -0,0 +1,14 @@
+-- 外部身份关联表（OIDC单点登录）
+CREATE TABLE i18n_user_identities (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    user_id INT UNSIGNED NOT NULL COMMENT '用户id',
+    provider VARCHAR(255) NOT NULL COMMENT '身份提供方（issuer）',
+    subject VARCHAR(255) NOT NULL COMMENT '提供方用户标识（sub）',
+    email VARCHAR(100) DEFAULT NULL COMMENT '提供方邮箱',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    UNIQUE KEY (provider, subject),
+    INDEX idx_user_identities_user (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -0,0 +1,17 @@
+-- 两步验证：TOTP 密钥与一次性恢复码
+ALTER TABLE i18n_users
+    ADD COLUMN totp_secret VARCHAR(64) DEFAULT NULL COMMENT 'TOTP密钥（Base32）' AFTER locked_until,
+    ADD COLUMN totp_enabled BOOLEAN NOT NULL DEFAULT FALSE COMMENT '是否已启用两步验证' AFTER totp_secret,
+    ADD COLUMN totp_last_step BIGINT UNSIGNED DEFAULT NULL COMMENT '最近一次使用的时间窗口，防止验证码重放' AFTER totp_enabled;
+
+CREATE TABLE i18n_user_recovery_codes (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    user_id INT UNSIGNED NOT NULL COMMENT '用户id',
+    code_hash CHAR(64) NOT NULL COMMENT '恢复码哈希（SHA-256）',
+    used_at DATETIME DEFAULT NULL COMMENT '使用时间',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    UNIQUE KEY uk_recovery_codes_user_hash (user_id, code_hash)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -0,0 +1,6 @@
+-- 乐观锁：词条和翻译更新时校验版本号
+ALTER TABLE i18n_phrases
+    ADD COLUMN version INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '版本号，每次更新加1' AFTER is_plural;
+
+ALTER TABLE i18n_translations
+    ADD COLUMN version INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '版本号，每次更新加1' AFTER reviewed_by;
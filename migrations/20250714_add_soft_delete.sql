@@ -0,0 +1,21 @@
+-- 软删除：删除时只记录删除时间，回收站中的数据超过保留期后清理
+-- 级联删除的父子数据使用相同的删除时间，恢复时据此一起恢复
+ALTER TABLE i18n_projects
+    ADD COLUMN deleted_at DATETIME(6) DEFAULT NULL COMMENT '删除时间' AFTER upt_at,
+    ADD INDEX idx_projects_deleted_at (deleted_at);
+
+ALTER TABLE i18n_modules
+    ADD COLUMN deleted_at DATETIME(6) DEFAULT NULL COMMENT '删除时间' AFTER upt_at,
+    ADD INDEX idx_modules_deleted_at (deleted_at);
+
+ALTER TABLE i18n_phrases
+    ADD COLUMN deleted_at DATETIME(6) DEFAULT NULL COMMENT '删除时间' AFTER upt_at,
+    ADD INDEX idx_phrases_deleted_at (deleted_at);
+
+ALTER TABLE i18n_phrase_screenshots
+    ADD COLUMN deleted_at DATETIME(6) DEFAULT NULL COMMENT '删除时间' AFTER upt_at,
+    ADD INDEX idx_screenshots_deleted_at (deleted_at);
+
+ALTER TABLE i18n_translations
+    ADD COLUMN deleted_at DATETIME(6) DEFAULT NULL COMMENT '删除时间' AFTER upt_at,
+    ADD INDEX idx_translations_deleted_at (deleted_at);
@@ -0,0 +1,9 @@
+-- 全文检索：使用 ngram 分词器，支持中日韩文本（默认 ngram_token_size = 2）
+ALTER TABLE i18n_phrases
+    ADD FULLTEXT INDEX ft_phrases_content (`key`, base_content, context) WITH PARSER ngram;
+
+ALTER TABLE i18n_translations
+    ADD FULLTEXT INDEX ft_translations_content (content) WITH PARSER ngram;
+
+ALTER TABLE i18n_terms
+    ADD FULLTEXT INDEX ft_terms_content (source_term, target_term, description) WITH PARSER ngram;
@@ -0,0 +1,3 @@
+-- 项目语言的回退链
+ALTER TABLE i18n_project_languages
+    ADD COLUMN fallbacks JSON NULL COMMENT '回退语言代码列表，按顺序尝试；为空时使用 BCP-47 父级语言' AFTER is_default;
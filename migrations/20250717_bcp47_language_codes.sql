@@ -0,0 +1,131 @@
+-- 语言代码改为 BCP-47 标签（如 zh-Hant-TW、sr-Latn、es-419），最长 35 个字符
+ALTER TABLE i18n_languages
+    MODIFY code VARCHAR(35) NOT NULL COMMENT 'BCP-47 语言标签',
+    MODIFY name VARCHAR(100) NOT NULL COMMENT '英文名称',
+    ADD COLUMN native_name VARCHAR(100) NULL COMMENT '本地名称' AFTER name,
+    ADD COLUMN direction VARCHAR(3) NOT NULL DEFAULT 'ltr' COMMENT '文字方向：ltr / rtl' AFTER native_name,
+    ADD COLUMN plural_categories JSON NULL COMMENT 'CLDR 复数类别' AFTER direction;
+
+ALTER TABLE i18n_projects MODIFY base_language VARCHAR(35) NOT NULL COMMENT '基础语言代码';
+ALTER TABLE i18n_translations MODIFY language VARCHAR(35) NOT NULL COMMENT '语言代码';
+ALTER TABLE i18n_terms MODIFY language VARCHAR(35) NOT NULL COMMENT '语言代码';
+ALTER TABLE i18n_project_languages MODIFY language VARCHAR(35) NOT NULL COMMENT '语言代码';
+
+-- 旧数据中的语言代码规范化：下划线改为连字符，语言小写、文字首字母大写、地区大写（zh_cn -> zh-CN，zh_hant_tw -> zh-Hant-TW）
+-- 临时表使用区分大小写的排序规则，只改写规范形式与原值不同的代码
+CREATE TEMPORARY TABLE tmp_language_codes (
+    code VARCHAR(35) COLLATE utf8mb4_bin NOT NULL PRIMARY KEY,
+    canonical VARCHAR(35) COLLATE utf8mb4_bin NULL
+);
+
+INSERT INTO tmp_language_codes (code)
+SELECT code COLLATE utf8mb4_bin FROM i18n_languages
+UNION SELECT language COLLATE utf8mb4_bin FROM i18n_translations
+UNION SELECT language COLLATE utf8mb4_bin FROM i18n_terms
+UNION SELECT language COLLATE utf8mb4_bin FROM i18n_project_languages
+UNION SELECT base_language COLLATE utf8mb4_bin FROM i18n_projects;
+
+UPDATE tmp_language_codes SET canonical = REPLACE(TRIM(code), '_', '-');
+
+UPDATE tmp_language_codes
+SET canonical = CASE
+    WHEN canonical REGEXP '^[A-Za-z]{2,3}$' THEN LOWER(canonical)
+    WHEN canonical REGEXP '^[A-Za-z]{2,3}-([A-Za-z]{2}|[0-9]{3})$' THEN CONCAT(
+        LOWER(SUBSTRING_INDEX(canonical, '-', 1)), '-',
+        UPPER(SUBSTRING_INDEX(canonical, '-', -1)))
+    WHEN canonical REGEXP '^[A-Za-z]{2,3}-[A-Za-z]{4}$' THEN CONCAT(
+        LOWER(SUBSTRING_INDEX(canonical, '-', 1)), '-',
+        UPPER(LEFT(SUBSTRING_INDEX(canonical, '-', -1), 1)),
+        LOWER(SUBSTRING(SUBSTRING_INDEX(canonical, '-', -1), 2)))
+    WHEN canonical REGEXP '^[A-Za-z]{2,3}-[A-Za-z]{4}-([A-Za-z]{2}|[0-9]{3})$' THEN CONCAT(
+        LOWER(SUBSTRING_INDEX(canonical, '-', 1)), '-',
+        UPPER(LEFT(SUBSTRING_INDEX(SUBSTRING_INDEX(canonical, '-', 2), '-', -1), 1)),
+        LOWER(SUBSTRING(SUBSTRING_INDEX(SUBSTRING_INDEX(canonical, '-', 2), '-', -1), 2)), '-',
+        UPPER(SUBSTRING_INDEX(canonical, '-', -1)))
+    ELSE canonical
+END;
+
+DELETE FROM tmp_language_codes WHERE canonical = code;
+
+-- 规范形式已存在时（如同时有 zh_CN 和 zh-CN）唯一键冲突的行保持原样，由管理员合并
+UPDATE IGNORE i18n_languages l JOIN tmp_language_codes m ON m.code = l.code COLLATE utf8mb4_bin
+SET l.code = m.canonical;
+UPDATE IGNORE i18n_translations t JOIN tmp_language_codes m ON m.code = t.language COLLATE utf8mb4_bin
+SET t.language = m.canonical;
+UPDATE IGNORE i18n_project_languages pl JOIN tmp_language_codes m ON m.code = pl.language COLLATE utf8mb4_bin
+SET pl.language = m.canonical;
+UPDATE i18n_terms t JOIN tmp_language_codes m ON m.code = t.language COLLATE utf8mb4_bin
+SET t.language = m.canonical;
+UPDATE i18n_projects p JOIN tmp_language_codes m ON m.code = p.base_language COLLATE utf8mb4_bin
+SET p.base_language = m.canonical;
+
+DROP TEMPORARY TABLE tmp_language_codes;
+
+-- 常用语言目录，默认不启用
+CREATE TEMPORARY TABLE tmp_locale_catalog (
+    code VARCHAR(35) NOT NULL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    native_name VARCHAR(100) NOT NULL,
+    direction VARCHAR(3) NOT NULL,
+    plural_categories JSON NOT NULL
+);
+
+INSERT INTO tmp_locale_catalog (code, name, native_name, direction, plural_categories) VALUES
+('en', 'English', 'English', 'ltr', '["one", "other"]'),
+('en-US', 'English (United States)', 'English (United States)', 'ltr', '["one", "other"]'),
+('en-GB', 'English (United Kingdom)', 'English (United Kingdom)', 'ltr', '["one", "other"]'),
+('zh', 'Chinese', '中文', 'ltr', '["other"]'),
+('zh-Hans', 'Chinese (Simplified)', '简体中文', 'ltr', '["other"]'),
+('zh-Hant', 'Chinese (Traditional)', '繁體中文', 'ltr', '["other"]'),
+('zh-Hant-TW', 'Chinese (Traditional, Taiwan)', '繁體中文（台灣）', 'ltr', '["other"]'),
+('zh-Hant-HK', 'Chinese (Traditional, Hong Kong)', '繁體中文（香港）', 'ltr', '["other"]'),
+('ja', 'Japanese', '日本語', 'ltr', '["other"]'),
+('ko', 'Korean', '한국어', 'ltr', '["other"]'),
+('fr', 'French', 'Français', 'ltr', '["one", "many", "other"]'),
+('fr-CA', 'French (Canada)', 'Français (Canada)', 'ltr', '["one", "many", "other"]'),
+('de', 'German', 'Deutsch', 'ltr', '["one", "other"]'),
+('es', 'Spanish', 'Español', 'ltr', '["one", "many", "other"]'),
+('es-419', 'Spanish (Latin America)', 'Español (Latinoamérica)', 'ltr', '["one", "many", "other"]'),
+('pt', 'Portuguese', 'Português', 'ltr', '["one", "many", "other"]'),
+('pt-BR', 'Portuguese (Brazil)', 'Português (Brasil)', 'ltr', '["one", "many", "other"]'),
+('pt-PT', 'Portuguese (Portugal)', 'Português (Portugal)', 'ltr', '["one", "many", "other"]'),
+('it', 'Italian', 'Italiano', 'ltr', '["one", "many", "other"]'),
+('nl', 'Dutch', 'Nederlands', 'ltr', '["one", "other"]'),
+('ru', 'Russian', 'Русский', 'ltr', '["one", "few", "many", "other"]'),
+('uk', 'Ukrainian', 'Українська', 'ltr', '["one", "few", "many", "other"]'),
+('pl', 'Polish', 'Polski', 'ltr', '["one", "few", "many", "other"]'),
+('cs', 'Czech', 'Čeština', 'ltr', '["one", "few", "many", "other"]'),
+('sr-Cyrl', 'Serbian (Cyrillic)', 'Српски (ћирилица)', 'ltr', '["one", "few", "other"]'),
+('sr-Latn', 'Serbian (Latin)', 'Srpski (latinica)', 'ltr', '["one", "few", "other"]'),
+('tr', 'Turkish', 'Türkçe', 'ltr', '["one", "other"]'),
+('sv', 'Swedish', 'Svenska', 'ltr', '["one", "other"]'),
+('da', 'Danish', 'Dansk', 'ltr', '["one", "other"]'),
+('nb', 'Norwegian Bokmål', 'Norsk bokmål', 'ltr', '["one", "other"]'),
+('fi', 'Finnish', 'Suomi', 'ltr', '["one", "other"]'),
+('el', 'Greek', 'Ελληνικά', 'ltr', '["one", "other"]'),
+('hu', 'Hungarian', 'Magyar', 'ltr', '["one", "other"]'),
+('ro', 'Romanian', 'Română', 'ltr', '["one", "few", "other"]'),
+('ar', 'Arabic', 'العربية', 'rtl', '["zero", "one", "two", "few", "many", "other"]'),
+('he', 'Hebrew', 'עברית', 'rtl', '["one", "two", "other"]'),
+('fa', 'Persian', 'فارسی', 'rtl', '["one", "other"]'),
+('ur', 'Urdu', 'اردو', 'rtl', '["one", "other"]'),
+('hi', 'Hindi', 'हिन्दी', 'ltr', '["one", "other"]'),
+('bn', 'Bengali', 'বাংলা', 'ltr', '["one", "other"]'),
+('th', 'Thai', 'ไทย', 'ltr', '["other"]'),
+('vi', 'Vietnamese', 'Tiếng Việt', 'ltr', '["other"]'),
+('id', 'Indonesian', 'Bahasa Indonesia', 'ltr', '["other"]'),
+('ms', 'Malay', 'Bahasa Melayu', 'ltr', '["other"]');
+
+-- 补全已有语言的元数据
+UPDATE i18n_languages l JOIN tmp_locale_catalog c ON c.code = l.code
+SET l.native_name = COALESCE(l.native_name, c.native_name),
+    l.direction = c.direction,
+    l.plural_categories = COALESCE(l.plural_categories, c.plural_categories);
+
+INSERT INTO i18n_languages (code, name, native_name, direction, plural_categories, is_active, is_native, crt_by)
+SELECT c.code, c.name, c.native_name, c.direction, c.plural_categories, FALSE, FALSE, 'system'
+FROM tmp_locale_catalog c
+WHERE NOT EXISTS (SELECT 1 FROM i18n_languages l WHERE l.code = c.code)
+  AND NOT EXISTS (SELECT 1 FROM i18n_languages l WHERE l.name = c.name);
+
+DROP TEMPORARY TABLE tmp_locale_catalog;
@@ -0,0 +1,6 @@
+-- 翻译历史记录每次修改后的状态和操作人，用于译员工作量与质量报表
+ALTER TABLE i18n_translation_history
+    ADD COLUMN status ENUM('pending', 'reviewed', 'published') NOT NULL DEFAULT 'pending' COMMENT '修改后的状态' AFTER version,
+    ADD COLUMN user_id INT UNSIGNED NULL COMMENT '操作人id' AFTER status,
+    ADD INDEX idx_history_user (user_id),
+    ADD INDEX idx_history_crt_at (crt_at);
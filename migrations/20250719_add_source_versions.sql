@@ -0,0 +1,21 @@
+-- 基础语言内容版本：修改词条原文后，依据旧原文的翻译标记为过期
+ALTER TABLE i18n_phrases
+    ADD COLUMN source_version INT UNSIGNED NOT NULL DEFAULT 1 COMMENT '基础语言内容版本号' AFTER version;
+
+ALTER TABLE i18n_translations
+    MODIFY status ENUM('pending', 'reviewed', 'published', 'outdated') DEFAULT 'pending',
+    ADD COLUMN source_version INT UNSIGNED NOT NULL DEFAULT 1 COMMENT '翻译所依据的基础语言内容版本号' AFTER version;
+
+ALTER TABLE i18n_translation_history
+    MODIFY status ENUM('pending', 'reviewed', 'published', 'outdated') NOT NULL DEFAULT 'pending' COMMENT '修改后的状态';
+
+-- 词条原文的历史版本，用于翻译工作台对比新旧原文
+CREATE TABLE i18n_phrase_sources (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    phrase_id INT UNSIGNED NOT NULL COMMENT '词条id',
+    source_version INT UNSIGNED NOT NULL COMMENT '基础语言内容版本号',
+    base_content TEXT NOT NULL COMMENT '该版本的基础语言内容',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    UNIQUE KEY uk_phrase_sources_version (phrase_id, source_version)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -0,0 +1,35 @@
+-- 项目 Webhook 订阅
+CREATE TABLE i18n_webhooks (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    project_id INT UNSIGNED NOT NULL COMMENT '项目id',
+    url VARCHAR(500) NOT NULL COMMENT '接收地址',
+    secret VARCHAR(100) NOT NULL COMMENT '签名密钥',
+    events JSON NOT NULL COMMENT '订阅的事件类型',
+    is_active BOOLEAN NOT NULL DEFAULT TRUE COMMENT '是否启用',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    KEY idx_webhooks_project (project_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+-- Webhook 投递记录，同时作为投递队列：待投递的记录由后台任务按 next_attempt_at 领取
+CREATE TABLE i18n_webhook_deliveries (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    webhook_id INT UNSIGNED NOT NULL COMMENT 'Webhook id',
+    event_id VARCHAR(36) NOT NULL COMMENT '事件id，同一事件投递到多个 Webhook 时相同',
+    event VARCHAR(64) NOT NULL COMMENT '事件类型',
+    payload MEDIUMTEXT NOT NULL COMMENT '请求体',
+    status ENUM('pending', 'delivered', 'failed') NOT NULL DEFAULT 'pending' COMMENT '投递状态',
+    attempts INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '已投递次数',
+    next_attempt_at TIMESTAMP NULL COMMENT '下次投递时间',
+    response_status INT COMMENT '最近一次响应状态码',
+    response_body TEXT COMMENT '最近一次响应内容（截断）',
+    error VARCHAR(1000) COMMENT '最近一次错误信息',
+    delivered_at TIMESTAMP NULL COMMENT '投递成功时间',
+    redelivery_of INT UNSIGNED COMMENT '手动重新投递时，原投递记录id',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    KEY idx_webhook_deliveries_webhook (webhook_id, id),
+    KEY idx_webhook_deliveries_due (status, next_attempt_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
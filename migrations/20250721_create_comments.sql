@@ -0,0 +1,23 @@
+-- 词条、翻译的评论：根评论构成讨论串，回复的 parent_id 指向根评论
+CREATE TABLE i18n_comments (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    project_id INT UNSIGNED NOT NULL COMMENT '项目id',
+    phrase_id INT UNSIGNED NOT NULL COMMENT '词条id',
+    translation_id INT UNSIGNED COMMENT '翻译id，为空表示评论词条本身',
+    parent_id INT UNSIGNED COMMENT '根评论id，为空表示根评论',
+    user_id INT UNSIGNED NOT NULL COMMENT '评论人id',
+    content TEXT NOT NULL COMMENT '评论内容',
+    mentions JSON COMMENT '@提及的用户',
+    is_question BOOLEAN NOT NULL DEFAULT FALSE COMMENT '是否为需要开发者解答的问题',
+    is_resolved BOOLEAN NOT NULL DEFAULT FALSE COMMENT '讨论串是否已解决',
+    resolved_by INT UNSIGNED COMMENT '解决人id',
+    resolved_at TIMESTAMP NULL COMMENT '解决时间',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    deleted_at TIMESTAMP NULL COMMENT '删除时间',
+    KEY idx_comments_phrase (phrase_id, translation_id),
+    KEY idx_comments_parent (parent_id),
+    KEY idx_comments_open (project_id, is_resolved, parent_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
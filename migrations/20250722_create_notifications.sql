@@ -0,0 +1,28 @@
+-- 站内通知：指派、审核结果、@提及、翻译过期
+CREATE TABLE i18n_notifications (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    user_id INT UNSIGNED NOT NULL COMMENT '接收人id',
+    kind ENUM('assignment', 'review_result', 'mention', 'outdated') NOT NULL COMMENT '通知类型',
+    title VARCHAR(255) NOT NULL COMMENT '标题',
+    content TEXT COMMENT '内容',
+    project_id INT UNSIGNED COMMENT '项目id',
+    target_type VARCHAR(50) NOT NULL COMMENT '关联对象类型',
+    target_id INT UNSIGNED NOT NULL COMMENT '关联对象id',
+    actor_id INT UNSIGNED COMMENT '触发人id',
+    actor VARCHAR(50) COMMENT '触发人',
+    is_read BOOLEAN NOT NULL DEFAULT FALSE COMMENT '是否已读',
+    read_at TIMESTAMP NULL COMMENT '阅读时间',
+    digest_id VARCHAR(36) COMMENT '所在邮件摘要批次，为空表示尚未处理',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    KEY idx_notifications_inbox (user_id, is_read, id),
+    KEY idx_notifications_digest (digest_id, is_read)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+-- 用户通知偏好，未设置时使用默认值：接收全部类型，发送每日摘要
+CREATE TABLE i18n_notification_preferences (
+    user_id INT UNSIGNED PRIMARY KEY COMMENT '用户id',
+    muted_kinds JSON COMMENT '不接收的通知类型',
+    email_digest BOOLEAN NOT NULL DEFAULT TRUE COMMENT '是否发送每日邮件摘要',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
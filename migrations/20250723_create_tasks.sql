@@ -0,0 +1,32 @@
+-- 翻译任务：将一组词条的某个目标语言指派给译者和审核人，进度由翻译状态自动统计
+CREATE TABLE i18n_tasks (
+    id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY COMMENT '主键id',
+    project_id INT UNSIGNED NOT NULL COMMENT '项目id',
+    language VARCHAR(20) NOT NULL COMMENT '目标语言',
+    name VARCHAR(100) NOT NULL COMMENT '任务名称',
+    description TEXT COMMENT '任务说明',
+    translator_id INT UNSIGNED NOT NULL COMMENT '译者id',
+    reviewer_id INT UNSIGNED COMMENT '审核人id，为空表示无需审核',
+    due_at TIMESTAMP NOT NULL COMMENT '截止时间',
+    status ENUM('open', 'completed', 'cancelled') NOT NULL DEFAULT 'open' COMMENT '状态',
+    total INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '词条数',
+    translated INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '已翻译词条数',
+    reviewed INT UNSIGNED NOT NULL DEFAULT 0 COMMENT '已审核词条数',
+    completed_at TIMESTAMP NULL COMMENT '完成时间',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+    KEY idx_tasks_project (project_id, status),
+    KEY idx_tasks_translator (translator_id, status),
+    KEY idx_tasks_reviewer (reviewer_id, status),
+    KEY idx_tasks_due (status, due_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+-- 任务包含的词条
+CREATE TABLE i18n_task_phrases (
+    task_id INT UNSIGNED NOT NULL COMMENT '任务id',
+    phrase_id INT UNSIGNED NOT NULL COMMENT '词条id',
+    PRIMARY KEY (task_id, phrase_id),
+    KEY idx_task_phrases_phrase (phrase_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -0,0 +1,13 @@
+-- 项目的词条标识符规则，创建、导入和重命名词条时校验
+CREATE TABLE i18n_key_rules (
+    project_id INT UNSIGNED PRIMARY KEY COMMENT '项目id',
+    pattern VARCHAR(255) COMMENT '标识符需完整匹配的正则表达式',
+    require_module_prefix BOOLEAN NOT NULL DEFAULT FALSE COMMENT '是否要求以所属模块路径为前缀',
+    key_separator VARCHAR(5) NOT NULL DEFAULT '.' COMMENT '层级分隔符',
+    max_depth INT UNSIGNED COMMENT '最大层级数',
+    forbidden_chars VARCHAR(100) COMMENT '禁止出现的字符',
+    crt_by VARCHAR(50) NOT NULL COMMENT '创建人',
+    crt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    upt_by VARCHAR(50) COMMENT '更新人',
+    upt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
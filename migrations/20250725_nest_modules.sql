@@ -0,0 +1,23 @@
+-- 模块支持嵌套：parent_id 指向父模块，path 为从根模块起的名称路径（如 checkout/cart），同一项目内唯一
+ALTER TABLE i18n_modules
+    ADD COLUMN parent_id INT UNSIGNED COMMENT '父模块id，为空表示根模块' AFTER project_id;
+
+-- 已有模块都作为根模块，名称中的 "/" 不能出现在路径中
+UPDATE i18n_modules SET path = REPLACE(name, '/', '-') WHERE path IS NULL OR path = '';
+
+-- 替换后可能重名（如 a/b 与 a-b），同一项目内除 id 最小者外都加上 id 后缀
+-- 用派生表先取出要改的 id，避免在子查询中引用正在更新的表
+UPDATE i18n_modules m
+    JOIN (
+        SELECT a.id
+        FROM i18n_modules a
+            JOIN i18n_modules b ON b.project_id = a.project_id AND b.path = a.path AND b.id < a.id
+        GROUP BY a.id
+    ) dup ON dup.id = m.id
+SET m.path = CONCAT(m.path, '-', m.id);
+
+ALTER TABLE i18n_modules
+    MODIFY COLUMN path VARCHAR(255) NOT NULL COMMENT '模块路径，从根模块起的名称以 / 连接',
+    DROP INDEX project_id,
+    ADD UNIQUE KEY uk_modules_path (project_id, path),
+    ADD INDEX idx_modules_parent (parent_id);
@@ -39,7 +39,67 @@ pub struct Kafka {
 #[derive(Debug, Deserialize)]
 pub struct Jwt {
     pub secret: String,
+    // 访问令牌有效期（秒）
     pub expiration: i64,
+    // 刷新令牌有效期（秒）
+    pub refresh_expiration: i64,
+    // 签名算法：HS256 / RS256 / EdDSA 等
+    pub algorithm: String,
+    // 当前签名密钥的 kid
+    pub key_id: Option<String>,
+    // 非对称算法的签名私钥（PEM）路径
+    pub private_key_path: Option<String>,
+    // 校验公钥集合（JWKS）路径，包含当前密钥和轮换中的旧密钥
+    pub jwks_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Oidc {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: String,
+    // 是否自动为首次登录的用户创建账号
+    pub auto_provision: bool,
+}
+
+fn default_oidc_scopes() -> String {
+    "openid email profile".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Mfa {
+    // 验证器 App 中显示的签发方名称
+    pub issuer: String,
+    // 必须启用两步验证的角色
+    pub required_roles: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Trash {
+    // 回收站保留天数，超过后物理删除
+    pub retention_days: i64,
+    // 清理任务执行间隔（秒），必须大于 0
+    pub purge_interval: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Mail {
+    // 发送方式：log 只写日志，file 写入 .eml 文件，smtp 通过 SMTP 服务器发送
+    pub sender: String,
+    pub from: String,
+    // file 方式的输出目录
+    pub file_dir: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    // 是否使用 STARTTLS，本地调试用的 SMTP 服务可关闭
+    pub smtp_starttls: bool,
+    // 每日通知摘要的发送时间（本地时间的小时）
+    pub digest_hour: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,6 +117,11 @@ pub struct Settings {
     pub minio: Minio,
     pub kafka: Kafka,
     pub jwt: Jwt,
+    // 未配置 OIDC_ISSUER 时不启用单点登录
+    pub oidc: Option<Oidc>,
+    pub mfa: Mfa,
+    pub trash: Trash,
+    pub mail: Mail,
     pub environment: String,
 }
 
@@ -99,6 +164,69 @@ impl Settings {
             jwt: Jwt {
                 secret: env::var("JWT_SECRET")?,
                 expiration: env::var("JWT_EXPIRATION")?.parse().unwrap_or(86400),
+                refresh_expiration: env::var("JWT_REFRESH_EXPIRATION")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(604800),
+                algorithm: env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".to_string()),
+                key_id: env::var("JWT_KEY_ID").ok(),
+                private_key_path: env::var("JWT_PRIVATE_KEY_PATH").ok(),
+                jwks_path: env::var("JWT_JWKS_PATH").ok(),
+            },
+            oidc: match env::var("OIDC_ISSUER") {
+                Ok(issuer) => Some(Oidc {
+                    issuer,
+                    client_id: env::var("OIDC_CLIENT_ID")?,
+                    client_secret: env::var("OIDC_CLIENT_SECRET").ok(),
+                    redirect_uri: env::var("OIDC_REDIRECT_URI")?,
+                    scopes: env::var("OIDC_SCOPES").unwrap_or_else(|_| default_oidc_scopes()),
+                    auto_provision: env::var("OIDC_AUTO_PROVISION")
+                        .map(|v| v.parse().unwrap_or(true))
+                        .unwrap_or(true),
+                }),
+                Err(_) => None,
+            },
+            mfa: Mfa {
+                issuer: env::var("MFA_ISSUER").unwrap_or_else(|_| "i18n-platform".to_string()),
+                required_roles: env::var("MFA_REQUIRED_ROLES")
+                    .unwrap_or_else(|_| "admin,project_manager".to_string())
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            },
+            trash: Trash {
+                retention_days: env::var("TRASH_RETENTION_DAYS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(30),
+                purge_interval: env::var("TRASH_PURGE_INTERVAL")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .filter(|interval| *interval > 0)
+                    .unwrap_or(3600),
+            },
+            mail: Mail {
+                sender: env::var("MAIL_SENDER").unwrap_or_else(|_| "log".to_string()),
+                from: env::var("MAIL_FROM")
+                    .unwrap_or_else(|_| "i18n-platform <noreply@localhost>".to_string()),
+                file_dir: env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "tmp/mail".to_string()),
+                smtp_host: env::var("MAIL_SMTP_HOST").ok(),
+                smtp_port: env::var("MAIL_SMTP_PORT")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(587),
+                smtp_username: env::var("MAIL_SMTP_USERNAME").ok(),
+                smtp_password: env::var("MAIL_SMTP_PASSWORD").ok(),
+                smtp_starttls: env::var("MAIL_SMTP_STARTTLS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(true),
+                digest_hour: env::var("MAIL_DIGEST_HOUR")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .filter(|hour| *hour < 24)
+                    .unwrap_or(9),
             },
             environment: env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
         })
@@ -0,0 +1,23 @@
+// 操作日志对象类型
+pub const AUDIT_TARGET_USER: &str = "user";
+
+// 操作日志动作
+pub const AUDIT_ACTION_LOGIN_SUCCESS: &str = "login_success";
+pub const AUDIT_ACTION_LOGIN_FAILED: &str = "login_failed";
+pub const AUDIT_ACTION_SSO_LOGIN: &str = "sso_login";
+pub const AUDIT_ACTION_ACCOUNT_LOCKED: &str = "account_locked";
+pub const AUDIT_ACTION_ACCOUNT_UNLOCKED: &str = "account_unlocked";
+pub const AUDIT_ACTION_MFA_ENABLED: &str = "mfa_enabled";
+pub const AUDIT_ACTION_MFA_DISABLED: &str = "mfa_disabled";
+pub const AUDIT_ACTION_MFA_VERIFIED: &str = "mfa_verified";
+pub const AUDIT_ACTION_MFA_FAILED: &str = "mfa_failed";
+pub const AUDIT_ACTION_RECOVERY_CODE_USED: &str = "recovery_code_used";
+pub const AUDIT_ACTION_RECOVERY_CODES_REGENERATED: &str = "recovery_codes_regenerated";
+
+// 评论
+pub const AUDIT_TARGET_COMMENT: &str = "comment";
+pub const AUDIT_ACTION_COMMENT_CREATED: &str = "comment_created";
+pub const AUDIT_ACTION_COMMENT_UPDATED: &str = "comment_updated";
+pub const AUDIT_ACTION_COMMENT_DELETED: &str = "comment_deleted";
+pub const AUDIT_ACTION_COMMENT_RESOLVED: &str = "comment_resolved";
+pub const AUDIT_ACTION_COMMENT_REOPENED: &str = "comment_reopened";
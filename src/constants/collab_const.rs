@@ -0,0 +1,8 @@
+// 实时协作
+pub const COLLAB_HEARTBEAT_SECONDS: u64 = 20; // 服务端心跳间隔，同时刷新在线状态和软锁
+pub const COLLAB_CLIENT_TIMEOUT_SECONDS: u64 = 60; // 超过此时间未收到客户端消息则断开
+pub const COLLAB_PRESENCE_TTL_SECONDS: i64 = 90; // 在线状态超过此时间未刷新视为离线
+pub const COLLAB_LOCK_SECONDS: u64 = 120; // 词条软锁时长，持有者心跳时续期
+pub const COLLAB_MESSAGE_MAX_BYTES: usize = 64 * 1024; // 客户端消息大小上限
+pub const COLLAB_RECONNECT_SECONDS: u64 = 5; // Redis 订阅断开后的重连间隔
+pub const COLLAB_TOKEN_PROTOCOL: &str = "access_token"; // 浏览器通过 Sec-WebSocket-Protocol 携带访问令牌时的子协议名
@@ -0,0 +1,3 @@
+// 评论
+pub const COMMENT_MAX_CHARS: usize = 5000; // 评论内容最大字符数
+pub const COMMENT_MAX_MENTIONS: usize = 20; // 单条评论最多@提及的用户数
@@ -0,0 +1,5 @@
+// 词条标识符规则
+pub const KEY_RULE_DEFAULT_SEPARATOR: &str = "."; // 默认层级分隔符
+pub const KEY_RULE_PATTERN_MAX_CHARS: usize = 255; // 正则表达式最大字符数
+pub const KEY_RULE_SEPARATOR_MAX_CHARS: usize = 5; // 分隔符最大字符数
+pub const KEY_RULE_FORBIDDEN_MAX_CHARS: usize = 100; // 禁用字符最多个数
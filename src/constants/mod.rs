@@ -1,6 +1,35 @@
+mod audit_const;
+mod collab_const;
+mod comment_const;
+mod key_rule_const;
+mod module_const;
+mod notification_const;
+mod phrase_const;
+mod stats_const;
+mod task_const;
 mod user_const;
+mod webhook_const;
 
+pub use audit_const::*;
+pub use collab_const::*;
+pub use comment_const::*;
+pub use key_rule_const::*;
+pub use module_const::*;
+pub use notification_const::*;
+pub use phrase_const::*;
+pub use stats_const::*;
+pub use task_const::*;
 pub use user_const::*;
+pub use webhook_const::*;
 
 
-pub const CAPTCHA_MD5_PREFIX: &str = "captcha_md5_";
\ No newline at end of file
+pub const CAPTCHA_MD5_PREFIX: &str = "captcha_md5_";
+pub const LOGIN_FAIL_USER_PREFIX: &str = "login_fail_user_";
+pub const LOGIN_FAIL_IP_PREFIX: &str = "login_fail_ip_";
+pub const OIDC_STATE_PREFIX: &str = "oidc_state_";
+pub const MFA_CHALLENGE_PREFIX: &str = "mfa_challenge_";
+pub const PROJECT_STATS_PREFIX: &str = "project_stats_";
+pub const PROJECT_STATS_VERSION_PREFIX: &str = "project_stats_version_";
+pub const COLLAB_CHANNEL_PREFIX: &str = "collab_channel_";
+pub const COLLAB_PRESENCE_PREFIX: &str = "collab_presence_";
+pub const COLLAB_LOCK_PREFIX: &str = "collab_lock_";
@@ -0,0 +1,2 @@
+// 模块
+pub const MODULE_PATH_MAX_CHARS: usize = 255; // 模块路径最大字符数
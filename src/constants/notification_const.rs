@@ -0,0 +1,10 @@
+// 通知
+pub const NOTIFICATION_DIGEST_CHECK_SECONDS: u64 = 300; // 摘要任务检查是否到达发送时间的间隔
+pub const NOTIFICATION_DIGEST_MAX_ITEMS: usize = 50; // 摘要邮件中最多列出的通知数
+pub const NOTIFICATION_CONTENT_MAX_CHARS: usize = 200; // 通知内容摘录的长度
+
+// 通知关联对象类型
+pub const NOTIFICATION_TARGET_PHRASE: &str = "phrase";
+pub const NOTIFICATION_TARGET_TRANSLATION: &str = "translation";
+pub const NOTIFICATION_TARGET_COMMENT: &str = "comment";
+pub const NOTIFICATION_TARGET_TASK: &str = "task";
@@ -0,0 +1,2 @@
+// 批量操作
+pub const PHRASE_BATCH_MAX_ITEMS: usize = 500; // 单次批量操作的最大条数
@@ -0,0 +1,4 @@
+// 项目统计
+pub const STATS_DEFAULT_DAYS: u32 = 30; // 完成度时间序列的默认天数
+pub const STATS_MAX_DAYS: u32 = 365; // 完成度时间序列的最大天数
+pub const STATS_CACHE_SECONDS: u64 = 600; // 统计结果缓存时长
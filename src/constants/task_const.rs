@@ -0,0 +1,3 @@
+// 翻译任务
+pub const TASK_MAX_PHRASES: usize = 10000; // 单个任务最多包含的词条数
+pub const TASK_NAME_MAX_CHARS: usize = 100; // 任务名称最大字符数
@@ -5,4 +5,18 @@ pub const USER_PASSWORD_MAX_LENGTH: usize = 32;
 pub const USER_EMAIL_MIN_LENGTH: usize = 5;
 pub const USER_EMAIL_MAX_LENGTH: usize = 32;
 pub const USER_EMAIL_REGEX: &str = r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$";
-pub const USER_PHONE_REGEX: &str = r"^1[3-9]\d{9}$";
\ No newline at end of file
+pub const USER_PHONE_REGEX: &str = r"^1[3-9]\d{9}$";
+
+// 登录失败限制
+pub const LOGIN_MAX_FAILED_ATTEMPTS: u64 = 5; // 单账号连续失败次数上限
+pub const LOGIN_IP_MAX_FAILED_ATTEMPTS: u64 = 20; // 单IP失败次数上限
+pub const LOGIN_LOCK_SECONDS: u64 = 900; // 锁定时长（15分钟）
+pub const LOGIN_DELAY_BASE_MS: u64 = 250; // 失败后的基础延迟
+pub const LOGIN_DELAY_MAX_MS: u64 = 4000; // 失败后的最大延迟
+pub const LOGIN_INVALID_CREDENTIALS: &str = "Invalid username or password";
+pub const LOGIN_TOO_MANY_ATTEMPTS: &str = "Too many failed attempts, please try again later";
+
+// 两步验证
+pub const MFA_CHALLENGE_SECONDS: u64 = 300; // 登录第二步的有效期
+pub const MFA_MAX_ATTEMPTS: u64 = 5; // 单个挑战允许的验证码错误次数
+pub const MFA_INVALID_CODE: &str = "Invalid verification code";
@@ -0,0 +1,21 @@
+// Webhook
+pub const WEBHOOK_MAX_ATTEMPTS: u32 = 8; // 最大投递次数，超过后标记为失败
+pub const WEBHOOK_BACKOFF_BASE_SECONDS: u64 = 30; // 首次重试间隔，之后每次翻倍
+pub const WEBHOOK_BACKOFF_MAX_SECONDS: u64 = 6 * 3600; // 重试间隔上限
+pub const WEBHOOK_TIMEOUT_SECONDS: u64 = 10; // 单次请求超时
+pub const WEBHOOK_POLL_SECONDS: u64 = 5; // 投递任务轮询间隔
+pub const WEBHOOK_BATCH_SIZE: u32 = 50; // 每轮最多投递条数
+pub const WEBHOOK_LEASE_SECONDS: i64 = 60; // 投递中的记录在此时间内不会被其他实例重复领取
+pub const WEBHOOK_RESPONSE_MAX_CHARS: usize = 200; // 投递日志保留的响应内容开头长度
+
+// 事件类型
+pub const WEBHOOK_EVENT_PHRASE_CREATED: &str = "phrase.created";
+pub const WEBHOOK_EVENT_PHRASE_UPDATED: &str = "phrase.updated";
+pub const WEBHOOK_EVENT_PHRASE_DELETED: &str = "phrase.deleted";
+pub const WEBHOOK_EVENT_TRANSLATION_PUBLISHED: &str = "translation.published";
+pub const WEBHOOK_EVENTS: [&str; 4] = [
+    WEBHOOK_EVENT_PHRASE_CREATED,
+    WEBHOOK_EVENT_PHRASE_UPDATED,
+    WEBHOOK_EVENT_PHRASE_DELETED,
+    WEBHOOK_EVENT_TRANSLATION_PUBLISHED,
+];
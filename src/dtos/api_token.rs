@@ -0,0 +1,50 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{api_token::ApiToken, enums::TokenPermission};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiTokenDto {
+    pub name: String,
+    // 为空表示可访问用户有权限的全部项目
+    pub project_ids: Option<Vec<u64>>,
+    pub permission: Option<TokenPermission>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiTokenVo {
+    pub id: u64,
+    pub name: String,
+    pub token_prefix: String,
+    pub project_ids: Option<Vec<u64>>,
+    pub permission: TokenPermission,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked: bool,
+    pub crt_at: DateTime<Utc>,
+}
+
+impl From<&ApiToken> for ApiTokenVo {
+    fn from(token: &ApiToken) -> Self {
+        Self {
+            id: token.id,
+            name: token.name.clone(),
+            token_prefix: token.token_prefix.clone(),
+            project_ids: token.project_id_list(),
+            permission: token.permission,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked: token.revoked_at.is_some(),
+            crt_at: token.crt_at,
+        }
+    }
+}
+
+/// 创建令牌的响应，明文令牌只在创建时返回一次
+#[derive(Debug, Serialize)]
+pub struct CreatedApiTokenVo {
+    pub token: String,
+    #[serde(flatten)]
+    pub info: ApiTokenVo,
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dtos::comment::CommentVo,
+    models::{enums::TranslationStatus, translation::Translation},
+};
+
+/// 客户端发送的消息
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    // 开始编辑词条
+    Focus { phrase_id: u64 },
+    // 离开正在编辑的词条
+    Blur,
+    // 申请词条软锁
+    Lock { phrase_id: u64 },
+    Unlock { phrase_id: u64 },
+    Ping,
+}
+
+/// 在线成员及其正在编辑的词条
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceVo {
+    pub session_id: String,
+    pub user_id: u64,
+    pub username: String,
+    pub phrase_id: Option<u64>,
+    // 最近一次活跃时间（Unix 秒）
+    pub seen_at: i64,
+}
+
+/// 词条软锁：只提示其他成员，不阻止保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhraseLockVo {
+    pub phrase_id: u64,
+    pub session_id: String,
+    pub user_id: u64,
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceState {
+    Joined,
+    Focus,
+    Blur,
+    Left,
+}
+
+/// 服务端推送的消息
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabEvent {
+    // 连接建立后发送给当前会话的在线成员和软锁
+    Snapshot {
+        session_id: String,
+        presence: Vec<PresenceVo>,
+        locks: Vec<PhraseLockVo>,
+    },
+    Presence {
+        state: PresenceState,
+        presence: PresenceVo,
+    },
+    Locked { lock: PhraseLockVo },
+    // 软锁已被其他成员持有，只发送给申请者
+    LockDenied { lock: PhraseLockVo },
+    Unlocked { phrase_id: u64, session_id: String },
+    TranslationEdited { translation: Translation },
+    TranslationStatusChanged {
+        translation_id: u64,
+        phrase_id: u64,
+        language: String,
+        status: TranslationStatus,
+    },
+    // 评论新增、修改、删除、解决或重新打开，action 与操作日志的动作一致
+    Comment { action: String, comment: CommentVo },
+    Pong,
+    Error { message: String },
+}
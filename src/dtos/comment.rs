@@ -0,0 +1,99 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::models::comment::Comment;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCommentDto {
+    // 评论词条本身；指定翻译或回复时可省略
+    pub phrase_id: Option<u64>,
+    pub translation_id: Option<u64>,
+    // 回复的讨论串，回复沿用讨论串的词条和翻译
+    pub parent_id: Option<u64>,
+    pub content: String,
+    pub is_question: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentDto {
+    pub content: Option<String>,
+    pub is_question: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentQuery {
+    pub phrase_id: u64,
+    // 指定时只查询该翻译的评论
+    pub translation_id: Option<u64>,
+}
+
+/// 项目中未解决的讨论串
+#[derive(Debug, Deserialize)]
+pub struct IssueQuery {
+    pub project_id: u64,
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    // 只查询需要开发者解答的问题
+    pub is_question: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionVo {
+    pub user_id: u64,
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentVo {
+    pub id: u64,
+    pub project_id: u64,
+    pub phrase_id: u64,
+    pub translation_id: Option<u64>,
+    pub parent_id: Option<u64>,
+    pub user_id: u64,
+    pub author: String,
+    pub content: String,
+    pub mentions: Vec<MentionVo>,
+    pub is_question: bool,
+    pub is_resolved: bool,
+    pub resolved_by: Option<u64>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub crt_at: NaiveDateTime,
+    pub upt_at: NaiveDateTime,
+}
+
+impl From<&Comment> for CommentVo {
+    fn from(comment: &Comment) -> Self {
+        Self {
+            id: comment.id,
+            project_id: comment.project_id,
+            phrase_id: comment.phrase_id,
+            translation_id: comment.translation_id,
+            parent_id: comment.parent_id,
+            user_id: comment.user_id,
+            author: comment.crt_by.clone(),
+            content: comment.content.clone(),
+            mentions: comment
+                .mentions
+                .clone()
+                .and_then(|mentions| serde_json::from_value(mentions).ok())
+                .unwrap_or_default(),
+            is_question: comment.is_question,
+            is_resolved: comment.is_resolved,
+            resolved_by: comment.resolved_by,
+            resolved_at: comment.resolved_at,
+            crt_at: comment.crt_at,
+            upt_at: comment.upt_at,
+        }
+    }
+}
+
+/// 讨论串：根评论及其回复
+#[derive(Debug, Serialize)]
+pub struct CommentThreadVo {
+    #[serde(flatten)]
+    pub comment: CommentVo,
+    pub replies: Vec<CommentVo>,
+}
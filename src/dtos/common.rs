@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::errors::AppError;
+
 #[derive(Debug, Serialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -21,6 +23,27 @@ pub struct PageRequest {
     pub size: u32,
     // 查询key支持编码、名称搜索
     pub search_key: Option<String>,
+    // 排序，如 "name,-crt_at"，"-" 前缀表示降序
+    pub sort: Option<String>,
+}
+
+/// 批量操作中单条数据的处理结果
+#[derive(Debug, Serialize)]
+pub struct BatchItemResult {
+    // 请求中的下标
+    pub index: usize,
+    pub id: Option<u64>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+/// 批量操作结果，任一条失败时整批回滚，committed 为 false
+#[derive(Debug, Serialize)]
+pub struct BatchResultVo {
+    pub committed: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult>,
 }
 
 #[derive(Debug, Serialize)]
@@ -50,6 +73,37 @@ impl<T> ApiResponse<T> {
     }
 }
 
+impl BatchItemResult {
+    pub fn new(index: usize, result: Result<u64, AppError>) -> Self {
+        match result {
+            Ok(id) => Self {
+                index,
+                id: Some(id),
+                success: true,
+                message: None,
+            },
+            Err(e) => Self {
+                index,
+                id: None,
+                success: false,
+                message: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+impl BatchResultVo {
+    pub fn new(results: Vec<BatchItemResult>) -> Self {
+        let succeeded = results.iter().filter(|r| r.success).count();
+        Self {
+            committed: succeeded == results.len(),
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+}
+
 impl<T> PaginatedResponse<T> {
     pub fn new(items: Vec<T>, total: i64, page: i32, size: i32) -> Self {
         let total_pages = ((total as f64) / (size as f64)).ceil() as i32;
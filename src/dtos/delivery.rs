@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    // 只导出该模块下的词条，如 "checkout"；"checkout/*" 包含其所有子模块
+    pub module: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeliveryMetaVo {
+    // 实际使用的回退链
+    pub chain: Vec<String>,
+    // 回退到其他语言的词条标识符 → 实际使用的语言
+    pub fallbacks: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeliveryVo {
+    pub language: String,
+    // 词条标识符 → 文本
+    pub messages: BTreeMap<String, String>,
+    pub meta: DeliveryMetaVo,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::key_rule::KeyViolation;
+
+/// 整体替换项目的标识符规则，未传的可选项即清空
+#[derive(Debug, Deserialize)]
+pub struct UpdateKeyRuleDto {
+    // 标识符需完整匹配的正则表达式，如 "[a-z0-9_]+(\\.[a-z0-9_]+)*"
+    pub pattern: Option<String>,
+    // 要求以所属模块路径（未设置时为模块名称）为前缀
+    #[serde(default)]
+    pub require_module_prefix: bool,
+    // 层级分隔符，默认为 "."
+    pub separator: Option<String>,
+    pub max_depth: Option<u32>,
+    // 禁止出现的字符，如 " -/"
+    pub forbidden_chars: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeyLintQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub module_id: Option<u64>,
+}
+
+/// 不符合规则的词条及建议的新标识符
+#[derive(Debug, Serialize)]
+pub struct KeyLintItem {
+    pub phrase_id: u64,
+    pub module_id: Option<u64>,
+    pub key: String,
+    pub violations: Vec<KeyViolation>,
+    // 无法自动修正时为空
+    pub suggestion: Option<String>,
+    // 建议的标识符已被占用，或与其他词条的建议重复，需要手动处理
+    pub conflict: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenamePhraseItem {
+    pub id: u64,
+    pub key: String,
+}
+
+/// 批量重命名词条，翻译和截图随词条保留
+#[derive(Debug, Deserialize)]
+pub struct BatchRenamePhraseDto {
+    pub items: Vec<RenamePhraseItem>,
+}
@@ -1,11 +1,13 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::models::language::Language;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateLanguageDto {
     pub name: String,
+    pub native_name: Option<String>,
     pub code: String,
     pub is_active: Option<bool>,
     pub is_native: Option<bool>,
@@ -15,6 +17,7 @@ pub struct CreateLanguageDto {
 #[derive(Debug, Deserialize)]
 pub struct UpdateLanguageDto {
     pub name: Option<String>,
+    pub native_name: Option<String>,
     pub code: Option<String>,
     pub is_active: Option<bool>,
     pub is_native: Option<bool>,
@@ -26,6 +29,9 @@ pub struct LanguageVo {
     pub id: u64,
     pub code: String,
     pub name: String,
+    pub native_name: Option<String>,
+    pub direction: String,
+    pub plural_categories: Option<Value>,
     pub is_active: bool,
     pub is_native: bool,
     pub crt_at: DateTime<Utc>,
@@ -38,6 +44,9 @@ impl From<&Language> for LanguageVo {
             id: language.id,
             code: language.code.clone(),
             name: language.name.clone(),
+            native_name: language.native_name.clone(),
+            direction: language.direction.clone(),
+            plural_categories: language.plural_categories.clone(),
             is_active: language.is_active,
             is_native: language.is_native,
             crt_at: language.crt_at,
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct MfaCodeDto {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MfaChallengeDto {
+    pub challenge_token: String,
+}
+
+/// 登录第二步，code 与 recovery_code 二选一
+#[derive(Debug, Deserialize)]
+pub struct MfaVerifyDto {
+    pub challenge_token: String,
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+/// 密码校验通过但需要两步验证时返回
+#[derive(Debug, Serialize)]
+pub struct MfaChallengeVo {
+    pub mfa_required: bool,
+    pub challenge_token: String,
+    // 为 false 时需先调用 /auth/mfa/setup 完成绑定
+    pub enrolled: bool,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaSetupVo {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaStatusVo {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesVo {
+    pub recovery_codes: Vec<String>,
+}
@@ -1,10 +1,22 @@
+pub mod api_token;
+pub mod collab;
+pub mod comment;
 pub mod common;
+pub mod delivery;
+pub mod key_rule;
 pub mod language;
+pub mod mfa;
 pub mod module;
+pub mod notification;
 pub mod phrase;
 pub mod project;
+pub mod report;
 pub mod screenshot;
+pub mod search;
+pub mod stats;
+pub mod task;
 pub mod term;
 pub mod translation;
 pub mod user;
+pub mod webhook;
 pub mod phrase_type;
@@ -4,6 +4,8 @@ use validator::Validate;
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateModuleDto {
     pub project_id: u64,
+    // 父模块，为空时创建根模块
+    pub parent_id: Option<u64>,
     #[validate(length(min = 1, max = 100))]
     pub name: String,
     pub description: Option<String>,
@@ -30,4 +32,42 @@ pub struct ModuleQuery {
     pub name: Option<String>,
     pub page: u32,
     pub size: u32,
+    pub sort: Option<String>,
+}
+
+/// 移动模块及其子模块，parent_id 为空时移动到根级
+#[derive(Debug, Deserialize)]
+pub struct MoveModuleDto {
+    pub parent_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModuleTreeQuery {
+    pub project_id: u64,
+}
+
+/// 模块在某个目标语言下的进度，包含子模块
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleProgressVo {
+    pub language: String,
+    // 已翻译（待审核、已审核或已发布）的词条数
+    pub translated: u64,
+    // 已审核或已发布的词条数
+    pub reviewed: u64,
+    // 已翻译占全部词条的百分比
+    pub percent: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleTreeNodeVo {
+    pub id: u64,
+    pub parent_id: Option<u64>,
+    pub name: String,
+    pub path: String,
+    // 直接归属该模块的词条数
+    pub phrase_count: u64,
+    // 包含子模块的词条数
+    pub total_phrase_count: u64,
+    pub progress: Vec<ModuleProgressVo>,
+    pub children: Vec<ModuleTreeNodeVo>,
 }
@@ -0,0 +1,89 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    enums::NotificationKind, notification::Notification,
+    notification_preference::NotificationPreference,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub kind: Option<NotificationKind>,
+    // 只查询未读通知
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePreferenceDto {
+    pub muted_kinds: Option<Vec<NotificationKind>>,
+    pub email_digest: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationVo {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub content: Option<String>,
+    pub project_id: Option<u64>,
+    pub target_type: String,
+    pub target_id: u64,
+    pub actor_id: Option<u64>,
+    pub actor: Option<String>,
+    pub is_read: bool,
+    pub read_at: Option<NaiveDateTime>,
+    pub crt_at: NaiveDateTime,
+}
+
+impl From<&Notification> for NotificationVo {
+    fn from(notification: &Notification) -> Self {
+        Self {
+            id: notification.id,
+            kind: notification.kind,
+            title: notification.title.clone(),
+            content: notification.content.clone(),
+            project_id: notification.project_id,
+            target_type: notification.target_type.clone(),
+            target_id: notification.target_id,
+            actor_id: notification.actor_id,
+            actor: notification.actor.clone(),
+            is_read: notification.is_read,
+            read_at: notification.read_at,
+            crt_at: notification.crt_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnreadCountVo {
+    pub unread: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreferenceVo {
+    pub muted_kinds: Vec<NotificationKind>,
+    pub email_digest: bool,
+}
+
+impl Default for PreferenceVo {
+    fn default() -> Self {
+        Self {
+            muted_kinds: vec![],
+            email_digest: true,
+        }
+    }
+}
+
+impl From<&NotificationPreference> for PreferenceVo {
+    fn from(preference: &NotificationPreference) -> Self {
+        Self {
+            muted_kinds: preference.muted_kind_list(),
+            email_digest: preference.email_digest,
+        }
+    }
+}
@@ -1,8 +1,11 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub struct CreatePhraseDto {
     pub project_id: u64,
+    // 所属模块，须属于同一项目
+    pub module_id: Option<u64>,
     pub key: String,
     pub source_text: String,
     pub context: Option<String>,
@@ -17,9 +20,92 @@ pub struct PhraseDto {
     pub context: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BatchCreatePhraseDto {
+    pub items: Vec<CreatePhraseDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchUpdatePhraseItem {
+    pub id: u64,
+    pub key: Option<String>,
+    pub source_text: Option<String>,
+    pub context: Option<String>,
+    // 移动到同一项目下的其他模块
+    pub module_id: Option<u64>,
+    pub type_id: Option<u64>,
+    pub platforms: Option<Vec<String>>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+    // 原文微调（如修正错别字），不将已有翻译标记为过期
+    #[serde(default)]
+    pub minor_edit: bool,
+    pub version: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchUpdatePhraseDto {
+    pub items: Vec<BatchUpdatePhraseItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchDeletePhraseDto {
+    pub ids: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PhraseQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub project_id: Option<u64>,
+    pub module_id: Option<u64>,
+    // 模块路径，如 "checkout"；"checkout/*" 包含其所有子模块
+    pub module: Option<String>,
+    pub type_id: Option<u64>,
+    // 逗号分隔，包含任一标签即匹配
+    pub tags: Option<String>,
+    // 逗号分隔，适用任一平台即匹配
+    pub platforms: Option<String>,
+    // 按词条标识符、基础语言内容搜索
+    pub search_key: Option<String>,
+    pub crt_by: Option<String>,
+    // 只返回该时间之后更新过的词条
+    pub updated_since: Option<NaiveDateTime>,
+    // 与 untranslated / status 配合使用的目标语言
+    pub language: Option<String>,
+    // 只返回在 language 下没有翻译的词条
+    #[serde(default)]
+    pub untranslated: bool,
+    // 只返回在 language 下翻译状态为该值的词条：pending / reviewed / published / outdated
+    pub status: Option<String>,
+    // 排序，如 "key,-upt_at"
+    pub sort: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PhraseTrashQuery {
+    pub project_id: Option<u64>,
+    // 按词条标识符、基础语言内容搜索
+    pub search_key: Option<String>,
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub sort: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdatePhraseDto {
     pub key: Option<String>,
     pub source_text: Option<String>,
     pub context: Option<String>,
+    // 原文微调（如修正错别字），不将已有翻译标记为过期
+    #[serde(default)]
+    pub minor_edit: bool,
+    // 客户端读取时的版本号，也可通过 If-Match 请求头传入
+    pub version: Option<u32>,
 }
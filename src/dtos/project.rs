@@ -23,6 +23,29 @@ pub struct UpdateProjectDto {
     pub upt_by: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AddProjectLanguageDto {
+    pub language: String,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLanguageFallbacksDto {
+    // 按顺序尝试的回退语言，为空时恢复为 BCP-47 父级语言
+    pub fallbacks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectLanguageVo {
+    pub language: String,
+    pub is_default: bool,
+    // 直接回退的语言
+    pub fallbacks: Vec<String>,
+    // 展开后的完整回退链，以基础语言结尾
+    pub chain: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ProjectVo {
     pub id: u64,
@@ -34,6 +57,8 @@ pub struct ProjectVo {
     pub status: bool,
     pub crt_at: DateTime<Utc>,
     pub upt_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<&Project> for ProjectVo {
@@ -48,6 +73,7 @@ impl From<&Project> for ProjectVo {
             status: project.status,
             crt_at: project.crt_at,
             upt_at: project.upt_at,
+            deleted_at: project.deleted_at,
         }
     }
 }
@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    // 起止日期，均包含在内
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub project_id: Option<u64>,
+    pub user_id: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TranslatorReportVo {
+    pub user_id: u64,
+    pub username: String,
+    // 提交的翻译内容次数
+    pub translations: u64,
+    pub words_translated: u64,
+    // 审核通过或退回的次数
+    pub reviews: u64,
+    // 提交的翻译被退回的次数
+    pub rejections: u64,
+    // 被退回次数 / 提交次数
+    pub rejection_rate: f64,
+    // 从创建到首次发布的平均小时数
+    pub avg_hours_to_publish: Option<f64>,
+    // 审核后再次修改内容的次数
+    pub rework_count: u64,
+}
+
+impl TranslatorReportVo {
+    pub const CSV_HEADERS: [&'static str; 9] = [
+        "user_id",
+        "username",
+        "translations",
+        "words_translated",
+        "reviews",
+        "rejections",
+        "rejection_rate",
+        "avg_hours_to_publish",
+        "rework_count",
+    ];
+
+    pub fn to_csv_row(&self) -> Vec<String> {
+        vec![
+            self.user_id.to_string(),
+            self.username.clone(),
+            self.translations.to_string(),
+            self.words_translated.to_string(),
+            self.reviews.to_string(),
+            self.rejections.to_string(),
+            self.rejection_rate.to_string(),
+            self.avg_hours_to_publish.map(|h| h.to_string()).unwrap_or_default(),
+            self.rework_count.to_string(),
+        ]
+    }
+}
@@ -12,3 +12,16 @@ pub struct UpdateScreenshotDto {
     pub image_url: Option<String>,
     pub description: Option<String>,
 }
+
+/// 截图列表查询
+#[derive(Debug, Deserialize)]
+pub struct ScreenshotQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    // 按词条所属项目过滤
+    pub project_id: Option<u64>,
+    pub phrase_id: Option<u64>,
+    pub sort: Option<String>,
+}
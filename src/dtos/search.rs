@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::search_hit::SearchHit;
+
+/// 检索来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Phrase,
+    Translation,
+    Term,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 3] = [SearchKind::Phrase, SearchKind::Translation, SearchKind::Term];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "phrase" => Some(SearchKind::Phrase),
+            "translation" => Some(SearchKind::Translation),
+            "term" => Some(SearchKind::Term),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    // 搜索关键字，空格分隔的多个词需同时命中
+    pub q: String,
+    pub project_id: Option<u64>,
+    // 只作用于翻译和术语
+    pub language: Option<String>,
+    // 模块路径，如 "checkout"；"checkout/*" 包含其所有子模块。指定后不检索术语
+    pub module: Option<String>,
+    // 逗号分隔：phrase / translation / term，默认全部
+    pub types: Option<String>,
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHitVo {
+    pub kind: String,
+    pub id: u64,
+    pub project_id: u64,
+    pub phrase_id: Option<u64>,
+    pub language: Option<String>,
+    // 高亮后的词条标识符
+    pub key: Option<String>,
+    // 高亮后的内容片段
+    pub snippet: String,
+    pub score: f64,
+}
+
+impl SearchHitVo {
+    pub fn new(hit: SearchHit, key: Option<String>, snippet: String) -> Self {
+        Self {
+            kind: hit.kind,
+            id: hit.id,
+            project_id: hit.project_id,
+            phrase_id: hit.phrase_id,
+            language: hit.language,
+            key,
+            snippet,
+            score: hit.score,
+        }
+    }
+}
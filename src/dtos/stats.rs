@@ -0,0 +1,64 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct StatsQuery {
+    // 完成度时间序列的天数
+    pub days: Option<u32>,
+}
+
+/// 按模块或平台细分的翻译进度
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StatsBreakdownVo {
+    // 模块ID，平台细分时为空
+    pub id: Option<u64>,
+    // 模块名称或平台
+    pub name: String,
+    pub total: u64,
+    pub pending: u64,
+    pub reviewed: u64,
+    pub published: u64,
+    // 原文已修改，需要重新确认
+    pub outdated: u64,
+    pub percent_complete: f64,
+}
+
+/// 某一天结束时的完成度
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsTimelineVo {
+    pub date: NaiveDate,
+    pub total: u64,
+    pub published: u64,
+    pub percent_complete: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LanguageStatsVo {
+    pub language: String,
+    pub total: u64,
+    pub untranslated: u64,
+    pub pending: u64,
+    pub reviewed: u64,
+    pub published: u64,
+    // 原文已修改，需要重新确认
+    pub outdated: u64,
+    pub translated_words: u64,
+    pub translated_chars: u64,
+    // 已发布占全部词条的百分比
+    pub percent_complete: f64,
+    pub modules: Vec<StatsBreakdownVo>,
+    pub platforms: Vec<StatsBreakdownVo>,
+    pub timeline: Vec<StatsTimelineVo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectStatsVo {
+    pub project_id: u64,
+    pub base_language: String,
+    pub total_phrases: u64,
+    // 基础语言的字数和字符数
+    pub source_words: u64,
+    pub source_chars: u64,
+    pub languages: Vec<LanguageStatsVo>,
+    pub generated_at: DateTime<Utc>,
+}
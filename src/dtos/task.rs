@@ -0,0 +1,149 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{enums::TaskStatus, task::Task};
+
+/// 任务包含的词条范围，各条件同时满足；都为空时包含项目的全部词条
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskScopeDto {
+    pub module_id: Option<u64>,
+    pub type_id: Option<u64>,
+    // 包含任一标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // 按 key 或原文搜索
+    pub search_key: Option<String>,
+    // 指定的词条
+    #[serde(default)]
+    pub phrase_ids: Vec<u64>,
+    // 只包含目标语言尚未翻译的词条
+    #[serde(default)]
+    pub untranslated_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTaskDto {
+    pub project_id: u64,
+    pub language: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub translator_id: u64,
+    pub reviewer_id: Option<u64>,
+    pub due_at: NaiveDateTime,
+    #[serde(default)]
+    pub scope: TaskScopeDto,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaskDto {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub translator_id: Option<u64>,
+    pub reviewer_id: Option<u64>,
+    pub due_at: Option<NaiveDateTime>,
+    // 只能取消或重新打开，完成状态由进度决定
+    pub status: Option<TaskStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub project_id: Option<u64>,
+    pub language: Option<String>,
+    pub status: Option<TaskStatus>,
+    // 译者或审核人
+    pub assignee_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MyTaskQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub status: Option<TaskStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OverdueTaskQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub project_id: Option<u64>,
+    pub assignee_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskPhraseQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskProgressVo {
+    pub total: u32,
+    pub translated: u32,
+    pub reviewed: u32,
+    // 完成百分比，需要审核时按已审核计算
+    pub percent: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskVo {
+    pub id: u64,
+    pub project_id: u64,
+    pub language: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub translator_id: u64,
+    pub reviewer_id: Option<u64>,
+    pub due_at: NaiveDateTime,
+    pub status: TaskStatus,
+    pub is_overdue: bool,
+    pub progress: TaskProgressVo,
+    pub completed_at: Option<NaiveDateTime>,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_at: NaiveDateTime,
+}
+
+impl From<&Task> for TaskVo {
+    fn from(task: &Task) -> Self {
+        let finished = match task.reviewer_id {
+            Some(_) => task.reviewed,
+            None => task.translated,
+        };
+        let percent = match task.total {
+            0 => 0,
+            total => finished.min(total) * 100 / total,
+        };
+        Self {
+            id: task.id,
+            project_id: task.project_id,
+            language: task.language.clone(),
+            name: task.name.clone(),
+            description: task.description.clone(),
+            translator_id: task.translator_id,
+            reviewer_id: task.reviewer_id,
+            due_at: task.due_at,
+            status: task.status,
+            is_overdue: task.is_overdue(Local::now().naive_local()),
+            progress: TaskProgressVo {
+                total: task.total,
+                translated: task.translated,
+                reviewed: task.reviewed,
+                percent,
+            },
+            completed_at: task.completed_at,
+            crt_by: task.crt_by.clone(),
+            crt_at: task.crt_at,
+            upt_at: task.upt_at,
+        }
+    }
+}
@@ -25,3 +25,18 @@ pub struct UpdateTermDto {
     pub target_term: Option<String>,
     pub description: Option<String>,
 }
+
+/// 术语列表查询
+#[derive(Debug, Deserialize)]
+pub struct TermQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub project_id: Option<u64>,
+    pub language: Option<String>,
+    // 按源术语、目标术语搜索
+    pub search_key: Option<String>,
+    // 排序，如 "source_term,-crt_at"
+    pub sort: Option<String>,
+}
@@ -1,8 +1,9 @@
 use crate::models::enums::TranslationStatus;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateTranslationDto {
+    pub phrase_id: u64,
     pub language_code: String,
     pub translated_text: String,
 }
@@ -14,4 +15,35 @@ pub struct UpdateTranslationDto {
     pub status: Option<TranslationStatus>,
     pub translated_by: Option<u64>,
     pub reviewed_by: Option<u64>,
+    // 客户端读取时的版本号，也可通过 If-Match 请求头传入
+    pub version: Option<u32>,
+}
+
+/// 翻译工作台的原文对比：翻译所依据的原文和当前原文
+#[derive(Debug, Serialize)]
+pub struct TranslationSourceVo {
+    pub translation_id: u64,
+    pub phrase_id: u64,
+    pub status: TranslationStatus,
+    // 当前原文及其版本号
+    pub source_version: u32,
+    pub current_source: String,
+    // 翻译所依据的原文版本号，与当前版本不同时 previous_source 为当时的原文
+    pub translated_source_version: u32,
+    pub previous_source: Option<String>,
+}
+
+/// 翻译列表查询
+#[derive(Debug, Deserialize)]
+pub struct TranslationQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    // 按词条所属项目过滤
+    pub project_id: Option<u64>,
+    pub phrase_id: Option<u64>,
+    pub language: Option<String>,
+    pub status: Option<TranslationStatus>,
+    pub sort: Option<String>,
 }
@@ -80,6 +80,8 @@ pub struct UserVo {
     pub avatar: Option<String>,
     pub status: bool,
     pub last_login: Option<NaiveDateTime>,
+    pub locked_until: Option<NaiveDateTime>,
+    pub mfa_enabled: bool,
     pub crt_at: DateTime<Utc>,
     pub upt_at: DateTime<Utc>,
 }
@@ -95,6 +97,8 @@ impl From<&User> for UserVo {
             avatar: user.avatar.clone(),
             status: user.status,
             last_login: user.last_login,
+            locked_until: user.locked_until,
+            mfa_enabled: user.totp_enabled,
             crt_at: user.crt_at,
             upt_at: user.upt_at,
         }
@@ -109,6 +113,10 @@ pub struct PageUserRequest {
     pub size: u32,
     // 查询key支持用户名、手机号、邮箱、昵称、真实姓名搜索
     pub search_key: Option<String>,
+    // 按状态过滤
+    pub status: Option<bool>,
+    // 排序，如 "username,-crt_at"，"-" 前缀表示降序
+    pub sort: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,120 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    constants::WEBHOOK_RESPONSE_MAX_CHARS,
+    models::{enums::DeliveryStatus, webhook::Webhook, webhook_delivery::WebhookDelivery},
+    utils::webhook,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookDto {
+    pub project_id: u64,
+    // 接收地址，必须是 http(s) 地址，且不能指向内网
+    pub url: String,
+    // 订阅的事件类型，如 phrase.created、translation.published
+    pub events: Vec<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookDto {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookQuery {
+    pub project_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    #[serde(default = "crate::utils::default_page")]
+    pub page: u32,
+    #[serde(default = "crate::utils::default_size")]
+    pub size: u32,
+    pub status: Option<DeliveryStatus>,
+}
+
+/// Webhook 信息，不包含签名密钥
+#[derive(Debug, Serialize)]
+pub struct WebhookVo {
+    pub id: u64,
+    pub project_id: u64,
+    pub url: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_at: NaiveDateTime,
+}
+
+impl From<&Webhook> for WebhookVo {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            project_id: webhook.project_id,
+            url: webhook.url.clone(),
+            events: webhook.event_list(),
+            is_active: webhook.is_active,
+            crt_by: webhook.crt_by.clone(),
+            crt_at: webhook.crt_at,
+            upt_at: webhook.upt_at,
+        }
+    }
+}
+
+/// 创建 Webhook 或重置密钥的响应，签名密钥只在此时返回
+#[derive(Debug, Serialize)]
+pub struct WebhookSecretVo {
+    pub secret: String,
+    #[serde(flatten)]
+    pub info: WebhookVo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryVo {
+    pub id: u64,
+    pub webhook_id: u64,
+    pub event_id: String,
+    pub event: String,
+    pub payload: Value,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub response_status: Option<i32>,
+    // 响应内容的开头，控制字符已替换
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub delivered_at: Option<NaiveDateTime>,
+    pub redelivery_of: Option<u64>,
+    pub crt_at: NaiveDateTime,
+}
+
+impl From<&WebhookDelivery> for WebhookDeliveryVo {
+    fn from(delivery: &WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            event_id: delivery.event_id.clone(),
+            event: delivery.event.clone(),
+            payload: serde_json::from_str(&delivery.payload)
+                .unwrap_or_else(|_| Value::String(delivery.payload.clone())),
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            response_status: delivery.response_status,
+            response_body: delivery
+                .response_body
+                .as_deref()
+                .map(|body| webhook::excerpt(body, WEBHOOK_RESPONSE_MAX_CHARS)),
+            error: delivery.error.clone(),
+            delivered_at: delivery.delivered_at,
+            redelivery_of: delivery.redelivery_of,
+            crt_at: delivery.crt_at,
+        }
+    }
+}
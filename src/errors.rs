@@ -4,6 +4,8 @@ use serde::Serialize;
 use std::fmt;
 use validator::ValidationErrors;
 
+use crate::utils::CONFLICT_CODE;
+
 #[derive(Debug)]
 pub enum AppError {
     Auth(String),
@@ -20,6 +22,9 @@ pub enum AppError {
     CasbinError(String),
     Base64Error(String),
     BusinessError(String),
+    Cache(String),
+    // 乐观锁冲突，附带服务端当前数据
+    Conflict(String, serde_json::Value),
 }
 
 impl fmt::Display for AppError {
@@ -39,6 +44,8 @@ impl fmt::Display for AppError {
             AppError::CasbinError(msg) => msg,
             AppError::Base64Error(msg) => msg,
             AppError::BusinessError(msg) => msg,
+            AppError::Cache(msg) => msg,
+            AppError::Conflict(msg, _) => msg,
         };
         write!(f, "{}", message)
     }
@@ -48,10 +55,19 @@ impl fmt::Display for AppError {
 struct ErrorResponse<'a> {
     pub code: i32,
     pub message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<&'a serde_json::Value>,
 }
 
 impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
+        if let AppError::Conflict(msg, current) = self {
+            return HttpResponse::build(StatusCode::CONFLICT).json(ErrorResponse {
+                code: CONFLICT_CODE,
+                message: msg,
+                data: Some(current),
+            });
+        }
         let (status, message) = match self {
             AppError::Auth(msg) => (StatusCode::OK, msg),
             AppError::Forbidden(msg) => (StatusCode::OK, msg),
@@ -67,11 +83,14 @@ impl ResponseError for AppError {
             AppError::CasbinError(msg) => (StatusCode::OK, msg),
             AppError::Base64Error(msg) => (StatusCode::OK, msg),
             AppError::BusinessError(msg) => (StatusCode::OK, msg),
+            AppError::Cache(msg) => (StatusCode::OK, msg),
+            AppError::Conflict(msg, _) => (StatusCode::CONFLICT, msg),
         };
 
         HttpResponse::build(status).json(ErrorResponse {
             code: 500,
             message,
+            data: None,
         })
     }
 }
@@ -103,6 +122,12 @@ impl From<base64::DecodeError> for AppError {
     }
 }
 
+impl From<redis::RedisError> for AppError {
+    fn from(err: redis::RedisError) -> Self {
+        AppError::Cache(format!("Redis error: {}", err))
+    }
+}
+
 impl<E> From<SdkError<E>> for AppError {
     fn from(err: SdkError<E>) -> Self {
         AppError::Storage(err.to_string())
@@ -17,15 +17,29 @@ mod services;
 mod utils;
 
 use crate::config::SETTINGS;
+use crate::services::api_token_service::ApiTokenService;
+use crate::services::collab_service::CollabHub;
+use crate::services::comment_service::CommentService;
+use crate::services::delivery_service::DeliveryService;
+use crate::services::key_rule_service::KeyRuleService;
 use crate::services::language_service::LanguageService;
+use crate::services::mfa_service::MfaService;
 use crate::services::module_service::ModuleService;
+use crate::services::notification_service::NotificationService;
+use crate::services::oidc_service::OidcService;
 use crate::services::phrase_service::PhraseService;
 use crate::services::phrase_type_service::PhraseTypeService;
 use crate::services::project_service::ProjectService;
+use crate::services::report_service::ReportService;
 use crate::services::screenshot_service::ScreenshotService;
+use crate::services::search_service::SearchService;
+use crate::services::stats_service::StatsService;
+use crate::services::task_service::TaskService;
 use crate::services::term_service::TermService;
 use crate::services::translation_service::TranslationService;
+use crate::services::trash_service::TrashService;
 use crate::services::user_service::UserService;
+use crate::services::webhook_service::WebhookService;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -48,6 +62,10 @@ async fn main() -> std::io::Result<()> {
     config::init();
     info!("配置加载完成");
 
+    // 加载JWT签名密钥
+    utils::jwt::init();
+    info!("JWT密钥加载完成");
+
     // 初始化数据库连接池
     let db_pool = Arc::new(
         utils::database::init()
@@ -66,6 +84,26 @@ async fn main() -> std::io::Result<()> {
         redis_client,
     });
 
+    // 定期清理回收站中超过保留期的数据
+    let trash_service = TrashService::new(state.mysql_pool.clone());
+    actix_web::rt::spawn(trash_service.run_purge_schedule());
+    info!("回收站清理任务已启动");
+
+    // 异步投递 Webhook，失败的按指数退避重试
+    let webhook_service = WebhookService::new(state.mysql_pool.clone());
+    actix_web::rt::spawn(webhook_service.run_delivery_schedule());
+    info!("Webhook 投递任务已启动");
+
+    // 每天定时发送未读通知的邮件摘要
+    let notification_service = NotificationService::new(state.mysql_pool.clone());
+    actix_web::rt::spawn(notification_service.run_digest_schedule());
+    info!("通知摘要任务已启动");
+
+    // 实时协作：订阅 Redis 频道，将消息转发给本实例上的 WebSocket 连接
+    let collab_hub = Arc::new(CollabHub::new(state.redis_client.clone()));
+    actix_web::rt::spawn(collab_hub.clone().run_subscriber());
+    info!("实时协作频道订阅任务已启动");
+
     // 启动HTTP服务器
     info!("正在启动服务器...");
     HttpServer::new(move || {
@@ -80,25 +118,69 @@ async fn main() -> std::io::Result<()> {
             .wrap(cors)
             .wrap(Logger::default())
             .app_data(web::Data::new(state.clone()))
+            .app_data(web::Data::from(collab_hub.clone()))
+            .app_data(web::Data::new(ApiTokenService::new(
+                state.mysql_pool.clone(),
+            )))
+            .app_data(web::Data::new(CommentService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(DeliveryService::new(
+                state.mysql_pool.clone(),
+            )))
+            .app_data(web::Data::new(KeyRuleService::new(state.mysql_pool.clone())))
             .app_data(web::Data::new(LanguageService::new(
                 state.mysql_pool.clone(),
             )))
-            .app_data(web::Data::new(ModuleService::new(state.mysql_pool.clone())))
-            .app_data(web::Data::new(PhraseService::new(state.mysql_pool.clone())))
+            .app_data(web::Data::new(MfaService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(ModuleService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(NotificationService::new(
+                state.mysql_pool.clone(),
+            )))
+            .app_data(web::Data::new(OidcService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(PhraseService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
             .app_data(web::Data::new(PhraseTypeService::new(
                 state.mysql_pool.clone(),
             )))
             .app_data(web::Data::new(ProjectService::new(
                 state.mysql_pool.clone(),
+                state.redis_client.clone(),
             )))
+            .app_data(web::Data::new(ReportService::new(state.mysql_pool.clone())))
             .app_data(web::Data::new(ScreenshotService::new(
                 state.mysql_pool.clone(),
             )))
+            .app_data(web::Data::new(SearchService::new(state.mysql_pool.clone())))
+            .app_data(web::Data::new(StatsService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(TaskService::new(state.mysql_pool.clone())))
             .app_data(web::Data::new(TermService::new(state.mysql_pool.clone())))
             .app_data(web::Data::new(TranslationService::new(
                 state.mysql_pool.clone(),
+                state.redis_client.clone(),
+            )))
+            .app_data(web::Data::new(UserService::new(
+                state.mysql_pool.clone(),
+                state.redis_client.clone(),
             )))
-            .app_data(web::Data::new(UserService::new(state.mysql_pool.clone())))
+            .app_data(web::Data::new(WebhookService::new(state.mysql_pool.clone())))
+            // 公开的令牌校验公钥
+            .service(web::scope("/.well-known").configure(routes::well_known_routes))
             // API路由
             .service(
                 web::scope("/api")
@@ -108,9 +190,18 @@ async fn main() -> std::io::Result<()> {
                     .service(web::scope("/language").configure(routes::language_routes))
                     .service(web::scope("/module").configure(routes::module_routes))
                     .service(web::scope("/phrase").configure(routes::phrase_routes))
+                    .service(web::scope("/key-rule").configure(routes::key_rule_routes))
                     .service(web::scope("/translation").configure(routes::translation_routes))
                     .service(web::scope("/term").configure(routes::term_routes))
-                    .service(web::scope("/screenshot").configure(routes::screenshot_routes)),
+                    .service(web::scope("/screenshot").configure(routes::screenshot_routes))
+                    .service(web::scope("/search").configure(routes::search_routes))
+                    .service(web::scope("/delivery").configure(routes::delivery_routes))
+                    .service(web::scope("/report").configure(routes::report_routes))
+                    .service(web::scope("/webhook").configure(routes::webhook_routes))
+                    .service(web::scope("/collab").configure(routes::collab_routes))
+                    .service(web::scope("/comment").configure(routes::comment_routes))
+                    .service(web::scope("/notification").configure(routes::notification_routes))
+                    .service(web::scope("/task").configure(routes::task_routes)),
             )
             // 健康检查
             .route("/health", web::get().to(health_check))
@@ -1,13 +1,16 @@
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpMessage,
+    http::{header, Method},
+    web, Error, HttpMessage,
 };
 use futures::future::LocalBoxFuture;
-use jsonwebtoken::{decode, DecodingKey, Validation};
 use std::future::{ready, Ready};
+use std::rc::Rc;
 
+use crate::constants::COLLAB_TOKEN_PROTOCOL;
 use crate::errors::AppError;
-use crate::utils::jwt::Claims;
+use crate::services::api_token_service::ApiTokenService;
+use crate::utils::jwt;
 
 pub struct Authentication;
 
@@ -19,7 +22,7 @@ impl Default for Authentication {
 
 impl<S, B> Transform<S, ServiceRequest> for Authentication
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -30,17 +33,24 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ready(Ok(AuthenticationMiddleware { service }))
+        ready(Ok(AuthenticationMiddleware {
+            service: Rc::new(service),
+        }))
     }
 }
 
 pub struct AuthenticationMiddleware<S> {
-    service: S,
+    service: Rc<S>,
+}
+
+enum Credential {
+    Bearer(String),
+    Token(String),
 }
 
 impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -51,7 +61,7 @@ where
     forward_ready!(service);
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
-        let token = match req.headers().get("Authorization") {
+        let credential = match req.headers().get("Authorization") {
             Some(auth_header) => {
                 let auth_str = auth_header
                     .to_str()
@@ -59,33 +69,47 @@ where
 
                 match auth_str {
                     Ok(str) => {
-                        if !str.starts_with("Bearer ") {
+                        if let Some(token) = str.strip_prefix("Bearer ") {
+                            Credential::Bearer(token.to_string())
+                        } else if let Some(token) = str.strip_prefix("Token ") {
+                            Credential::Token(token.to_string())
+                        } else {
                             return Box::pin(ready(Err(AppError::Unauthorized(
                                 "Invalid Authorization header format".into(),
                             )
                             .into())));
                         }
-                        str[7..].to_string()
                     }
                     Err(e) => return Box::pin(ready(Err(e.into()))),
                 }
             }
-            None => {
+            // 浏览器建立 WebSocket 连接时无法设置 Authorization 请求头，允许通过子协议携带访问令牌
+            None => match websocket_access_token(&req) {
+                Some(token) => Credential::Bearer(token),
+                None => {
+                    return Box::pin(ready(Err(AppError::Unauthorized(
+                        "Missing Authorization header".into(),
+                    )
+                    .into())))
+                }
+            },
+        };
+
+        let token = match credential {
+            Credential::Bearer(token) => token,
+            Credential::Token(token) => return self.call_with_api_token(req, token),
+        };
+
+        let claims = match jwt::verify_token(&token) {
+            // 刷新令牌只能用于换取新令牌
+            Ok(claims) if claims.refresh == Some(true) => {
                 return Box::pin(ready(Err(AppError::Unauthorized(
-                    "Missing Authorization header".into(),
+                    "Refresh token cannot be used for authentication".into(),
                 )
                 .into())))
             }
-        };
-
-        let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
-        let claims = match decode::<Claims>(
-            &token,
-            &DecodingKey::from_secret(secret.as_bytes()),
-            &Validation::default(),
-        ) {
-            Ok(token_data) => token_data.claims,
-            Err(e) => return Box::pin(ready(Err(AppError::Unauthorized(e.to_string()).into()))),
+            Ok(claims) => claims,
+            Err(e) => return Box::pin(ready(Err(e.into()))),
         };
 
         req.extensions_mut().insert(claims);
@@ -97,3 +121,63 @@ where
         })
     }
 }
+
+impl<S, B> AuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    /// API 令牌认证，需要查库校验，因此在异步块中完成
+    fn call_with_api_token(
+        &self,
+        req: ServiceRequest,
+        token: String,
+    ) -> LocalBoxFuture<'static, Result<ServiceResponse<B>, Error>> {
+        let service = self.service.clone();
+        Box::pin(async move {
+            let token_service = req
+                .app_data::<web::Data<ApiTokenService>>()
+                .cloned()
+                .ok_or_else(|| AppError::Internal("ApiTokenService not configured".into()))?;
+            let (claims, scope) = token_service.authenticate(&token).await?;
+
+            // 只读令牌只允许安全方法
+            let is_read = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+            if !is_read && !scope.allows_write() {
+                return Err(AppError::Forbidden("API token is read-only".into()).into());
+            }
+
+            req.extensions_mut().insert(claims);
+            req.extensions_mut().insert(scope);
+            service.call(req).await
+        })
+    }
+}
+
+/// WebSocket 升级请求中以子协议携带的访问令牌，格式为 `Sec-WebSocket-Protocol: access_token, <token>`
+///
+/// 不接受查询参数中的令牌，避免被访问日志和代理记录
+fn websocket_access_token(req: &ServiceRequest) -> Option<String> {
+    let is_websocket = req
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+    if !is_websocket {
+        return None;
+    }
+    let protocols = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)?
+        .to_str()
+        .ok()?;
+    let mut protocols = protocols.split(',').map(str::trim);
+    if protocols.next()? != COLLAB_TOKEN_PROTOCOL {
+        return None;
+    }
+    protocols
+        .next()
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+use crate::models::enums::TokenPermission;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiToken {
+    pub id: u64,
+    // 所属用户id
+    pub user_id: u64,
+    // 令牌名称
+    pub name: String,
+    // 令牌前缀
+    pub token_prefix: String,
+    // 令牌哈希
+    pub token_hash: String,
+    // 可访问的项目id列表
+    pub project_ids: Option<Value>,
+    // 读写权限
+    pub permission: TokenPermission,
+    // 过期时间
+    pub expires_at: Option<NaiveDateTime>,
+    // 最后使用时间
+    pub last_used_at: Option<NaiveDateTime>,
+    // 吊销时间
+    pub revoked_at: Option<NaiveDateTime>,
+    pub crt_by: String,
+    pub crt_at: DateTime<Utc>,
+    pub upt_by: Option<String>,
+    pub upt_at: DateTime<Utc>,
+}
+
+impl ApiToken {
+    /// 令牌可访问的项目id，None 表示不限
+    pub fn project_id_list(&self) -> Option<Vec<u64>> {
+        self.project_ids
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+/// 词条或翻译的评论
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Comment {
+    pub id: u64,
+    pub project_id: u64,
+    pub phrase_id: u64,
+    // 为空表示评论词条本身
+    pub translation_id: Option<u64>,
+    // 根评论id，为空表示根评论
+    pub parent_id: Option<u64>,
+    // 评论人id
+    pub user_id: u64,
+    pub content: String,
+    // @提及的用户，如 [{"user_id": 1, "username": "alice"}]
+    pub mentions: Option<Value>,
+    // 需要开发者解答的问题
+    pub is_question: bool,
+    // 讨论串是否已解决，只对根评论有意义
+    pub is_resolved: bool,
+    pub resolved_by: Option<u64>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_by: Option<String>,
+    pub upt_at: NaiveDateTime,
+    // 删除时间，不为空表示已删除
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl Comment {
+    pub fn is_root(&self) -> bool {
+        self.parent_id.is_none()
+    }
+
+    /// 被@提及的用户id
+    pub fn mentioned_user_ids(&self) -> Vec<u64> {
+        self.mentions
+            .as_ref()
+            .and_then(Value::as_array)
+            .map(|mentions| {
+                mentions
+                    .iter()
+                    .filter_map(|mention| mention.get("user_id").and_then(Value::as_u64))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
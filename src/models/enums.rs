@@ -1,11 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "ENUM", rename_all = "lowercase")]
 pub enum TranslationStatus {
     Pending,
     Reviewed,
     Published,
+    // 原文已修改，需要重新翻译或确认
+    Outdated,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -22,6 +24,7 @@ impl ToString for TranslationStatus {
             TranslationStatus::Pending => "pending".to_string(),
             TranslationStatus::Reviewed => "reviewed".to_string(),
             TranslationStatus::Published => "published".to_string(),
+            TranslationStatus::Outdated => "outdated".to_string(),
         }
     }
 }
@@ -36,3 +39,86 @@ impl ToString for UserRole {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "ENUM", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TokenPermission {
+    Read,
+    Write,
+}
+
+impl ToString for TokenPermission {
+    fn to_string(&self) -> String {
+        match self {
+            TokenPermission::Read => "read".to_string(),
+            TokenPermission::Write => "write".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "ENUM", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    // 等待投递或重试
+    Pending,
+    Delivered,
+    // 重试次数用尽
+    Failed,
+}
+
+impl ToString for DeliveryStatus {
+    fn to_string(&self) -> String {
+        match self {
+            DeliveryStatus::Pending => "pending".to_string(),
+            DeliveryStatus::Delivered => "delivered".to_string(),
+            DeliveryStatus::Failed => "failed".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "ENUM", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    // 被指派了翻译或审核任务
+    Assignment,
+    // 自己的翻译被审核通过、发布或退回
+    ReviewResult,
+    // 在评论中被@提及
+    Mention,
+    // 自己的翻译因原文修改而过期
+    Outdated,
+}
+
+impl ToString for NotificationKind {
+    fn to_string(&self) -> String {
+        match self {
+            NotificationKind::Assignment => "assignment".to_string(),
+            NotificationKind::ReviewResult => "review_result".to_string(),
+            NotificationKind::Mention => "mention".to_string(),
+            NotificationKind::Outdated => "outdated".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "ENUM", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Open,
+    // 全部词条已翻译（需要审核时已审核）
+    Completed,
+    Cancelled,
+}
+
+impl ToString for TaskStatus {
+    fn to_string(&self) -> String {
+        match self {
+            TaskStatus::Open => "open".to_string(),
+            TaskStatus::Completed => "completed".to_string(),
+            TaskStatus::Cancelled => "cancelled".to_string(),
+        }
+    }
+}
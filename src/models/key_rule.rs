@@ -0,0 +1,48 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::{
+    errors::AppError,
+    models::module::Module,
+    utils::key_rule::{self, KeyChecker},
+};
+
+/// 项目的词条标识符规则
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct KeyRule {
+    pub project_id: u64,
+    // 标识符需完整匹配的正则表达式
+    pub pattern: Option<String>,
+    // 是否要求以所属模块路径为前缀，未归属模块的词条不受限制
+    pub require_module_prefix: bool,
+    // 层级分隔符
+    pub key_separator: String,
+    // 最大层级数
+    pub max_depth: Option<u32>,
+    // 禁止出现的字符
+    pub forbidden_chars: Option<String>,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_by: Option<String>,
+    pub upt_at: NaiveDateTime,
+}
+
+impl KeyRule {
+    pub fn checker(&self) -> Result<KeyChecker, AppError> {
+        KeyChecker::new(
+            self.pattern.as_deref(),
+            &self.key_separator,
+            self.max_depth,
+            self.forbidden_chars.as_deref(),
+        )
+        .map_err(|e| AppError::BadRequest(format!("Invalid key pattern: {}", e)))
+    }
+
+    /// 词条所属模块要求的前缀，不要求前缀或词条未归属模块时为 None
+    pub fn prefix_for(&self, module: Option<&Module>) -> Option<String> {
+        module.filter(|_| self.require_module_prefix).map(|module| {
+            key_rule::module_prefix(module.path.as_deref(), &module.name, &self.key_separator)
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::FromRow;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -7,6 +8,12 @@ pub struct Language {
     pub id: u64,
     pub code: String,
     pub name: String,
+    // 本地名称，如 "繁體中文（台灣）"
+    pub native_name: Option<String>,
+    // 文字方向：ltr / rtl
+    pub direction: String,
+    // CLDR 复数类别，如 ["one", "other"]
+    pub plural_categories: Option<Value>,
     pub is_active: bool,
     pub is_native: bool,
     pub crt_by: String,
@@ -1,10 +1,25 @@
+pub mod api_token;
+pub mod comment;
 pub mod enums;
+pub mod key_rule;
 pub mod language;
 pub mod module;
+pub mod notification;
+pub mod notification_preference;
+pub mod operation_log;
 pub mod phrase;
+pub mod phrase_source;
 pub mod phrase_type;
 pub mod project;
+pub mod project_language;
+pub mod recovery_code;
 pub mod screenshot;
+pub mod search_hit;
+pub mod task;
 pub mod term;
 pub mod translation;
+pub mod translation_history;
 pub mod user;
+pub mod user_identity;
+pub mod webhook;
+pub mod webhook_delivery;
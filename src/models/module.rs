@@ -8,13 +8,18 @@ use crate::dtos::module::{CreateModuleDto, UpdateModuleDto};
 pub struct Module {
     pub id: u64,
     pub project_id: u64,
+    // 父模块ID，为空表示根模块
+    pub parent_id: Option<u64>,
     pub name: String,
     pub description: Option<String>,
+    // 从根模块起的名称路径，如 checkout/cart
     pub path: Option<String>,
     pub crt_by: String,
     pub crt_at: NaiveDateTime,
     pub upt_by: Option<String>,
     pub upt_at: NaiveDateTime,
+    // 删除时间，不为空表示在回收站中
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<&CreateModuleDto> for Module {
@@ -22,13 +27,15 @@ impl From<&CreateModuleDto> for Module {
         Module {
             id: 0,
             project_id: dto.project_id,
-            name: dto.name.clone(),
+            parent_id: dto.parent_id,
+            name: dto.name.trim().to_string(),
             description: dto.description.clone(),
-            path: None,
+            path: Some(dto.name.trim().to_string()),
             crt_by: "".to_string(),
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
@@ -38,6 +45,7 @@ impl From<&UpdateModuleDto> for Module {
         Module {
             id: 0,
             project_id: 0,
+            parent_id: None,
             name: dto.name.clone().unwrap_or_default(),
             description: dto.description.clone(),
             path: None,
@@ -45,6 +53,7 @@ impl From<&UpdateModuleDto> for Module {
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
@@ -0,0 +1,50 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::enums::NotificationKind;
+
+/// 站内通知
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub id: u64,
+    // 接收人id
+    pub user_id: u64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub content: Option<String>,
+    pub project_id: Option<u64>,
+    // 关联对象，如 translation、comment、task
+    pub target_type: String,
+    pub target_id: u64,
+    // 触发人，系统触发时为空
+    pub actor_id: Option<u64>,
+    pub actor: Option<String>,
+    pub is_read: bool,
+    pub read_at: Option<NaiveDateTime>,
+    // 所在邮件摘要批次，为空表示尚未处理
+    pub digest_id: Option<String>,
+    pub crt_at: NaiveDateTime,
+}
+
+impl Notification {
+    /// 通知模板，接收人在发送时填入
+    pub fn new(kind: NotificationKind, title: String, target_type: &str, target_id: u64) -> Self {
+        Self {
+            id: 0,
+            user_id: 0,
+            kind,
+            title,
+            content: None,
+            project_id: None,
+            target_type: target_type.to_string(),
+            target_id,
+            actor_id: None,
+            actor: None,
+            is_read: false,
+            read_at: None,
+            digest_id: None,
+            crt_at: Local::now().naive_local(),
+        }
+    }
+}
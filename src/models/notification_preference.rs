@@ -0,0 +1,31 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+use crate::models::enums::NotificationKind;
+
+/// 用户的通知偏好
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationPreference {
+    pub user_id: u64,
+    // 不接收的通知类型，如 ["outdated"]
+    pub muted_kinds: Option<Value>,
+    // 是否发送每日邮件摘要
+    pub email_digest: bool,
+    pub crt_at: NaiveDateTime,
+    pub upt_at: NaiveDateTime,
+}
+
+impl NotificationPreference {
+    pub fn muted_kind_list(&self) -> Vec<NotificationKind> {
+        self.muted_kinds
+            .clone()
+            .and_then(|kinds| serde_json::from_value(kinds).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_muted(&self, kind: NotificationKind) -> bool {
+        self.muted_kind_list().contains(&kind)
+    }
+}
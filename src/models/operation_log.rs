@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OperationLog {
+    pub id: u64,
+    // 操作用户id，未识别的用户为0
+    pub user_id: u64,
+    // 操作类型
+    pub action: String,
+    // 操作对象类型
+    pub target_type: String,
+    // 操作对象ID
+    pub target_id: String,
+    // 操作详情
+    pub details: Option<Value>,
+    // IP地址
+    pub ip_address: String,
+    // 创建时间
+    pub crt_at: DateTime<Utc>,
+}
+
+impl OperationLog {
+    pub fn new(
+        user_id: u64,
+        action: &str,
+        target_type: &str,
+        target_id: String,
+        details: Option<Value>,
+        ip_address: &str,
+    ) -> Self {
+        Self {
+            id: 0,
+            user_id,
+            action: action.to_string(),
+            target_type: target_type.to_string(),
+            target_id,
+            details,
+            ip_address: ip_address.to_string(),
+            crt_at: Utc::now(),
+        }
+    }
+}
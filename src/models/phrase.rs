@@ -1,9 +1,9 @@
 use chrono::{Local, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use sqlx::FromRow;
 
-use crate::dtos::phrase::{CreatePhraseDto, UpdatePhraseDto};
+use crate::{dtos::phrase::CreatePhraseDto, models::phrase_source::PhraseSource};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Phrase {
@@ -19,10 +19,16 @@ pub struct Phrase {
     pub tags: Option<Value>,
     pub max_length: Option<i32>,
     pub is_plural: bool,
+    // 乐观锁版本号
+    pub version: u32,
+    // 基础语言内容版本号，原文修改（非微调）时递增
+    pub source_version: u32,
     pub crt_by: String,
     pub crt_at: NaiveDateTime,
     pub upt_by: Option<String>,
     pub upt_at: NaiveDateTime,
+    // 删除时间，不为空表示在回收站中
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<&CreatePhraseDto> for Phrase {
@@ -30,43 +36,111 @@ impl From<&CreatePhraseDto> for Phrase {
         Phrase {
             id: 0,
             project_id: dto.project_id,
-            module_id: None,
+            module_id: dto.module_id,
             type_id: 1,
             key: dto.key.clone(),
             base_content: dto.source_text.clone(),
             context: dto.context.clone(),
             variables: None,
-            platforms: Value::Null,
+            platforms: Value::Array(Vec::new()),
             tags: None,
             max_length: None,
             is_plural: false,
+            version: 0,
+            source_version: 1,
             crt_by: "".to_string(),
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
 
-impl From<&UpdatePhraseDto> for Phrase {
-    fn from(dto: &UpdatePhraseDto) -> Self {
-        Phrase {
+impl Phrase {
+    /// 添加、移除标签，保持原有顺序且不重复
+    pub fn update_tags(&mut self, add: &[String], remove: &[String]) {
+        let mut tags: Vec<String> = self
+            .tags
+            .as_ref()
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|tag| tag.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for tag in add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|tag| !remove.contains(tag));
+        self.tags = Some(json!(tags));
+    }
+
+    /// 修改原文：非微调时递增原文版本并返回待归档的旧原文，原文未变或微调时返回 None
+    pub fn change_source(
+        &mut self,
+        source_text: &str,
+        minor_edit: bool,
+        operator: &str,
+    ) -> Option<PhraseSource> {
+        if source_text == self.base_content {
+            return None;
+        }
+        let archived = (!minor_edit).then(|| PhraseSource {
             id: 0,
-            project_id: 0,
-            module_id: None,
-            type_id: 0,
-            key: dto.key.clone().unwrap_or_default(),
-            base_content: dto.source_text.clone().unwrap_or_default(),
-            context: dto.context.clone(),
-            variables: None,
-            platforms: Value::Null,
-            tags: None,
-            max_length: None,
-            is_plural: false,
-            crt_by: "".to_string(),
+            phrase_id: self.id,
+            source_version: self.source_version,
+            base_content: self.base_content.clone(),
+            crt_by: operator.to_string(),
             crt_at: Local::now().naive_local(),
-            upt_by: None,
-            upt_at: Local::now().naive_local(),
+        });
+        if archived.is_some() {
+            self.source_version += 1;
         }
+        self.base_content = source_text.to_string();
+        archived
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phrase() -> Phrase {
+        Phrase::from(&CreatePhraseDto {
+            project_id: 1,
+            module_id: None,
+            key: "login.title".into(),
+            source_text: "Sign in".into(),
+            context: None,
+        })
+    }
+
+    #[test]
+    fn test_change_source_bumps_version() {
+        let mut phrase = phrase();
+        let archived = phrase.change_source("Log in", false, "admin").unwrap();
+        assert_eq!(archived.source_version, 1);
+        assert_eq!(archived.base_content, "Sign in");
+        assert_eq!(phrase.source_version, 2);
+        assert_eq!(phrase.base_content, "Log in");
+    }
+
+    #[test]
+    fn test_change_source_minor_edit() {
+        let mut phrase = phrase();
+        assert!(phrase.change_source("Sign in.", true, "admin").is_none());
+        assert_eq!(phrase.source_version, 1);
+        assert_eq!(phrase.base_content, "Sign in.");
+    }
+
+    #[test]
+    fn test_change_source_unchanged() {
+        let mut phrase = phrase();
+        assert!(phrase.change_source("Sign in", false, "admin").is_none());
+        assert_eq!(phrase.source_version, 1);
+    }
+}
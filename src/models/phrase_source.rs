@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 词条原文的历史版本
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PhraseSource {
+    pub id: u64,
+    pub phrase_id: u64,
+    pub source_version: u32,
+    pub base_content: String,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+}
@@ -17,6 +17,8 @@ pub struct Project {
     pub crt_at: DateTime<Utc>,
     pub upt_by: Option<String>,
     pub upt_at: DateTime<Utc>,
+    // 删除时间，不为空表示在回收站中
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<&CreateProjectDto> for Project {
@@ -33,6 +35,7 @@ impl From<&CreateProjectDto> for Project {
             crt_at: Utc::now(),
             upt_by: None,
             upt_at: Utc::now(),
+            deleted_at: None,
         }
     }
 }
@@ -51,6 +54,7 @@ impl From<&UpdateProjectDto> for Project {
             crt_at: Utc::now(),
             upt_by: dto.upt_by.clone(),
             upt_at: Utc::now(),
+            deleted_at: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+use crate::{errors::AppError, utils::locale};
+
+/// 项目的目标语言
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectLanguage {
+    pub project_id: u64,
+    // 语言代码，对应 i18n_languages.code
+    pub language: String,
+    // 是否默认目标语言，每个项目最多一个
+    pub is_default: bool,
+    // 直接回退的语言代码列表，为空时使用 BCP-47 父级语言
+    pub fallbacks: Option<Value>,
+}
+
+impl ProjectLanguage {
+    /// 配置的回退语言，未配置时为 BCP-47 父级语言
+    pub fn fallback_languages(&self) -> Vec<String> {
+        match self.fallbacks.as_ref().and_then(Value::as_array) {
+            Some(list) => list
+                .iter()
+                .filter_map(|l| l.as_str().map(String::from))
+                .collect(),
+            None => locale::parent(&self.language).into_iter().collect(),
+        }
+    }
+}
+
+/// 在项目语言配置下展开语言的回退链，末尾为项目基础语言
+pub fn resolve_chain(
+    languages: &[ProjectLanguage],
+    language: &str,
+    base_language: &str,
+) -> Result<Vec<String>, AppError> {
+    locale::fallback_chain(language, base_language, |code| {
+        match languages.iter().find(|l| l.language == code) {
+            Some(configured) => configured.fallback_languages(),
+            None => locale::parent(code).into_iter().collect(),
+        }
+    })
+    .map_err(AppError::BusinessError)
+}
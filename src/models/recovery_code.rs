@@ -0,0 +1,18 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RecoveryCode {
+    pub id: u64,
+    // 用户id
+    pub user_id: u64,
+    // 恢复码哈希
+    pub code_hash: String,
+    // 使用时间
+    pub used_at: Option<NaiveDateTime>,
+    pub crt_by: String,
+    pub crt_at: DateTime<Utc>,
+    pub upt_by: Option<String>,
+    pub upt_at: DateTime<Utc>,
+}
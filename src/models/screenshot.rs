@@ -14,6 +14,8 @@ pub struct Screenshot {
     pub crt_at: NaiveDateTime,
     pub upt_by: Option<String>,
     pub upt_at: NaiveDateTime,
+    // 删除时间，不为空表示在回收站中
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<&CreateScreenshotDto> for Screenshot {
@@ -27,6 +29,7 @@ impl From<&CreateScreenshotDto> for Screenshot {
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
@@ -42,6 +45,7 @@ impl From<&UpdateScreenshotDto> for Screenshot {
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// 全文检索命中的一条记录，来自词条、翻译或术语
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SearchHit {
+    // 命中来源：phrase / translation / term
+    pub kind: String,
+    pub id: u64,
+    pub project_id: u64,
+    // 词条和翻译所属的词条ID，术语为空
+    pub phrase_id: Option<u64>,
+    // 翻译和术语的语言，词条为空
+    pub language: Option<String>,
+    // 词条标识符；术语为源术语
+    pub key: Option<String>,
+    pub content: String,
+    // 相关度得分
+    pub score: f64,
+}
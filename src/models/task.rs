@@ -0,0 +1,182 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::enums::{TaskStatus, TranslationStatus};
+
+/// 翻译任务
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Task {
+    pub id: u64,
+    pub project_id: u64,
+    // 目标语言
+    pub language: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub translator_id: u64,
+    // 为空表示无需审核
+    pub reviewer_id: Option<u64>,
+    pub due_at: NaiveDateTime,
+    pub status: TaskStatus,
+    // 进度，由翻译状态自动统计
+    pub total: u32,
+    pub translated: u32,
+    pub reviewed: u32,
+    pub completed_at: Option<NaiveDateTime>,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_by: Option<String>,
+    pub upt_at: NaiveDateTime,
+}
+
+impl Task {
+    /// 全部词条已翻译，需要审核时全部已审核
+    pub fn is_done(&self) -> bool {
+        let finished = match self.reviewer_id {
+            Some(_) => self.reviewed,
+            None => self.translated,
+        };
+        self.total > 0 && finished >= self.total
+    }
+
+    /// 按最新统计更新进度和状态，已取消的任务保持取消
+    pub fn apply_progress(&mut self, progress: &TaskProgress, now: NaiveDateTime) {
+        self.total = progress.total as u32;
+        self.translated = progress.translated as u32;
+        self.reviewed = progress.reviewed as u32;
+        if self.status == TaskStatus::Cancelled {
+            return;
+        }
+        if self.is_done() {
+            self.status = TaskStatus::Completed;
+            self.completed_at = self.completed_at.or(Some(now));
+        } else {
+            self.status = TaskStatus::Open;
+            self.completed_at = None;
+        }
+    }
+
+    pub fn is_overdue(&self, now: NaiveDateTime) -> bool {
+        self.status == TaskStatus::Open && self.due_at < now
+    }
+}
+
+/// 任务进度统计
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct TaskProgress {
+    pub total: i64,
+    pub translated: i64,
+    pub reviewed: i64,
+}
+
+/// 任务中的词条及其在目标语言下的翻译
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TaskPhrase {
+    pub phrase_id: u64,
+    pub key: String,
+    pub base_content: String,
+    pub translation_id: Option<u64>,
+    pub status: Option<TranslationStatus>,
+    pub translated_by: Option<u64>,
+    pub reviewed_by: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 7, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn task(reviewer_id: Option<u64>) -> Task {
+        Task {
+            id: 1,
+            project_id: 1,
+            language: "zh-CN".into(),
+            name: "task".into(),
+            description: None,
+            translator_id: 1,
+            reviewer_id,
+            due_at: at(10),
+            status: TaskStatus::Open,
+            total: 0,
+            translated: 0,
+            reviewed: 0,
+            completed_at: None,
+            crt_by: "admin".into(),
+            crt_at: at(1),
+            upt_by: None,
+            upt_at: at(1),
+        }
+    }
+
+    fn progress(total: i64, translated: i64, reviewed: i64) -> TaskProgress {
+        TaskProgress { total, translated, reviewed }
+    }
+
+    #[test]
+    fn test_empty_task_is_not_done() {
+        let mut task = task(None);
+        task.apply_progress(&progress(0, 0, 0), at(2));
+        assert!(!task.is_done());
+        assert_eq!(task.status, TaskStatus::Open);
+        assert_eq!(task.completed_at, None);
+    }
+
+    #[test]
+    fn test_review_required() {
+        let mut task = task(Some(2));
+        task.apply_progress(&progress(2, 2, 1), at(2));
+        assert_eq!(task.status, TaskStatus::Open);
+
+        task.apply_progress(&progress(2, 2, 2), at(3));
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(task.completed_at, Some(at(3)));
+    }
+
+    #[test]
+    fn test_reopen_and_complete_again() {
+        let mut task = task(None);
+        task.apply_progress(&progress(2, 2, 0), at(2));
+        assert_eq!(task.status, TaskStatus::Completed);
+
+        // 完成后再次统计仍完成时保留原完成时间
+        task.apply_progress(&progress(2, 2, 0), at(3));
+        assert_eq!(task.completed_at, Some(at(2)));
+
+        // 翻译过期或新增词条后重新打开
+        task.apply_progress(&progress(3, 2, 0), at(4));
+        assert_eq!(task.status, TaskStatus::Open);
+        assert_eq!(task.completed_at, None);
+
+        task.apply_progress(&progress(3, 3, 0), at(5));
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(task.completed_at, Some(at(5)));
+    }
+
+    #[test]
+    fn test_cancelled_keeps_status() {
+        let mut task = task(None);
+        task.status = TaskStatus::Cancelled;
+        task.apply_progress(&progress(2, 2, 0), at(2));
+        assert_eq!(task.status, TaskStatus::Cancelled);
+        assert_eq!(task.translated, 2);
+        assert_eq!(task.completed_at, None);
+        assert!(!task.is_overdue(at(11)));
+    }
+
+    #[test]
+    fn test_is_overdue() {
+        let mut task = task(None);
+        assert!(!task.is_overdue(at(9)));
+        assert!(task.is_overdue(at(11)));
+
+        task.apply_progress(&progress(1, 1, 0), at(11));
+        assert!(!task.is_overdue(at(12)));
+    }
+}
@@ -2,7 +2,7 @@ use chrono::{Local, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-use crate::{dtos::translation::{CreateTranslationDto, UpdateTranslationDto}, models::enums::TranslationStatus};
+use crate::{dtos::translation::CreateTranslationDto, models::enums::TranslationStatus};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Translation {
@@ -13,44 +13,68 @@ pub struct Translation {
     pub status: TranslationStatus,
     pub translated_by: Option<u64>,
     pub reviewed_by: Option<u64>,
+    // 乐观锁版本号
+    pub version: u32,
+    // 翻译所依据的基础语言内容版本号
+    pub source_version: u32,
     pub crt_by: String,
     pub crt_at: NaiveDateTime,
     pub upt_by: Option<String>,
     pub upt_at: NaiveDateTime,
+    // 删除时间，不为空表示在回收站中
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl From<&CreateTranslationDto> for Translation {
     fn from(dto: &CreateTranslationDto) -> Self {
         Translation {
             id: 0,
-            phrase_id: 0,
+            phrase_id: dto.phrase_id,
             language: dto.language_code.clone(),
             content: dto.translated_text.clone(),
             status: TranslationStatus::Pending,
             translated_by: None,
             reviewed_by: None,
+            version: 0,
+            source_version: 1,
             crt_by: "".to_string(),
             crt_at: Local::now().naive_local(),
             upt_by: None,
             upt_at: Local::now().naive_local(),
+            deleted_at: None,
         }
     }
 }
 
-impl From<&UpdateTranslationDto> for Translation {
-    fn from(dto: &UpdateTranslationDto) -> Self {
-        Translation {
-            id: 0,
-            phrase_id: 0,
-            language: dto.language_code.clone().unwrap_or_default(),
-            content: dto.content.clone().unwrap_or_default(),
-            status: dto.status.clone().unwrap_or(TranslationStatus::Pending),
-            translated_by: dto.translated_by,
-            reviewed_by: dto.reviewed_by,
-            crt_by: "".to_string(),
-            crt_at: Local::now().naive_local(),
-            upt_by: None,
-            upt_at: Local::now().naive_local(),
-        }
+impl Translation {
+    /// 修改内容或确认为非过期状态后，翻译即依据当前原文，需要刷新 source_version
+    pub fn follows_current_source(&self, content_changed: bool, status_changed: bool) -> bool {
+        content_changed || (status_changed && self.status != TranslationStatus::Outdated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation(status: TranslationStatus) -> Translation {
+        let mut translation = Translation::from(&CreateTranslationDto {
+            phrase_id: 1,
+            language_code: "zh-CN".into(),
+            translated_text: "登录".into(),
+        });
+        translation.status = status;
+        translation
+    }
+
+    #[test]
+    fn test_follows_current_source() {
+        // 修改内容时无论状态如何都依据当前原文
+        assert!(translation(TranslationStatus::Outdated).follows_current_source(true, false));
+        // 确认为非过期状态
+        assert!(translation(TranslationStatus::Reviewed).follows_current_source(false, true));
+        // 只改为过期状态、或内容和状态都未变化时保留原来的版本
+        assert!(!translation(TranslationStatus::Outdated).follows_current_source(false, true));
+        assert!(!translation(TranslationStatus::Reviewed).follows_current_source(false, false));
     }
 }
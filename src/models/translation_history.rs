@@ -0,0 +1,41 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::{enums::TranslationStatus, translation::Translation};
+
+/// 翻译的修改历史，每次内容或状态变化记录一条
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranslationHistory {
+    pub id: u64,
+    pub translation_id: u64,
+    // 修改后的内容
+    pub content: String,
+    // 修改后的版本号
+    pub version: i32,
+    // 修改后的状态
+    pub status: TranslationStatus,
+    // 操作人
+    pub user_id: Option<u64>,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_by: Option<String>,
+    pub upt_at: NaiveDateTime,
+}
+
+impl TranslationHistory {
+    pub fn new(translation: &Translation, operator_id: u64, operator: &str) -> Self {
+        Self {
+            id: 0,
+            translation_id: translation.id,
+            content: translation.content.clone(),
+            version: translation.version as i32,
+            status: translation.status.clone(),
+            user_id: Some(operator_id),
+            crt_by: operator.to_string(),
+            crt_at: Local::now().naive_local(),
+            upt_by: None,
+            upt_at: Local::now().naive_local(),
+        }
+    }
+}
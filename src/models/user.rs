@@ -31,6 +31,16 @@ pub struct User {
     pub status: bool,
     // 最后登录时间
     pub last_login: Option<NaiveDateTime>,
+    // 连续登录失败次数
+    pub failed_attempts: u32,
+    // 锁定截止时间
+    pub locked_until: Option<NaiveDateTime>,
+    // TOTP密钥
+    pub totp_secret: Option<String>,
+    // 是否已启用两步验证
+    pub totp_enabled: bool,
+    // 最近一次使用的TOTP时间窗口
+    pub totp_last_step: Option<u64>,
     // 创建人
     pub crt_by: String,
     // 创建时间
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserIdentity {
+    pub id: u64,
+    // 用户id
+    pub user_id: u64,
+    // 身份提供方（issuer）
+    pub provider: String,
+    // 提供方用户标识（sub）
+    pub subject: String,
+    // 提供方邮箱
+    pub email: Option<String>,
+    pub crt_by: String,
+    pub crt_at: DateTime<Utc>,
+    pub upt_by: Option<String>,
+    pub upt_at: DateTime<Utc>,
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+/// 项目 Webhook 订阅
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub id: u64,
+    pub project_id: u64,
+    // 接收地址
+    pub url: String,
+    // 签名密钥
+    pub secret: String,
+    // 订阅的事件类型列表
+    pub events: Value,
+    pub is_active: bool,
+    pub crt_by: String,
+    pub crt_at: NaiveDateTime,
+    pub upt_by: Option<String>,
+    pub upt_at: NaiveDateTime,
+}
+
+impl Webhook {
+    pub fn event_list(&self) -> Vec<String> {
+        self.events
+            .as_array()
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|event| event.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 是否订阅了该事件
+    pub fn subscribes(&self, event: &str) -> bool {
+        self.is_active && self.event_list().iter().any(|e| e == event)
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::enums::DeliveryStatus;
+
+/// Webhook 投递记录
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: u64,
+    pub webhook_id: u64,
+    // 事件id，同一事件投递到多个 Webhook 时相同
+    pub event_id: String,
+    pub event: String,
+    // 请求体
+    pub payload: String,
+    pub status: DeliveryStatus,
+    // 已投递次数
+    pub attempts: u32,
+    // 下次投递时间，投递成功或失败后为空
+    pub next_attempt_at: Option<NaiveDateTime>,
+    // 最近一次响应状态码
+    pub response_status: Option<i32>,
+    // 最近一次响应内容（截断）
+    pub response_body: Option<String>,
+    // 最近一次错误信息
+    pub error: Option<String>,
+    pub delivered_at: Option<NaiveDateTime>,
+    // 手动重新投递时，原投递记录id
+    pub redelivery_of: Option<u64>,
+    pub crt_at: NaiveDateTime,
+    pub upt_at: NaiveDateTime,
+}
+
+impl WebhookDelivery {
+    /// 待立即投递的记录
+    pub fn pending(webhook_id: u64, event_id: &str, event: &str, payload: String) -> Self {
+        let now = chrono::Local::now().naive_local();
+        Self {
+            id: 0,
+            webhook_id,
+            event_id: event_id.to_string(),
+            event: event.to_string(),
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: Some(now),
+            response_status: None,
+            response_body: None,
+            error: None,
+            delivered_at: None,
+            redelivery_of: None,
+            crt_at: now,
+            upt_at: now,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+use crate::{errors::AppError, models::api_token::ApiToken};
+
+use super::BaseRepository;
+
+pub struct ApiTokenRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl ApiTokenRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 根据令牌前缀查找令牌
+    pub async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiToken>, AppError> {
+        self.select_by_column_optional("token_prefix", prefix).await
+    }
+
+    /// 查找用户的令牌列表
+    pub async fn find_by_user_id(&self, user_id: u64) -> Result<Vec<ApiToken>, AppError> {
+        sqlx::query_as::<_, ApiToken>(&format!(
+            r#"
+            SELECT * FROM {} WHERE user_id = ? ORDER BY crt_at DESC
+            "#,
+            self.get_table_name()
+        ))
+        .bind(user_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 吊销令牌，只能吊销自己的令牌
+    pub async fn revoke(&self, id: u64, user_id: u64, upt_by: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET revoked_at = NOW(), upt_by = ? WHERE id = ? AND user_id = ? AND revoked_at IS NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(upt_by)
+        .bind(id)
+        .bind(user_id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 记录令牌最后使用时间
+    pub async fn touch(&self, id: u64) -> Result<(), AppError> {
+        sqlx::query(&format!(
+            r#"
+            UPDATE {} SET last_used_at = NOW() WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<ApiToken> for ApiTokenRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_api_tokens"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "user_id", "token_prefix"]
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -1,6 +1,9 @@
 use crate::errors::AppError;
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::{NaiveDateTime, Utc};
+use sqlx::{MySql, MySqlConnection, MySqlPool, Transaction};
+
+use super::query::{self, PageQuery};
 
 #[async_trait]
 pub trait BaseRepository<T> {
@@ -15,6 +18,30 @@ pub trait BaseRepository<T> {
         "crt_by"
     }
     fn get_table_name(&self) -> &str;
+    /// 乐观锁版本列，返回 None 时更新不做版本校验
+    fn get_version_column_name(&self) -> Option<&str> {
+        None
+    }
+    /// 软删除列，返回 None 时删除为物理删除
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        None
+    }
+    /// 未删除数据的过滤条件，读取时默认带上
+    fn live_condition(&self) -> String {
+        match self.get_deleted_column_name() {
+            Some(column) => format!("{} IS NULL", column),
+            None => "1 = 1".to_string(),
+        }
+    }
+    /// 允许过滤和排序的列
+    fn get_query_columns(&self) -> &[&str] {
+        &[]
+    }
+
+    /// 开启事务，事务内调用各方法的 *_with 版本并传入 &mut *tx
+    async fn begin(&self) -> Result<Transaction<'static, MySql>, AppError> {
+        Ok(self.get_pool().begin().await?)
+    }
 
     async fn select_count(&self, sql: String) -> Result<i64, AppError> {
         let count = sqlx::query_scalar::<_, i64>(&sql)
@@ -26,17 +53,27 @@ pub trait BaseRepository<T> {
     }
 
     async fn select_by_id(&self, id: u64) -> Result<Option<T>, AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+    {
+        let mut conn = self.get_pool().acquire().await?;
+        self.select_by_id_with(&mut conn, id).await
+    }
+
+    /// 在指定连接（或事务）中按ID查询
+    async fn select_by_id_with(&self, conn: &mut MySqlConnection, id: u64) -> Result<Option<T>, AppError>
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
     {
         let query = format!(
-            "SELECT * FROM {} WHERE {} = ?",
+            "SELECT * FROM {} WHERE {} = ? AND {}",
             self.get_table_name(),
-            self.get_id_column_name()
+            self.get_id_column_name(),
+            self.live_condition()
         );
         let result = sqlx::query_as::<_, T>(&query)
             .bind(id)
-            .fetch_optional(self.get_pool())
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(result)
@@ -46,7 +83,11 @@ pub trait BaseRepository<T> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
     {
-        let query = format!("SELECT * FROM {}", self.get_table_name());
+        let query = format!(
+            "SELECT * FROM {} WHERE {}",
+            self.get_table_name(),
+            self.live_condition()
+        );
         let result = sqlx::query_as::<_, T>(&query)
             .fetch_all(self.get_pool())
             .await
@@ -58,13 +99,21 @@ pub trait BaseRepository<T> {
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
     {
-        let query = format!("SELECT COUNT(*) FROM {}", self.get_table_name());
+        let query = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            self.get_table_name(),
+            self.live_condition()
+        );
         let count = sqlx::query_scalar::<_, i64>(&query)
             .fetch_one(self.get_pool())
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
-        let query = format!("SELECT * FROM {} LIMIT ?, ?", self.get_table_name());
+        let query = format!(
+            "SELECT * FROM {} WHERE {} LIMIT ?, ?",
+            self.get_table_name(),
+            self.live_condition()
+        );
         let result = sqlx::query_as::<_, T>(&query)
             .bind((page - 1) * page_size)
             .bind(page_size)
@@ -74,14 +123,82 @@ pub trait BaseRepository<T> {
         Ok((result, count))
     }
 
+    /// 按条件、排序分页查询，所有参数均以占位符绑定
+    async fn select_page(&self, page_query: &PageQuery) -> Result<(Vec<T>, i64), AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+    {
+        let order = self.get_id_column_name().to_string();
+        self.select_page_in(page_query, self.live_condition(), order)
+            .await
+    }
+
+    /// 回收站分页查询，默认按删除时间倒序
+    async fn select_deleted_page(&self, page_query: &PageQuery) -> Result<(Vec<T>, i64), AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+    {
+        let column = self
+            .get_deleted_column_name()
+            .ok_or_else(|| AppError::BadRequest("Soft delete is not enabled".into()))?;
+        let scope = format!("{} IS NOT NULL", column);
+        let order = format!("{} DESC", column);
+        self.select_page_in(page_query, scope, order).await
+    }
+
+    /// 在指定范围内（未删除或已删除）按条件分页查询
+    async fn select_page_in(
+        &self,
+        page_query: &PageQuery,
+        scope: String,
+        default_order: String,
+    ) -> Result<(Vec<T>, i64), AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+    {
+        let columns = self.get_query_columns();
+        let (conditions, params) = page_query.filter.to_sql(columns)?;
+        let where_sql = if conditions.is_empty() {
+            format!(" WHERE {}", scope)
+        } else {
+            format!(" WHERE {} AND {}", scope, conditions)
+        };
+        let order_sql = match query::order_by_sql(&page_query.sorts, columns)? {
+            order if order.is_empty() => default_order,
+            order => order,
+        };
+
+        let count_sql = format!("SELECT COUNT(*) FROM {}{}", self.get_table_name(), where_sql);
+        let count = query::bind_all_scalar(sqlx::query_scalar::<_, i64>(&count_sql), params.clone())
+            .fetch_one(self.get_pool())
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let (offset, limit) = page_query.limit();
+        let list_sql = format!(
+            "SELECT * FROM {}{} ORDER BY {} LIMIT ?, ?",
+            self.get_table_name(),
+            where_sql,
+            order_sql
+        );
+        let result = query::bind_all(sqlx::query_as::<_, T>(&list_sql), params)
+            .bind(offset)
+            .bind(limit)
+            .fetch_all(self.get_pool())
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok((result, count))
+    }
+
     async fn select_by_column(&self, column: &str, value: &str) -> Result<Vec<T>, AppError>
     where
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
     {
         let query = format!(
-            "SELECT * FROM {} WHERE {} = ?",
+            "SELECT * FROM {} WHERE {} = ? AND {}",
             self.get_table_name(),
-            column
+            query::check_column(column, self.get_query_columns())?,
+            self.live_condition()
         );
         let result = sqlx::query_as::<_, T>(&query)
             .bind(value)
@@ -100,9 +217,10 @@ pub trait BaseRepository<T> {
         T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
     {
         let query = format!(
-            "SELECT * FROM {} WHERE {} = ?",
+            "SELECT * FROM {} WHERE {} = ? AND {}",
             self.get_table_name(),
-            column
+            query::check_column(column, self.get_query_columns())?,
+            self.live_condition()
         );
         let result = sqlx::query_as::<_, T>(&query)
             .bind(value)
@@ -113,6 +231,15 @@ pub trait BaseRepository<T> {
     }
 
     async fn insert(&self, entity: &T) -> Result<u64, AppError>
+    where
+        T: serde::Serialize + Send + Sync,
+    {
+        let mut conn = self.get_pool().acquire().await?;
+        self.insert_with(&mut conn, entity).await
+    }
+
+    /// 在指定连接（或事务）中插入
+    async fn insert_with(&self, conn: &mut MySqlConnection, entity: &T) -> Result<u64, AppError>
     where
         T: serde::Serialize + Send + Sync,
     {
@@ -158,12 +285,15 @@ pub trait BaseRepository<T> {
                         query_builder = query_builder.bind(s);
                     }
                 }
-                _ => return Err(AppError::Database("Unsupported value type".into())),
+                // JSON 列（数组、对象）按字符串写入
+                serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                    query_builder = query_builder.bind(value.to_string());
+                }
             }
         }
 
         let result = query_builder
-            .execute(self.get_pool())
+            .execute(&mut *conn)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -172,7 +302,24 @@ pub trait BaseRepository<T> {
 
     async fn update_by_id(&self, id: u64, entity: &T) -> Result<bool, AppError>
     where
-        T: serde::Serialize + Send + Sync,
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + serde::Serialize + Send + Sync + Unpin,
+    {
+        let mut conn = self.get_pool().acquire().await?;
+        self.update_by_id_with(&mut conn, id, entity).await
+    }
+
+    /// 在指定连接（或事务）中更新
+    ///
+    /// 配置了版本列时按实体中的版本号做条件更新并递增版本，
+    /// 版本不一致返回 Conflict，并附带服务端当前数据
+    async fn update_by_id_with(
+        &self,
+        conn: &mut MySqlConnection,
+        id: u64,
+        entity: &T,
+    ) -> Result<bool, AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + serde::Serialize + Send + Sync + Unpin,
     {
         let value = serde_json::to_value(entity)
             .map_err(|e| AppError::Database(format!("Failed to serialize entity: {}", e)))?;
@@ -184,8 +331,14 @@ pub trait BaseRepository<T> {
         // 过滤掉 null 值的字段和 id 字段
         let mut update_fields = Vec::new();
         let mut values = Vec::new();
+        let version_column = self.get_version_column_name();
+        let mut expected_version = None;
 
         for (key, value) in obj {
+            if Some(key.as_str()) == version_column {
+                expected_version = value.as_u64();
+                continue;
+            }
             // 跳过 id 字段和 null 值
             if value.is_null() || key == self.get_id_column_name() || key == self.get_create_time_column_name() || key == self.get_create_by_column_name() { 
                 continue;
@@ -198,11 +351,19 @@ pub trait BaseRepository<T> {
             return Ok(false);
         }
 
+        let mut condition = format!("{} = ? AND {}", self.get_id_column_name(), self.live_condition());
+        if let Some(column) = version_column {
+            update_fields.push(format!("{0} = {0} + 1", column));
+            if expected_version.is_some() {
+                condition.push_str(&format!(" AND {} = ?", column));
+            }
+        }
+
         let query = format!(
-            "UPDATE {} SET {} WHERE {} = ?",
+            "UPDATE {} SET {} WHERE {}",
             self.get_table_name(),
             update_fields.join(", "),
-            self.get_id_column_name()
+            condition
         );
 
         let mut query_builder = sqlx::query(&query);
@@ -224,36 +385,170 @@ pub trait BaseRepository<T> {
                         query_builder = query_builder.bind(s);
                     }
                 }
+                serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                    query_builder = query_builder.bind(value.to_string());
+                }
                 _ => return Err(AppError::Database("Unsupported value type".into())),
             }
         }
 
         // 使用参数中的 id
         query_builder = query_builder.bind(id);
+        if let Some(version) = expected_version {
+            query_builder = query_builder.bind(version);
+        }
 
         let result = query_builder
-            .execute(self.get_pool())
+            .execute(&mut *conn)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        if result.rows_affected() == 0 && expected_version.is_some() {
+            // 记录仍存在说明已被他人修改，返回最新数据供客户端合并
+            if let Some(current) = self.select_by_id_with(conn, id).await? {
+                let current = serde_json::to_value(&current)
+                    .map_err(|e| AppError::SerdeError(e.to_string()))?;
+                return Err(AppError::Conflict(
+                    "Record has been modified by someone else".into(),
+                    current,
+                ));
+            }
+        }
+
         Ok(result.rows_affected() > 0)
     }
 
     async fn delete_by_id(&self, id: u64) -> Result<bool, AppError> {
+        let mut conn = self.get_pool().acquire().await?;
+        self.delete_by_id_with(&mut conn, id).await
+    }
+
+    /// 在指定连接（或事务）中删除，配置了软删除列时只做标记
+    async fn delete_by_id_with(&self, conn: &mut MySqlConnection, id: u64) -> Result<bool, AppError> {
+        Ok(self.delete_by_ids_with(conn, &[id]).await? > 0)
+    }
+
+    async fn delete_by_ids(&self, ids: &[u64]) -> Result<u64, AppError> {
+        let mut conn = self.get_pool().acquire().await?;
+        self.delete_by_ids_with(&mut conn, ids).await
+    }
+
+    /// 在指定连接（或事务）中批量删除，配置了软删除列时只做标记
+    async fn delete_by_ids_with(&self, conn: &mut MySqlConnection, ids: &[u64]) -> Result<u64, AppError> {
+        if self.get_deleted_column_name().is_some() {
+            let deleted_at = Utc::now().naive_utc();
+            self.soft_delete_by_ids_with(conn, ids, deleted_at).await
+        } else {
+            self.purge_by_ids_with(conn, ids).await
+        }
+    }
+
+    /// 软删除并指定删除时间，级联删除时父子数据使用同一时间，便于一起恢复
+    async fn soft_delete_by_ids_with(
+        &self,
+        conn: &mut MySqlConnection,
+        ids: &[u64],
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let column = self
+            .get_deleted_column_name()
+            .ok_or_else(|| AppError::BadRequest("Soft delete is not enabled".into()))?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let placeholders = std::iter::repeat("?")
+            .take(ids.len())
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            "UPDATE {} SET {} = ? WHERE {} IN ({}) AND {} IS NULL",
+            self.get_table_name(),
+            column,
+            self.get_id_column_name(),
+            placeholders,
+            column
+        );
+
+        let mut query_builder = sqlx::query(&query).bind(deleted_at);
+        for id in ids {
+            query_builder = query_builder.bind(*id);
+        }
+        let result = query_builder
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    /// 按ID查询回收站中的数据
+    async fn select_deleted_by_id(&self, id: u64) -> Result<Option<T>, AppError>
+    where
+        T: for<'r> sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
+    {
+        let column = match self.get_deleted_column_name() {
+            Some(column) => column,
+            None => return Ok(None),
+        };
         let query = format!(
-            "DELETE FROM {} WHERE {} = ?",
+            "SELECT * FROM {} WHERE {} = ? AND {} IS NOT NULL",
             self.get_table_name(),
-            self.get_id_column_name()
+            self.get_id_column_name(),
+            column
+        );
+        let result = sqlx::query_as::<_, T>(&query)
+            .bind(id)
+            .fetch_optional(self.get_pool())
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(result)
+    }
+
+    /// 在指定连接（或事务）中从回收站恢复
+    async fn restore_by_id_with(&self, conn: &mut MySqlConnection, id: u64) -> Result<bool, AppError> {
+        let column = self
+            .get_deleted_column_name()
+            .ok_or_else(|| AppError::BadRequest("Soft delete is not enabled".into()))?;
+        let query = format!(
+            "UPDATE {} SET {} = NULL WHERE {} = ? AND {} IS NOT NULL",
+            self.get_table_name(),
+            column,
+            self.get_id_column_name(),
+            column
         );
         let result = sqlx::query(&query)
             .bind(id)
-            .execute(self.get_pool())
+            .execute(&mut *conn)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(result.rows_affected() > 0)
     }
 
-    async fn delete_by_ids(&self, ids: &[u64]) -> Result<u64, AppError> {
+    /// 物理删除回收站中早于指定时间的数据
+    async fn purge_deleted_before_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let column = match self.get_deleted_column_name() {
+            Some(column) => column,
+            None => return Ok(0),
+        };
+        let query = format!(
+            "DELETE FROM {} WHERE {} < ?",
+            self.get_table_name(),
+            column
+        );
+        let result = sqlx::query(&query)
+            .bind(before)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    /// 在指定连接（或事务）中物理删除
+    async fn purge_by_ids_with(&self, conn: &mut MySqlConnection, ids: &[u64]) -> Result<u64, AppError> {
         if ids.is_empty() {
             return Ok(0);
         }
@@ -276,7 +571,7 @@ pub trait BaseRepository<T> {
         }
 
         let result = query_builder
-            .execute(self.get_pool())
+            .execute(&mut *conn)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::comment::Comment};
+
+use super::BaseRepository;
+
+/// 所在词条和翻译都未进入回收站的评论
+pub const ACTIVE_TARGET_CONDITION: &str = r#"
+    phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at IS NULL)
+    AND (translation_id IS NULL
+        OR translation_id IN (SELECT id FROM i18n_translations WHERE deleted_at IS NULL))
+"#;
+
+/// 超过保留期的评论：评论本身、所在词条或翻译在回收站中超过保留期
+pub const EXPIRED_CONDITION: &str = r#"
+    deleted_at < ?
+    OR phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at < ?)
+    OR translation_id IN (SELECT id FROM i18n_translations WHERE deleted_at < ?)
+"#;
+
+pub struct CommentRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl CommentRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 词条的评论，指定翻译时只查询该翻译的评论，按时间顺序
+    pub async fn find_by_phrase(
+        &self,
+        phrase_id: u64,
+        translation_id: Option<u64>,
+    ) -> Result<Vec<Comment>, AppError> {
+        let mut sql = format!(
+            "SELECT * FROM {} WHERE phrase_id = ? AND deleted_at IS NULL",
+            self.get_table_name()
+        );
+        if translation_id.is_some() {
+            sql.push_str(" AND translation_id = ?");
+        }
+        sql.push_str(" ORDER BY crt_at, id");
+        let mut query = sqlx::query_as::<_, Comment>(&sql).bind(phrase_id);
+        if let Some(translation_id) = translation_id {
+            query = query.bind(translation_id);
+        }
+        query.fetch_all(self.get_pool()).await.map_err(AppError::from)
+    }
+
+    /// 多个讨论串的回复，按时间顺序
+    pub async fn find_replies(&self, root_ids: &[u64]) -> Result<Vec<Comment>, AppError> {
+        if root_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            "SELECT * FROM {} WHERE parent_id IN ({}) AND deleted_at IS NULL ORDER BY crt_at, id",
+            self.get_table_name(),
+            vec!["?"; root_ids.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, Comment>(&sql);
+        for id in root_ids {
+            query = query.bind(id);
+        }
+        query.fetch_all(self.get_pool()).await.map_err(AppError::from)
+    }
+
+    /// 删除讨论串中的全部回复
+    pub async fn soft_delete_replies_with(
+        &self,
+        conn: &mut MySqlConnection,
+        root_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = ? WHERE parent_id = ? AND deleted_at IS NULL",
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(root_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 标记讨论串已解决或重新打开
+    pub async fn set_resolved_with(
+        &self,
+        conn: &mut MySqlConnection,
+        id: u64,
+        resolved_by: Option<u64>,
+        resolved_at: Option<NaiveDateTime>,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET is_resolved = ?, resolved_by = ?, resolved_at = ?, upt_by = ?
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(resolved_by.is_some())
+        .bind(resolved_by)
+        .bind(resolved_at)
+        .bind(operator)
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 物理删除翻译下的全部评论，包括已删除的
+    pub async fn delete_by_translation_with(
+        &self,
+        conn: &mut MySqlConnection,
+        translation_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE translation_id = ?",
+            self.get_table_name()
+        ))
+        .bind(translation_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除超过保留期的评论，以及回收站中超过保留期的词条和翻译下的评论
+    pub async fn purge_expired_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE {}",
+            self.get_table_name(),
+            EXPIRED_CONDITION
+        ))
+        .bind(before)
+        .bind(before)
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<Comment> for CommentRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_comments"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &[
+            "id", "project_id", "phrase_id", "translation_id", "user_id", "is_question",
+            "is_resolved", "crt_at", "upt_at",
+        ]
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::key_rule::KeyRule};
+
+use super::BaseRepository;
+
+pub struct KeyRuleRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl KeyRuleRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 保存项目的标识符规则，不存在时创建；可选项需要能清空，不使用跳过空值的通用更新
+    pub async fn upsert(&self, rule: &KeyRule) -> Result<(), AppError> {
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO {} (project_id, pattern, require_module_prefix, key_separator, max_depth, forbidden_chars, crt_by)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE pattern = VALUES(pattern),
+                require_module_prefix = VALUES(require_module_prefix),
+                key_separator = VALUES(key_separator),
+                max_depth = VALUES(max_depth),
+                forbidden_chars = VALUES(forbidden_chars),
+                upt_by = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(rule.project_id)
+        .bind(&rule.pattern)
+        .bind(rule.require_module_prefix)
+        .bind(&rule.key_separator)
+        .bind(rule.max_depth)
+        .bind(&rule.forbidden_chars)
+        .bind(&rule.crt_by)
+        .bind(&rule.upt_by)
+        .execute(self.get_pool())
+        .await?;
+        Ok(())
+    }
+
+    /// 物理删除回收站中超过保留期的项目的标识符规则
+    pub async fn purge_by_deleted_projects_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE project_id IN (SELECT id FROM i18n_projects WHERE deleted_at < ?)",
+            self.get_table_name()
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<KeyRule> for KeyRuleRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_key_rules"
+    }
+
+    fn get_id_column_name(&self) -> &str {
+        "project_id"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
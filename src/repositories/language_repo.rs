@@ -72,35 +72,6 @@ impl LanguageRepository {
         .await
         .map_err(AppError::from)
     }
-
-    /// 根据编码或名称搜索用户
-    pub async fn select_page_by_key(
-        &self,
-        page: u32,
-        size: u32,
-        key: &str,
-    ) -> Result<(Vec<Language>, i64), AppError> {
-        let query_count = format!(
-            "SELECT count(*) FROM {} WHERE code LIKE '%{}%' OR name LIKE '%{}%'",
-            self.get_table_name(),
-            key,
-            key
-        );
-        let query = format!(
-            "SELECT * FROM {} WHERE code LIKE '%{}%' OR name LIKE '%{}%' LIMIT ?, ?",
-            self.get_table_name(),
-            key,
-            key
-        );
-        let count = self.select_count(query_count).await?;
-        let languages = sqlx::query_as::<_, Language>(&query)
-            .bind((page - 1) * size)
-            .bind(size)
-            .fetch_all(self.get_pool())
-            .await
-            .map_err(|e| AppError::Database(e.to_string()))?;
-        Ok((languages, count))
-    }
 }
 
 #[async_trait]
@@ -109,6 +80,10 @@ impl BaseRepository<Language> for LanguageRepository {
         "i18n_languages"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "code", "name", "native_name", "direction", "is_active", "is_native", "crt_at", "upt_at"]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
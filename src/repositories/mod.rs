@@ -1,27 +1,58 @@
 use sqlx::{MySql, Pool};
 
+pub mod api_token_repo;
 pub mod base_repo;
+pub mod comment_repo;
+pub mod key_rule_repo;
 pub mod language_repo;
 pub mod module_repo;
+pub mod notification_preference_repo;
+pub mod notification_repo;
+pub mod operation_log_repo;
 pub mod phrase_repo;
+pub mod phrase_source_repo;
 pub mod phrase_type_repo;
+pub mod project_language_repo;
 pub mod project_repo;
+pub mod query;
+pub mod recovery_code_repo;
 pub mod screenshot_repo;
+pub mod search_repo;
+pub mod task_repo;
 pub mod term_repo;
+pub mod translation_history_repo;
 pub mod translation_repo;
+pub mod user_identity_repo;
 pub mod user_repo;
+pub mod webhook_delivery_repo;
+pub mod webhook_repo;
 
 pub trait Repository {
     fn get_pool(&self) -> &Pool<MySql>;
 }
 
+pub use api_token_repo::ApiTokenRepository;
 pub use base_repo::BaseRepository;
+pub use comment_repo::CommentRepository;
+pub use key_rule_repo::KeyRuleRepository;
 pub use language_repo::LanguageRepository;
 pub use module_repo::ModuleRepository;
+pub use notification_preference_repo::NotificationPreferenceRepository;
+pub use notification_repo::NotificationRepository;
+pub use operation_log_repo::OperationLogRepository;
 pub use phrase_repo::PhraseRepository;
+pub use phrase_source_repo::PhraseSourceRepository;
 pub use phrase_type_repo::PhraseTypeRepository;
+pub use project_language_repo::ProjectLanguageRepository;
 pub use project_repo::ProjectRepository;
+pub use recovery_code_repo::RecoveryCodeRepository;
 pub use screenshot_repo::ScreenshotRepository;
+pub use search_repo::SearchRepository;
+pub use task_repo::TaskRepository;
 pub use term_repo::TermRepository;
+pub use translation_history_repo::TranslationHistoryRepository;
 pub use translation_repo::TranslationRepository;
+pub use user_identity_repo::UserIdentityRepository;
 pub use user_repo::UserRepository;
+pub use webhook_delivery_repo::WebhookDeliveryRepository;
+pub use webhook_repo::WebhookRepository;
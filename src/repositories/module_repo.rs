@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
 
-use crate::{errors::AppError, models::module::Module};
+use crate::{errors::AppError, models::module::Module, utils::module_tree::PathFilter};
 
-use super::BaseRepository;
+use super::{
+    query::{self, FilterValue},
+    BaseRepository,
+};
 
 pub struct ModuleRepository {
     pool: Arc<MySqlPool>,
@@ -16,11 +20,23 @@ impl ModuleRepository {
         Self { pool }
     }
 
+    /// 模块所属的项目，包括回收站中的模块
+    pub async fn find_project_id(&self, id: u64) -> Result<Option<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT project_id FROM {} WHERE id = ?",
+            self.get_table_name()
+        ))
+        .bind(id)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据项目ID查找模块列表
     pub async fn find_by_project_id(&self, project_id: u64) -> Result<Vec<Module>, AppError> {
         sqlx::query_as::<_, Module>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ?
+            SELECT * FROM {} WHERE project_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -38,7 +54,7 @@ impl ModuleRepository {
     ) -> Result<Option<Module>, AppError> {
         sqlx::query_as::<_, Module>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ? AND code = ?
+            SELECT * FROM {} WHERE project_id = ? AND code = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -48,6 +64,139 @@ impl ModuleRepository {
         .await
         .map_err(AppError::from)
     }
+
+    /// 路径是否已被其他模块占用，包括回收站中的模块
+    pub async fn exists_path_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        path: &str,
+        exclude_id: u64,
+    ) -> Result<bool, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM {} WHERE project_id = ? AND path = ? AND id <> ?",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .bind(path)
+        .bind(exclude_id)
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// 模块及其所有子孙模块，包括回收站中的
+    pub async fn find_subtree_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        path: &str,
+    ) -> Result<Vec<Module>, AppError> {
+        sqlx::query_as::<_, Module>(&format!(
+            "SELECT * FROM {} WHERE project_id = ? AND (path = ? OR path LIKE ?) ORDER BY path",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .bind(path)
+        .bind(format!("{}/%", query::escape_like(path)))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 修改模块的父模块、名称和路径，移动到根级时需要将 parent_id 置空，不能使用跳过空值的通用更新
+    pub async fn update_location_with(
+        &self,
+        conn: &mut MySqlConnection,
+        module: &Module,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET parent_id = ?, name = ?, path = ?, upt_by = ? WHERE id = ?",
+            self.get_table_name()
+        ))
+        .bind(module.parent_id)
+        .bind(&module.name)
+        .bind(&module.path)
+        .bind(&module.upt_by)
+        .bind(module.id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 将子孙模块路径中的 old_path 前缀替换为 new_path，包括回收站中的模块
+    pub async fn rebase_descendants_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET path = CONCAT(?, SUBSTRING(path, ?)) WHERE project_id = ? AND path LIKE ?",
+            self.get_table_name()
+        ))
+        .bind(new_path)
+        .bind(old_path.chars().count() as u64 + 1)
+        .bind(project_id)
+        .bind(format!("{}/%", query::escape_like(old_path)))
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 按模块路径过滤的条件，column 为词条的模块ID列
+    pub fn path_condition(&self, column: &str, filter: &PathFilter) -> (String, Vec<FilterValue>) {
+        let mut sql = format!(
+            "{} IN (SELECT id FROM {} WHERE {} AND (path = ?",
+            column,
+            self.get_table_name(),
+            self.live_condition()
+        );
+        let mut params = vec![FilterValue::from(filter.path.clone())];
+        if filter.subtree {
+            sql.push_str(" OR path LIKE ?");
+            params.push(FilterValue::from(format!("{}/%", query::escape_like(&filter.path))));
+        }
+        sql.push_str("))");
+        (sql, params)
+    }
+
+    /// 级联软删除项目下的模块，使用与父级相同的删除时间
+    pub async fn soft_delete_by_project(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = ? WHERE project_id = ? AND deleted_at IS NULL",
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 恢复随项目一起删除的模块
+    pub async fn restore_by_project(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE project_id = ? AND deleted_at = ?",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
 }
 
 #[async_trait]
@@ -56,7 +205,15 @@ impl BaseRepository<Module> for ModuleRepository {
         "i18n_modules"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "project_id", "parent_id", "name", "path", "crt_at", "upt_at", "deleted_at"]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
 }
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::notification_preference::NotificationPreference};
+
+use super::BaseRepository;
+
+pub struct NotificationPreferenceRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl NotificationPreferenceRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 多个用户的通知偏好，未设置的用户不在结果中
+    pub async fn find_by_users_with(
+        &self,
+        conn: &mut MySqlConnection,
+        user_ids: &[u64],
+    ) -> Result<Vec<NotificationPreference>, AppError> {
+        if user_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            "SELECT * FROM {} WHERE user_id IN ({})",
+            self.get_table_name(),
+            vec!["?"; user_ids.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, NotificationPreference>(&sql);
+        for user_id in user_ids {
+            query = query.bind(user_id);
+        }
+        query.fetch_all(&mut *conn).await.map_err(AppError::from)
+    }
+
+    /// 保存用户的通知偏好，不存在时创建
+    pub async fn upsert(&self, preference: &NotificationPreference) -> Result<(), AppError> {
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO {} (user_id, muted_kinds, email_digest) VALUES (?, ?, ?)
+            ON DUPLICATE KEY UPDATE muted_kinds = VALUES(muted_kinds), email_digest = VALUES(email_digest)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(preference.user_id)
+        .bind(&preference.muted_kinds)
+        .bind(preference.email_digest)
+        .execute(self.get_pool())
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<NotificationPreference> for NotificationPreferenceRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_notification_preferences"
+    }
+
+    fn get_id_column_name(&self) -> &str {
+        "user_id"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
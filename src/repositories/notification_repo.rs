@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    constants::{
+        NOTIFICATION_TARGET_COMMENT, NOTIFICATION_TARGET_PHRASE, NOTIFICATION_TARGET_TRANSLATION,
+    },
+    errors::AppError,
+    models::notification::Notification,
+};
+
+use super::{comment_repo, BaseRepository};
+
+pub struct NotificationRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl NotificationRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 用户的未读通知数
+    pub async fn count_unread(&self, user_id: u64) -> Result<i64, AppError> {
+        sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM {} WHERE user_id = ? AND is_read = FALSE",
+            self.get_table_name()
+        ))
+        .bind(user_id)
+        .fetch_one(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 将用户的一条通知标记为已读
+    pub async fn mark_read(
+        &self,
+        user_id: u64,
+        id: u64,
+        read_at: NaiveDateTime,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET is_read = TRUE, read_at = ? WHERE id = ? AND user_id = ? AND is_read = FALSE",
+            self.get_table_name()
+        ))
+        .bind(read_at)
+        .bind(id)
+        .bind(user_id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 将用户的全部未读通知标记为已读
+    pub async fn mark_all_read(&self, user_id: u64, read_at: NaiveDateTime) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET is_read = TRUE, read_at = ? WHERE user_id = ? AND is_read = FALSE",
+            self.get_table_name()
+        ))
+        .bind(read_at)
+        .bind(user_id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 有未读且尚未进入邮件摘要的通知的用户
+    pub async fn find_digest_recipients(&self) -> Result<Vec<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT DISTINCT user_id FROM {} WHERE digest_id IS NULL AND is_read = FALSE",
+            self.get_table_name()
+        ))
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 将用户尚未处理的未读通知归入摘要批次，多实例部署时只有一个实例能领取成功
+    pub async fn claim_for_digest(&self, user_id: u64, digest_id: &str) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET digest_id = ? WHERE user_id = ? AND digest_id IS NULL AND is_read = FALSE",
+            self.get_table_name()
+        ))
+        .bind(digest_id)
+        .bind(user_id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 摘要批次中的通知，最新的在前
+    pub async fn find_by_digest(&self, digest_id: &str) -> Result<Vec<Notification>, AppError> {
+        sqlx::query_as::<_, Notification>(&format!(
+            "SELECT * FROM {} WHERE digest_id = ? ORDER BY id DESC",
+            self.get_table_name()
+        ))
+        .bind(digest_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 邮件发送失败时释放摘要批次，下次重新发送
+    pub async fn release_digest(&self, digest_id: &str) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET digest_id = NULL WHERE digest_id = ?",
+            self.get_table_name()
+        ))
+        .bind(digest_id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除关联对象即将被清理的通知：回收站中超过保留期的项目、词条、翻译和评论，需在清理这些数据之前调用
+    pub async fn purge_by_expired_targets_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            DELETE FROM {} WHERE project_id IN (SELECT id FROM i18n_projects WHERE deleted_at < ?)
+            OR (target_type = ? AND target_id IN (SELECT id FROM i18n_phrases WHERE deleted_at < ?))
+            OR (target_type = ? AND target_id IN (SELECT id FROM i18n_translations WHERE deleted_at < ?))
+            OR (target_type = ? AND target_id IN (SELECT id FROM i18n_comments WHERE {}))
+            "#,
+            self.get_table_name(),
+            comment_repo::EXPIRED_CONDITION
+        ))
+        .bind(before)
+        .bind(NOTIFICATION_TARGET_PHRASE)
+        .bind(before)
+        .bind(NOTIFICATION_TARGET_TRANSLATION)
+        .bind(before)
+        .bind(NOTIFICATION_TARGET_COMMENT)
+        .bind(before)
+        .bind(before)
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<Notification> for NotificationRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_notifications"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "user_id", "kind", "project_id", "is_read", "crt_at"]
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+use crate::{errors::AppError, models::operation_log::OperationLog};
+
+use super::BaseRepository;
+
+pub struct OperationLogRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl OperationLogRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 根据操作对象查找操作日志
+    pub async fn find_by_target(
+        &self,
+        target_type: &str,
+        target_id: &str,
+    ) -> Result<Vec<OperationLog>, AppError> {
+        sqlx::query_as::<_, OperationLog>(&format!(
+            r#"
+            SELECT * FROM {} WHERE target_type = ? AND target_id = ? ORDER BY crt_at DESC
+            "#,
+            self.get_table_name()
+        ))
+        .bind(target_type)
+        .bind(target_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+}
+
+#[async_trait]
+impl BaseRepository<OperationLog> for OperationLogRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_operation_logs"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
 
 use crate::{errors::AppError, models::phrase::Phrase};
 
-use super::BaseRepository;
+use super::{
+    query::{self, Filter, FilterValue},
+    BaseRepository,
+};
 
 pub struct PhraseRepository {
     pool: Arc<MySqlPool>,
@@ -20,7 +24,7 @@ impl PhraseRepository {
     pub async fn find_by_project_id(&self, project_id: u64) -> Result<Vec<Phrase>, AppError> {
         sqlx::query_as::<_, Phrase>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ?
+            SELECT * FROM {} WHERE project_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -30,11 +34,42 @@ impl PhraseRepository {
         .map_err(AppError::from)
     }
 
+    /// 按条件查询未删除词条的id，按id排序
+    pub async fn find_ids(&self, filter: &Filter) -> Result<Vec<u64>, AppError> {
+        let (conditions, params) = filter.to_sql(self.get_query_columns())?;
+        let mut sql = format!(
+            "SELECT id FROM {} WHERE {}",
+            self.get_table_name(),
+            self.live_condition()
+        );
+        if !conditions.is_empty() {
+            sql.push_str(" AND ");
+            sql.push_str(&conditions);
+        }
+        sql.push_str(" ORDER BY id");
+        query::bind_all_scalar(sqlx::query_scalar::<_, u64>(&sql), params)
+            .fetch_all(self.get_pool())
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// 词条所属的项目，包括回收站中的词条
+    pub async fn find_project_id(&self, id: u64) -> Result<Option<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT project_id FROM {} WHERE id = ?",
+            self.get_table_name()
+        ))
+        .bind(id)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据模块ID查找短语列表
     pub async fn find_by_module_id(&self, module_id: u64) -> Result<Vec<Phrase>, AppError> {
         sqlx::query_as::<_, Phrase>(&format!(
             r#"
-            SELECT * FROM {} WHERE module_id = ?
+            SELECT * FROM {} WHERE module_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -52,7 +87,7 @@ impl PhraseRepository {
     ) -> Result<Option<Phrase>, AppError> {
         sqlx::query_as::<_, Phrase>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ? AND `key` = ?
+            SELECT * FROM {} WHERE project_id = ? AND `key` = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -63,11 +98,71 @@ impl PhraseRepository {
         .map_err(AppError::from)
     }
 
+    /// 项目中已占用的标识符，包括回收站中的词条（唯一索引同样约束它们）
+    pub async fn find_keys_by_project(&self, project_id: u64) -> Result<Vec<String>, AppError> {
+        sqlx::query_scalar::<_, String>(&format!(
+            "SELECT `key` FROM {} WHERE project_id = ?",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 占用该标识符的其他词条，包括回收站中的词条；比较规则与列的排序规则一致（不区分大小写）
+    pub async fn find_by_key_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        key: &str,
+        exclude_id: u64,
+    ) -> Result<Option<Phrase>, AppError> {
+        sqlx::query_as::<_, Phrase>(&format!(
+            "SELECT * FROM {} WHERE project_id = ? AND `key` = ? AND id <> ? LIMIT 1",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .bind(key)
+        .bind(exclude_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 按词条所属项目过滤的条件，column 为翻译、截图等的词条ID列
+    pub fn project_condition(&self, column: &str, project_ids: &[u64]) -> (String, Vec<FilterValue>) {
+        if project_ids.is_empty() {
+            // 空集合不匹配任何记录
+            return ("1 = 0".to_string(), vec![]);
+        }
+        let placeholders = vec!["?"; project_ids.len()].join(", ");
+        let sql = format!(
+            "{} IN (SELECT id FROM {} WHERE project_id IN ({}))",
+            column,
+            self.get_table_name(),
+            placeholders
+        );
+        (sql, project_ids.iter().map(|id| FilterValue::from(*id)).collect())
+    }
+
+    /// 项目中各模块直接包含的未删除词条数，未归属模块的词条 module_id 为空
+    pub async fn count_by_module(&self, project_id: u64) -> Result<Vec<(Option<u64>, i64)>, AppError> {
+        sqlx::query_as::<_, (Option<u64>, i64)>(&format!(
+            "SELECT module_id, COUNT(*) FROM {} WHERE project_id = ? AND deleted_at IS NULL GROUP BY module_id",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据类型ID查找短语列表
     pub async fn find_by_type_id(&self, type_id: u64) -> Result<Vec<Phrase>, AppError> {
         sqlx::query_as::<_, Phrase>(&format!(
             r#"
-            SELECT * FROM {} WHERE type_id = ?
+            SELECT * FROM {} WHERE type_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -76,6 +171,78 @@ impl PhraseRepository {
         .await
         .map_err(AppError::from)
     }
+
+    /// 级联软删除项目下的词条，使用与父级相同的删除时间
+    pub async fn soft_delete_by_project(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = ? WHERE project_id = ? AND deleted_at IS NULL",
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 恢复随项目一起删除的词条
+    pub async fn restore_by_project(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE project_id = ? AND deleted_at = ?",
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 级联软删除模块下的词条，使用与父级相同的删除时间
+    pub async fn soft_delete_by_module(
+        &self,
+        conn: &mut MySqlConnection,
+        module_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = ? WHERE module_id = ? AND deleted_at IS NULL",
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(module_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 恢复随模块一起删除的词条
+    pub async fn restore_by_module(
+        &self,
+        conn: &mut MySqlConnection,
+        module_id: u64,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE module_id = ? AND deleted_at = ?",
+            self.get_table_name()
+        ))
+        .bind(module_id)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
 }
 
 #[async_trait]
@@ -84,7 +251,22 @@ impl BaseRepository<Phrase> for PhraseRepository {
         "i18n_phrases"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &[
+            "id", "project_id", "module_id", "type_id", "key", "base_content", "tags", "platforms",
+            "crt_by", "crt_at", "upt_at", "deleted_at",
+        ]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
+
+    fn get_version_column_name(&self) -> Option<&str> {
+        Some("version")
+    }
 }
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::phrase_source::PhraseSource};
+
+use super::BaseRepository;
+
+pub struct PhraseSourceRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl PhraseSourceRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 查找词条某个版本的原文
+    pub async fn find_by_phrase_and_version(
+        &self,
+        phrase_id: u64,
+        source_version: u32,
+    ) -> Result<Option<PhraseSource>, AppError> {
+        sqlx::query_as::<_, PhraseSource>(&format!(
+            r#"
+            SELECT * FROM {} WHERE phrase_id = ? AND source_version = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(phrase_id)
+        .bind(source_version)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 物理删除回收站中超过保留期的词条的原文版本
+    pub async fn purge_by_deleted_phrases_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at < ?)",
+            self.get_table_name()
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<PhraseSource> for PhraseSourceRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_phrase_sources"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
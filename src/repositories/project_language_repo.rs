@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use chrono::NaiveDateTime;
+use sqlx::{MySql, MySqlConnection, MySqlPool, Pool};
+
+use crate::{errors::AppError, models::project_language::ProjectLanguage};
+
+use super::Repository;
+
+const TABLE_NAME: &str = "i18n_project_languages";
+
+/// 项目目标语言，主键为 (project_id, language)，不适用 BaseRepository
+pub struct ProjectLanguageRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl ProjectLanguageRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 项目的全部目标语言，默认语言排在最前
+    pub async fn find_by_project(&self, project_id: u64) -> Result<Vec<ProjectLanguage>, AppError> {
+        sqlx::query_as::<_, ProjectLanguage>(&format!(
+            "SELECT * FROM {} WHERE project_id = ? ORDER BY is_default DESC, language",
+            TABLE_NAME
+        ))
+        .bind(project_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    pub async fn exists(&self, project_id: u64, language: &str) -> Result<bool, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM {} WHERE project_id = ? AND language = ?",
+            TABLE_NAME
+        ))
+        .bind(project_id)
+        .bind(language)
+        .fetch_one(self.get_pool())
+        .await?;
+        Ok(count > 0)
+    }
+
+    pub async fn insert_with(
+        &self,
+        conn: &mut MySqlConnection,
+        entity: &ProjectLanguage,
+    ) -> Result<(), AppError> {
+        sqlx::query(&format!(
+            "INSERT INTO {} (project_id, language, is_default, fallbacks) VALUES (?, ?, ?, ?)",
+            TABLE_NAME
+        ))
+        .bind(entity.project_id)
+        .bind(&entity.language)
+        .bind(entity.is_default)
+        .bind(&entity.fallbacks)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, project_id: u64, language: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE project_id = ? AND language = ?",
+            TABLE_NAME
+        ))
+        .bind(project_id)
+        .bind(language)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 将指定语言设为默认，同时取消项目其他语言的默认标记
+    pub async fn set_default_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        language: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(&format!(
+            "UPDATE {} SET is_default = (language = ?) WHERE project_id = ?",
+            TABLE_NAME
+        ))
+        .bind(language)
+        .bind(project_id)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// 设置回退链，None 表示恢复为默认的 BCP-47 父级语言
+    pub async fn update_fallbacks(
+        &self,
+        project_id: u64,
+        language: &str,
+        fallbacks: Option<Value>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET fallbacks = ? WHERE project_id = ? AND language = ?",
+            TABLE_NAME
+        ))
+        .bind(fallbacks)
+        .bind(project_id)
+        .bind(language)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 物理删除回收站中超过保留期的项目的目标语言
+    pub async fn purge_by_deleted_projects_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE project_id IN (SELECT id FROM i18n_projects WHERE deleted_at < ?)",
+            TABLE_NAME
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+}
+
+impl Repository for ProjectLanguageRepository {
+    fn get_pool(&self) -> &Pool<MySql> {
+        &self.pool
+    }
+}
@@ -65,35 +65,6 @@ impl ProjectRepository {
         .await
         .map_err(AppError::from)
     }
-
-    /// 根据编码或名称搜索项目
-    pub async fn select_page_by_key(
-        &self,
-        page: u32,
-        size: u32,
-        key: &str,
-    ) -> Result<(Vec<Project>, i64), AppError> {
-        let query_count = format!(
-            "SELECT count(*) FROM {} WHERE code LIKE '%{}%' OR name LIKE '%{}%'",
-            self.get_table_name(),
-            key,
-            key
-        );
-        let query = format!(
-            "SELECT * FROM {} WHERE code LIKE '%{}%' OR name LIKE '%{}%' LIMIT ?, ?",
-            self.get_table_name(),
-            key,
-            key
-        );
-        let count = self.select_count(query_count).await?;
-        let projects = sqlx::query_as::<_, Project>(&query)
-            .bind((page - 1) * size)
-            .bind(size)
-            .fetch_all(self.get_pool())
-            .await
-            .map_err(|e| AppError::Database(e.to_string()))?;
-        Ok((projects, count))
-    }
 }
 
 #[async_trait]
@@ -102,7 +73,15 @@ impl BaseRepository<Project> for ProjectRepository {
         "i18n_projects"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "name", "code", "base_language", "owner_id", "status", "crt_at", "upt_at", "deleted_at"]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
 }
@@ -0,0 +1,496 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use sqlx::mysql::{MySql, MySqlArguments};
+use sqlx::query::{QueryAs, QueryScalar};
+
+use crate::errors::AppError;
+
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// 查询参数值，全部以占位符绑定
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+    Str(String),
+    DateTime(NaiveDateTime),
+}
+
+impl From<i64> for FilterValue {
+    fn from(v: i64) -> Self {
+        FilterValue::Int(v)
+    }
+}
+
+impl From<u64> for FilterValue {
+    fn from(v: u64) -> Self {
+        FilterValue::UInt(v)
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(v: bool) -> Self {
+        FilterValue::Bool(v)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(v: &str) -> Self {
+        FilterValue::Str(v.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(v: String) -> Self {
+        FilterValue::Str(v)
+    }
+}
+
+impl From<NaiveDateTime> for FilterValue {
+    fn from(v: NaiveDateTime) -> Self {
+        FilterValue::DateTime(v)
+    }
+}
+
+macro_rules! bind_value {
+    ($query:expr, $value:expr) => {
+        match $value {
+            FilterValue::Int(v) => $query.bind(v),
+            FilterValue::UInt(v) => $query.bind(v),
+            FilterValue::Bool(v) => $query.bind(v),
+            FilterValue::Str(v) => $query.bind(v),
+            FilterValue::DateTime(v) => $query.bind(v),
+        }
+    };
+}
+
+/// 按顺序绑定参数
+pub fn bind_all<'q, T>(
+    mut query: QueryAs<'q, MySql, T, MySqlArguments>,
+    params: Vec<FilterValue>,
+) -> QueryAs<'q, MySql, T, MySqlArguments> {
+    for value in params {
+        query = bind_value!(query, value);
+    }
+    query
+}
+
+/// 按顺序绑定参数（标量查询）
+pub fn bind_all_scalar<'q, T>(
+    mut query: QueryScalar<'q, MySql, T, MySqlArguments>,
+    params: Vec<FilterValue>,
+) -> QueryScalar<'q, MySql, T, MySqlArguments> {
+    for value in params {
+        query = bind_value!(query, value);
+    }
+    query
+}
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Eq(String, FilterValue),
+    In(String, Vec<FilterValue>),
+    // 包含匹配，值中的通配符会被转义
+    Like(String, String),
+    // 闭区间，两端都可省略
+    Range(String, Option<FilterValue>, Option<FilterValue>),
+    // 任一条件成立（OR）
+    Any(Vec<Condition>),
+    // JSON 数组列包含指定字符串
+    JsonContains(String, String),
+    // 由代码构造的 SQL 片段（如子查询），用户输入只能通过参数传入
+    Sql(String, Vec<FilterValue>),
+}
+
+/// 查询条件，各条件之间为 AND
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    conditions: Vec<Condition>,
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn eq(mut self, column: &str, value: impl Into<FilterValue>) -> Self {
+        self.conditions
+            .push(Condition::Eq(column.to_string(), value.into()));
+        self
+    }
+
+    pub fn is_in<V: Into<FilterValue>>(mut self, column: &str, values: Vec<V>) -> Self {
+        self.conditions.push(Condition::In(
+            column.to_string(),
+            values.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
+    pub fn like(mut self, column: &str, value: &str) -> Self {
+        self.conditions
+            .push(Condition::Like(column.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn range(
+        mut self,
+        column: &str,
+        from: Option<impl Into<FilterValue>>,
+        to: Option<impl Into<FilterValue>>,
+    ) -> Self {
+        self.conditions.push(Condition::Range(
+            column.to_string(),
+            from.map(Into::into),
+            to.map(Into::into),
+        ));
+        self
+    }
+
+    /// 关键字在任一列中出现
+    pub fn search(mut self, columns: &[&str], key: &str) -> Self {
+        self.conditions.push(Condition::Any(
+            columns
+                .iter()
+                .map(|column| Condition::Like(column.to_string(), key.to_string()))
+                .collect(),
+        ));
+        self
+    }
+
+    /// JSON 数组列包含任一值，值为空时不添加条件
+    pub fn json_contains_any(mut self, column: &str, values: &[String]) -> Self {
+        if !values.is_empty() {
+            self.conditions.push(Condition::Any(
+                values
+                    .iter()
+                    .map(|value| Condition::JsonContains(column.to_string(), value.clone()))
+                    .collect(),
+            ));
+        }
+        self
+    }
+
+    /// 添加由代码构造的 SQL 片段，片段中不得拼接用户输入
+    pub fn sql(mut self, fragment: &str, params: Vec<FilterValue>) -> Self {
+        self.conditions
+            .push(Condition::Sql(fragment.to_string(), params));
+        self
+    }
+
+    /// 值为 Some 时添加等值条件
+    pub fn eq_opt(self, column: &str, value: Option<impl Into<FilterValue>>) -> Self {
+        match value {
+            Some(value) => self.eq(column, value),
+            None => self,
+        }
+    }
+
+    /// 关键字非空时添加搜索条件
+    pub fn search_opt(self, columns: &[&str], key: Option<&str>) -> Self {
+        match key.map(str::trim).filter(|k| !k.is_empty()) {
+            Some(key) => self.search(columns, key),
+            None => self,
+        }
+    }
+
+    /// 生成 WHERE 子句（不含 WHERE 关键字）和待绑定的参数
+    pub fn to_sql(&self, columns: &[&str]) -> Result<(String, Vec<FilterValue>), AppError> {
+        let mut params = Vec::new();
+        let clauses = self
+            .conditions
+            .iter()
+            .map(|condition| render(condition, columns, &mut params))
+            .collect::<Result<Vec<_>, _>>()?;
+        let clauses: Vec<_> = clauses.into_iter().filter(|c| !c.is_empty()).collect();
+        Ok((clauses.join(" AND "), params))
+    }
+}
+
+fn render(
+    condition: &Condition,
+    columns: &[&str],
+    params: &mut Vec<FilterValue>,
+) -> Result<String, AppError> {
+    match condition {
+        Condition::Eq(column, value) => {
+            let column = check_column(column, columns)?;
+            params.push(value.clone());
+            Ok(format!("{} = ?", column))
+        }
+        Condition::In(column, values) => {
+            let column = check_column(column, columns)?;
+            if values.is_empty() {
+                // 空集合不匹配任何记录
+                return Ok("1 = 0".to_string());
+            }
+            params.extend(values.iter().cloned());
+            let placeholders = vec!["?"; values.len()].join(", ");
+            Ok(format!("{} IN ({})", column, placeholders))
+        }
+        Condition::Like(column, value) => {
+            let column = check_column(column, columns)?;
+            params.push(FilterValue::Str(format!("%{}%", escape_like(value))));
+            Ok(format!("{} LIKE ?", column))
+        }
+        Condition::Range(column, from, to) => {
+            let column = check_column(column, columns)?;
+            let mut parts = Vec::new();
+            if let Some(from) = from {
+                params.push(from.clone());
+                parts.push(format!("{} >= ?", column));
+            }
+            if let Some(to) = to {
+                params.push(to.clone());
+                parts.push(format!("{} <= ?", column));
+            }
+            Ok(parts.join(" AND "))
+        }
+        Condition::JsonContains(column, value) => {
+            let column = check_column(column, columns)?;
+            params.push(FilterValue::Str(serde_json::Value::from(value.as_str()).to_string()));
+            Ok(format!("JSON_CONTAINS({}, ?)", column))
+        }
+        Condition::Sql(fragment, values) => {
+            params.extend(values.iter().cloned());
+            Ok(format!("({})", fragment))
+        }
+        Condition::Any(conditions) => {
+            let parts = conditions
+                .iter()
+                .map(|c| render(c, columns, params))
+                .collect::<Result<Vec<_>, _>>()?;
+            let parts: Vec<_> = parts.into_iter().filter(|p| !p.is_empty()).collect();
+            if parts.is_empty() {
+                return Ok(String::new());
+            }
+            Ok(format!("({})", parts.join(" OR ")))
+        }
+    }
+}
+
+/// 列名必须在白名单中，避免拼接任意标识符；返回加反引号的列名，兼容 `key` 等保留字
+pub fn check_column(column: &str, columns: &[&str]) -> Result<String, AppError> {
+    columns
+        .iter()
+        .find(|c| **c == column)
+        .map(|c| format!("`{}`", c))
+        .ok_or_else(|| AppError::BadRequest(format!("Unsupported column: {}", column)))
+}
+
+/// 解析逗号分隔的列表，如 "web,ios"，忽略空项
+pub fn parse_csv(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// 转义 LIKE 通配符
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sort {
+    pub column: String,
+    pub direction: SortDirection,
+}
+
+impl Sort {
+    pub fn asc(column: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            direction: SortDirection::Asc,
+        }
+    }
+
+    pub fn desc(column: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            direction: SortDirection::Desc,
+        }
+    }
+
+    /// 解析排序参数，如 "name,-crt_at"，"-" 前缀表示降序
+    pub fn parse_list(value: Option<&str>) -> Vec<Sort> {
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.strip_prefix('-') {
+                Some(column) => Sort::desc(column),
+                None => Sort::asc(s.trim_start_matches('+')),
+            })
+            .collect()
+    }
+}
+
+/// 生成 ORDER BY 子句（不含 ORDER BY 关键字）
+pub fn order_by_sql(sorts: &[Sort], columns: &[&str]) -> Result<String, AppError> {
+    let parts = sorts
+        .iter()
+        .map(|sort| {
+            let column = check_column(&sort.column, columns)?;
+            let direction = match sort.direction {
+                SortDirection::Asc => "ASC",
+                SortDirection::Desc => "DESC",
+            };
+            Ok(format!("{} {}", column, direction))
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    Ok(parts.join(", "))
+}
+
+/// 分页查询条件
+#[derive(Debug, Clone)]
+pub struct PageQuery {
+    pub filter: Filter,
+    pub sorts: Vec<Sort>,
+    pub page: u32,
+    pub size: u32,
+}
+
+impl PageQuery {
+    pub fn new(page: u32, size: u32) -> Self {
+        Self {
+            filter: Filter::new(),
+            sorts: Vec::new(),
+            page,
+            size,
+        }
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn sort(mut self, sorts: Vec<Sort>) -> Self {
+        self.sorts = sorts;
+        self
+    }
+
+    /// 返回 (offset, limit)，页码从 1 开始，每页数量限制在 1..=MAX_PAGE_SIZE
+    pub fn limit(&self) -> (u32, u32) {
+        let size = self.size.clamp(1, MAX_PAGE_SIZE);
+        (self.page.max(1).saturating_sub(1).saturating_mul(size), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[&str] = &["name", "code", "status", "crt_at"];
+
+    #[test]
+    fn test_filter_binds_values() {
+        let (sql, params) = Filter::new()
+            .eq("status", true)
+            .search(&["name", "code"], "a'b")
+            .is_in("code", vec!["x", "y"])
+            .to_sql(COLUMNS)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "`status` = ? AND (`name` LIKE ? OR `code` LIKE ?) AND `code` IN (?, ?)"
+        );
+        assert_eq!(
+            params,
+            vec![
+                FilterValue::Bool(true),
+                FilterValue::Str("%a'b%".into()),
+                FilterValue::Str("%a'b%".into()),
+                FilterValue::Str("x".into()),
+                FilterValue::Str("y".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_and_empty_in() {
+        let (sql, params) = Filter::new()
+            .range("crt_at", Some(1i64), None::<i64>)
+            .range("status", None::<i64>, None::<i64>)
+            .is_in::<u64>("code", vec![])
+            .to_sql(COLUMNS)
+            .unwrap();
+        assert_eq!(sql, "`crt_at` >= ? AND 1 = 0");
+        assert_eq!(params, vec![FilterValue::Int(1)]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_columns() {
+        assert!(Filter::new()
+            .eq("name; DROP TABLE i18n_users", "x")
+            .to_sql(COLUMNS)
+            .is_err());
+        assert!(order_by_sql(&[Sort::asc("password")], COLUMNS).is_err());
+    }
+
+    #[test]
+    fn test_json_contains_and_sql() {
+        let columns = &["tags", "status"];
+        let (sql, params) = Filter::new()
+            .json_contains_any("tags", &["ui".to_string(), "a\"b".to_string()])
+            .json_contains_any("tags", &[])
+            .sql("EXISTS (SELECT 1 FROM t WHERE t.x = ?)", vec![FilterValue::from("en")])
+            .to_sql(columns)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "(JSON_CONTAINS(`tags`, ?) OR JSON_CONTAINS(`tags`, ?)) AND (EXISTS (SELECT 1 FROM t WHERE t.x = ?))"
+        );
+        assert_eq!(
+            params,
+            vec![
+                FilterValue::Str("\"ui\"".into()),
+                FilterValue::Str("\"a\\\"b\"".into()),
+                FilterValue::Str("en".into()),
+            ]
+        );
+        assert_eq!(parse_csv(Some(" web, ,ios")), vec!["web", "ios"]);
+        assert!(parse_csv(None).is_empty());
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like(r"50%_off\"), r"50\%\_off\\");
+    }
+
+    #[test]
+    fn test_sort_parse() {
+        let sorts = Sort::parse_list(Some("name, -crt_at,"));
+        assert_eq!(sorts, vec![Sort::asc("name"), Sort::desc("crt_at")]);
+        assert_eq!(order_by_sql(&sorts, COLUMNS).unwrap(), "`name` ASC, `crt_at` DESC");
+        assert!(Sort::parse_list(None).is_empty());
+    }
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(PageQuery::new(1, 20).limit(), (0, 20));
+        assert_eq!(PageQuery::new(3, 20).limit(), (40, 20));
+        assert_eq!(PageQuery::new(0, 0).limit(), (0, 1));
+        assert_eq!(PageQuery::new(2, 10_000).limit(), (MAX_PAGE_SIZE, MAX_PAGE_SIZE));
+    }
+}
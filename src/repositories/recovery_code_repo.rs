@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::recovery_code::RecoveryCode};
+
+use super::BaseRepository;
+
+pub struct RecoveryCodeRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl RecoveryCodeRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 使用恢复码，成功返回 true；已使用或不存在返回 false
+    pub async fn consume(&self, user_id: u64, code_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET used_at = NOW(), upt_by = 'mfa' WHERE user_id = ? AND code_hash = ? AND used_at IS NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 剩余可用的恢复码数量
+    pub async fn count_unused(&self, user_id: u64) -> Result<i64, AppError> {
+        sqlx::query_scalar::<_, i64>(&format!(
+            r#"
+            SELECT COUNT(*) FROM {} WHERE user_id = ? AND used_at IS NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(user_id)
+        .fetch_one(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 删除用户的全部恢复码
+    pub async fn delete_by_user_id(
+        &self,
+        conn: &mut MySqlConnection,
+        user_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            DELETE FROM {} WHERE user_id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<RecoveryCode> for RecoveryCodeRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_user_recovery_codes"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
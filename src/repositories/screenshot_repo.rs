@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
 
 use crate::{errors::AppError, models::screenshot::Screenshot};
 
@@ -16,11 +17,23 @@ impl ScreenshotRepository {
         Self { pool }
     }
 
+    /// 截图所属的项目（通过词条），包括回收站中的数据
+    pub async fn find_project_id(&self, id: u64) -> Result<Option<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT p.project_id FROM {} s JOIN i18n_phrases p ON p.id = s.phrase_id WHERE s.id = ?",
+            self.get_table_name()
+        ))
+        .bind(id)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据项目ID查找截图列表
     pub async fn find_by_project_id(&self, project_id: u64) -> Result<Vec<Screenshot>, AppError> {
         sqlx::query_as::<_, Screenshot>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ?
+            SELECT * FROM {} WHERE project_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -34,7 +47,7 @@ impl ScreenshotRepository {
     pub async fn find_by_phrase_id(&self, phrase_id: u64) -> Result<Vec<Screenshot>, AppError> {
         sqlx::query_as::<_, Screenshot>(&format!(
             r#"
-            SELECT * FROM {} WHERE phrase_id = ?
+            SELECT * FROM {} WHERE phrase_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -43,15 +56,78 @@ impl ScreenshotRepository {
         .await
         .map_err(AppError::from)
     }
+
+    /// 级联软删除已删除词条下的截图，使用与词条相同的删除时间
+    pub async fn soft_delete_by_deleted_phrases(
+        &self,
+        conn: &mut MySqlConnection,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET deleted_at = ?
+            WHERE deleted_at IS NULL
+              AND phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at = ?)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 恢复随词条一起删除的截图（词条已恢复的部分）
+    pub async fn restore_by_restored_phrases(
+        &self,
+        conn: &mut MySqlConnection,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET deleted_at = NULL
+            WHERE deleted_at = ?
+              AND phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at IS NULL)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 查询回收站中早于指定时间的截图，清理时需要先删除存储对象
+    pub async fn find_deleted_before(&self, before: NaiveDateTime) -> Result<Vec<Screenshot>, AppError> {
+        sqlx::query_as::<_, Screenshot>(&format!(
+            r#"
+            SELECT * FROM {} WHERE deleted_at < ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(before)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
 }
 
 #[async_trait]
 impl BaseRepository<Screenshot> for ScreenshotRepository {
     fn get_table_name(&self) -> &str {
-        "i18n_screenshots"
+        "i18n_phrase_screenshots"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "phrase_id", "crt_by", "crt_at", "upt_at", "deleted_at"]
     }
 
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
 }
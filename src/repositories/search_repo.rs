@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use sqlx::{MySql, MySqlPool, Pool};
+
+use crate::{
+    dtos::search::SearchKind,
+    errors::AppError,
+    models::search_hit::SearchHit,
+};
+
+use super::{
+    query::{self, FilterValue},
+    Repository,
+};
+
+const PHRASE_TABLE: &str = "i18n_phrases";
+const TRANSLATION_TABLE: &str = "i18n_translations";
+const TERM_TABLE: &str = "i18n_terms";
+
+/// 全文检索条件
+pub struct SearchCriteria<'a> {
+    // BOOLEAN MODE 查询串
+    pub query: &'a str,
+    pub kinds: &'a [SearchKind],
+    // None 表示不限制项目
+    pub project_ids: Option<&'a [u64]>,
+    // 只作用于翻译和术语
+    pub language: Option<&'a str>,
+    // 按模块过滤的条件（作用于词条的模块ID列 p.module_id）及参数，指定后不检索术语
+    pub module_condition: Option<(String, Vec<FilterValue>)>,
+}
+
+/// 基于 MySQL FULLTEXT（ngram 分词）索引的检索，索引由 MySQL 在写入时同步维护
+pub struct SearchRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl SearchRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 按相关度分页检索，返回 (命中列表, 总数)
+    pub async fn search(
+        &self,
+        criteria: &SearchCriteria<'_>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<(Vec<SearchHit>, i64), AppError> {
+        let (sql, params) = Self::union_sql(criteria);
+        if sql.is_empty() {
+            return Ok((vec![], 0));
+        }
+
+        let count_sql = format!("SELECT COUNT(*) FROM ({}) s", sql);
+        let total = query::bind_all_scalar(sqlx::query_scalar::<_, i64>(&count_sql), params.clone())
+            .fetch_one(self.get_pool())
+            .await?;
+        if total == 0 {
+            return Ok((vec![], 0));
+        }
+
+        let page_sql = format!("{} ORDER BY score DESC, id DESC LIMIT ?, ?", sql);
+        let hits = query::bind_all(sqlx::query_as::<_, SearchHit>(&page_sql), params)
+            .bind(offset)
+            .bind(limit)
+            .fetch_all(self.get_pool())
+            .await?;
+        Ok((hits, total))
+    }
+
+    /// 拼接各来源的子查询，返回 (SQL, 参数)
+    fn union_sql(criteria: &SearchCriteria<'_>) -> (String, Vec<FilterValue>) {
+        let mut parts = Vec::new();
+        let mut params = Vec::new();
+        // 令牌没有可访问的项目时不会有任何结果
+        if criteria.project_ids.is_some_and(|ids| ids.is_empty()) {
+            return (String::new(), params);
+        }
+
+        for kind in criteria.kinds {
+            if criteria.module_condition.is_some() && *kind == SearchKind::Term {
+                continue;
+            }
+            let (select, project_column, language_column) = match kind {
+                SearchKind::Phrase => (
+                    format!(
+                        "SELECT 'phrase' AS kind, p.id, p.project_id, p.id AS phrase_id, \
+                         CAST(NULL AS CHAR) AS language, p.`key`, \
+                         CONCAT_WS('\\n', p.base_content, p.context) AS content, \
+                         MATCH (p.`key`, p.base_content, p.context) AGAINST (? IN BOOLEAN MODE) AS score \
+                         FROM {} p \
+                         WHERE p.deleted_at IS NULL \
+                         AND MATCH (p.`key`, p.base_content, p.context) AGAINST (? IN BOOLEAN MODE)",
+                        PHRASE_TABLE
+                    ),
+                    "p.project_id",
+                    None,
+                ),
+                SearchKind::Translation => (
+                    format!(
+                        "SELECT 'translation' AS kind, t.id, p.project_id, t.phrase_id, \
+                         t.language, p.`key`, t.content, \
+                         MATCH (t.content) AGAINST (? IN BOOLEAN MODE) AS score \
+                         FROM {} t JOIN {} p ON p.id = t.phrase_id \
+                         WHERE t.deleted_at IS NULL AND p.deleted_at IS NULL \
+                         AND MATCH (t.content) AGAINST (? IN BOOLEAN MODE)",
+                        TRANSLATION_TABLE, PHRASE_TABLE
+                    ),
+                    "p.project_id",
+                    Some("t.language"),
+                ),
+                SearchKind::Term => (
+                    format!(
+                        "SELECT 'term' AS kind, m.id, m.project_id, CAST(NULL AS UNSIGNED) AS phrase_id, \
+                         m.language, m.source_term AS `key`, \
+                         CONCAT_WS('\\n', m.target_term, m.description) AS content, \
+                         MATCH (m.source_term, m.target_term, m.description) AGAINST (? IN BOOLEAN MODE) AS score \
+                         FROM {} m \
+                         WHERE MATCH (m.source_term, m.target_term, m.description) AGAINST (? IN BOOLEAN MODE)",
+                        TERM_TABLE
+                    ),
+                    "m.project_id",
+                    Some("m.language"),
+                ),
+            };
+
+            let mut sql = select;
+            params.push(criteria.query.into());
+            params.push(criteria.query.into());
+            if let Some(project_ids) = criteria.project_ids {
+                sql.push_str(&format!(
+                    " AND {} IN ({})",
+                    project_column,
+                    vec!["?"; project_ids.len()].join(", ")
+                ));
+                params.extend(project_ids.iter().map(|id| FilterValue::from(*id)));
+            }
+            if let (Some(column), Some(language)) = (language_column, criteria.language) {
+                sql.push_str(&format!(" AND {} = ?", column));
+                params.push(language.into());
+            }
+            if let Some((condition, module_params)) = &criteria.module_condition {
+                sql.push_str(&format!(" AND {}", condition));
+                params.extend(module_params.iter().cloned());
+            }
+            parts.push(format!("({})", sql));
+        }
+
+        (parts.join(" UNION ALL "), params)
+    }
+}
+
+impl Repository for SearchRepository {
+    fn get_pool(&self) -> &Pool<MySql> {
+        &self.pool
+    }
+}
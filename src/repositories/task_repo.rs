@@ -0,0 +1,235 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    errors::AppError,
+    models::task::{Task, TaskPhrase, TaskProgress},
+};
+
+use super::{query::PageQuery, BaseRepository};
+
+const TASK_PHRASE_TABLE: &str = "i18n_task_phrases";
+const INSERT_CHUNK_SIZE: usize = 500;
+
+pub struct TaskRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl TaskRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 写入任务包含的词条
+    pub async fn insert_phrases_with(
+        &self,
+        conn: &mut MySqlConnection,
+        task_id: u64,
+        phrase_ids: &[u64],
+    ) -> Result<(), AppError> {
+        for chunk in phrase_ids.chunks(INSERT_CHUNK_SIZE) {
+            let sql = format!(
+                "INSERT IGNORE INTO {} (task_id, phrase_id) VALUES {}",
+                TASK_PHRASE_TABLE,
+                vec!["(?, ?)"; chunk.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql);
+            for phrase_id in chunk {
+                query = query.bind(task_id).bind(phrase_id);
+            }
+            query.execute(&mut *conn).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn delete_phrases_with(
+        &self,
+        conn: &mut MySqlConnection,
+        task_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!("DELETE FROM {} WHERE task_id = ?", TASK_PHRASE_TABLE))
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 包含该词条且未取消的任务，指定语言时只查询该语言的任务
+    pub async fn find_active_by_phrase_with(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase_id: u64,
+        language: Option<&str>,
+    ) -> Result<Vec<Task>, AppError> {
+        let mut sql = format!(
+            r#"
+            SELECT k.* FROM {} k JOIN {} tp ON tp.task_id = k.id
+            WHERE tp.phrase_id = ? AND k.status <> 'cancelled'
+            "#,
+            self.get_table_name(),
+            TASK_PHRASE_TABLE
+        );
+        if language.is_some() {
+            sql.push_str(" AND k.language = ?");
+        }
+        let mut query = sqlx::query_as::<_, Task>(&sql).bind(phrase_id);
+        if let Some(language) = language {
+            query = query.bind(language);
+        }
+        query.fetch_all(&mut *conn).await.map_err(AppError::from)
+    }
+
+    /// 统计任务进度：未删除的词条数，以及目标语言下未过期、已审核的翻译数
+    pub async fn find_progress_with(
+        &self,
+        conn: &mut MySqlConnection,
+        task_id: u64,
+    ) -> Result<TaskProgress, AppError> {
+        let progress = sqlx::query_as::<_, TaskProgress>(&format!(
+            r#"
+            SELECT COUNT(*) AS total,
+                   COUNT(CASE WHEN t.status IN ('pending', 'reviewed', 'published') THEN 1 END) AS translated,
+                   COUNT(CASE WHEN t.status IN ('reviewed', 'published') THEN 1 END) AS reviewed
+            FROM {} tp
+            JOIN {} k ON k.id = tp.task_id
+            JOIN i18n_phrases p ON p.id = tp.phrase_id AND p.deleted_at IS NULL
+            LEFT JOIN i18n_translations t
+                   ON t.phrase_id = p.id AND t.language = k.language AND t.deleted_at IS NULL
+            WHERE tp.task_id = ?
+            GROUP BY tp.task_id
+            "#,
+            TASK_PHRASE_TABLE,
+            self.get_table_name()
+        ))
+        .bind(task_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(progress.unwrap_or_default())
+    }
+
+    /// 保存进度和状态，完成时间可能被清空，不能使用跳过空值的通用更新
+    pub async fn update_progress_with(
+        &self,
+        conn: &mut MySqlConnection,
+        task: &Task,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET total = ?, translated = ?, reviewed = ?, status = ?, completed_at = ?
+            WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(task.total)
+        .bind(task.translated)
+        .bind(task.reviewed)
+        .bind(task.status)
+        .bind(task.completed_at)
+        .bind(task.id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 任务中的词条及目标语言下的翻译，按词条id排序
+    pub async fn find_phrases(
+        &self,
+        task: &Task,
+        page_query: &PageQuery,
+    ) -> Result<(Vec<TaskPhrase>, i64), AppError> {
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            r#"
+            SELECT COUNT(*) FROM {} tp
+            JOIN i18n_phrases p ON p.id = tp.phrase_id AND p.deleted_at IS NULL
+            WHERE tp.task_id = ?
+            "#,
+            TASK_PHRASE_TABLE
+        ))
+        .bind(task.id)
+        .fetch_one(self.get_pool())
+        .await?;
+
+        let (offset, limit) = page_query.limit();
+        let phrases = sqlx::query_as::<_, TaskPhrase>(&format!(
+            r#"
+            SELECT p.id AS phrase_id, p.`key`, p.base_content, t.id AS translation_id,
+                   t.status, t.translated_by, t.reviewed_by
+            FROM {} tp
+            JOIN i18n_phrases p ON p.id = tp.phrase_id AND p.deleted_at IS NULL
+            LEFT JOIN i18n_translations t
+                   ON t.phrase_id = p.id AND t.language = ? AND t.deleted_at IS NULL
+            WHERE tp.task_id = ?
+            ORDER BY p.id
+            LIMIT ?, ?
+            "#,
+            TASK_PHRASE_TABLE
+        ))
+        .bind(&task.language)
+        .bind(task.id)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(self.get_pool())
+        .await?;
+        Ok((phrases, total))
+    }
+
+    /// 物理删除回收站中超过保留期的词条在任务中的记录
+    pub async fn purge_phrases_by_deleted_phrases_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at < ?)",
+            TASK_PHRASE_TABLE
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除回收站中超过保留期的项目的任务及任务词条
+    pub async fn purge_by_deleted_projects_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let condition = "project_id IN (SELECT id FROM i18n_projects WHERE deleted_at < ?)";
+        let phrases = sqlx::query(&format!(
+            "DELETE FROM {} WHERE task_id IN (SELECT id FROM {} WHERE {})",
+            TASK_PHRASE_TABLE,
+            self.get_table_name(),
+            condition
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        let tasks = sqlx::query(&format!("DELETE FROM {} WHERE {}", self.get_table_name(), condition))
+            .bind(before)
+            .execute(&mut *conn)
+            .await?;
+        Ok(phrases.rows_affected() + tasks.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<Task> for TaskRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_tasks"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &[
+            "id", "project_id", "language", "translator_id", "reviewer_id", "status", "due_at",
+            "crt_at", "upt_at",
+        ]
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -30,6 +30,18 @@ impl TermRepository {
         .map_err(AppError::from)
     }
 
+    /// 术语所属的项目
+    pub async fn find_project_id(&self, id: u64) -> Result<Option<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT project_id FROM {} WHERE id = ?",
+            self.get_table_name()
+        ))
+        .bind(id)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据项目ID和术语代码查找术语
     pub async fn find_by_project_and_code(
         &self,
@@ -56,6 +68,13 @@ impl BaseRepository<Term> for TermRepository {
         "i18n_terms"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &[
+            "id", "project_id", "source_term", "target_term", "language", "crt_by", "crt_at",
+            "upt_at",
+        ]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::translation_history::TranslationHistory};
+
+use super::BaseRepository;
+
+pub struct TranslationHistoryRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl TranslationHistoryRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 时间范围内有修改的翻译的完整历史，按翻译和时间排序
+    ///
+    /// 返回范围之前的记录是为了判断范围内每次修改之前的状态
+    pub async fn find_touched_between(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        project_id: Option<u64>,
+    ) -> Result<Vec<TranslationHistory>, AppError> {
+        let project_condition = match project_id {
+            Some(_) => "AND p.project_id = ?",
+            None => "",
+        };
+        let sql = format!(
+            r#"
+            SELECT h.* FROM {table} h
+            WHERE h.translation_id IN (
+                SELECT DISTINCT r.translation_id FROM {table} r
+                JOIN i18n_translations t ON t.id = r.translation_id
+                JOIN i18n_phrases p ON p.id = t.phrase_id
+                WHERE r.crt_at >= ? AND r.crt_at < ? {project_condition}
+            )
+            AND h.crt_at < ?
+            ORDER BY h.translation_id, h.id
+            "#,
+            table = self.get_table_name(),
+            project_condition = project_condition,
+        );
+        let mut query = sqlx::query_as::<_, TranslationHistory>(&sql).bind(from).bind(to);
+        if let Some(project_id) = project_id {
+            query = query.bind(project_id);
+        }
+        query
+            .bind(to)
+            .fetch_all(self.get_pool())
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// 为即将被标记为过期的翻译写入历史，需在 mark_outdated_by_phrase 之前调用
+    pub async fn insert_outdated_by_phrase(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase_id: u64,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            INSERT INTO {} (translation_id, content, version, status, user_id, crt_by)
+            SELECT id, content, version + 1, 'outdated', ?, ? FROM i18n_translations
+            WHERE phrase_id = ? AND deleted_at IS NULL AND status <> 'outdated'
+            "#,
+            self.get_table_name()
+        ))
+        .bind(operator_id)
+        .bind(operator)
+        .bind(phrase_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除翻译的全部历史
+    pub async fn delete_by_translation_with(
+        &self,
+        conn: &mut MySqlConnection,
+        translation_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE translation_id = ?",
+            self.get_table_name()
+        ))
+        .bind(translation_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除回收站中超过保留期的翻译的历史
+    pub async fn purge_by_deleted_translations_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE translation_id IN (SELECT id FROM i18n_translations WHERE deleted_at < ?)",
+            self.get_table_name()
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<TranslationHistory> for TranslationHistoryRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_translation_history"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
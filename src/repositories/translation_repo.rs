@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
 
 use crate::{errors::AppError, models::translation::Translation};
 
@@ -16,6 +17,18 @@ impl TranslationRepository {
         Self { pool }
     }
 
+    /// 翻译所属的项目（通过词条），包括回收站中的数据
+    pub async fn find_project_id(&self, id: u64) -> Result<Option<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            "SELECT p.project_id FROM {} t JOIN i18n_phrases p ON p.id = t.phrase_id WHERE t.id = ?",
+            self.get_table_name()
+        ))
+        .bind(id)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
     /// 根据项目ID和语言代码查找翻译列表
     pub async fn find_by_project_and_language(
         &self,
@@ -24,7 +37,7 @@ impl TranslationRepository {
     ) -> Result<Vec<Translation>, AppError> {
         sqlx::query_as::<_, Translation>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ? AND language_code = ?
+            SELECT * FROM {} WHERE project_id = ? AND language_code = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -43,7 +56,7 @@ impl TranslationRepository {
     ) -> Result<Option<Translation>, AppError> {
         sqlx::query_as::<_, Translation>(&format!(
             r#"
-            SELECT * FROM {} WHERE phrase_id = ? AND language_code = ?
+            SELECT * FROM {} WHERE phrase_id = ? AND language_code = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -58,7 +71,7 @@ impl TranslationRepository {
     pub async fn find_by_project_id(&self, project_id: u64) -> Result<Vec<Translation>, AppError> {
         sqlx::query_as::<_, Translation>(&format!(
             r#"
-            SELECT * FROM {} WHERE project_id = ?
+            SELECT * FROM {} WHERE project_id = ? AND deleted_at IS NULL
             "#,
             self.get_table_name()
         ))
@@ -67,6 +80,133 @@ impl TranslationRepository {
         .await
         .map_err(AppError::from)
     }
+
+    /// 项目内指定语言的翻译，只包含未删除的词条
+    pub async fn find_by_project_and_languages(
+        &self,
+        project_id: u64,
+        languages: &[String],
+    ) -> Result<Vec<Translation>, AppError> {
+        if languages.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            r#"
+            SELECT t.* FROM {} t JOIN i18n_phrases p ON p.id = t.phrase_id
+            WHERE p.project_id = ? AND p.deleted_at IS NULL AND t.deleted_at IS NULL
+            AND t.language IN ({})
+            "#,
+            self.get_table_name(),
+            vec!["?"; languages.len()].join(", ")
+        );
+        let mut query = sqlx::query_as::<_, Translation>(&sql).bind(project_id);
+        for language in languages {
+            query = query.bind(language);
+        }
+        query.fetch_all(self.get_pool()).await.map_err(AppError::from)
+    }
+
+    /// 各模块直接包含的词条在各语言下的已翻译数和已审核数：(模块ID, 语言, 已翻译, 已审核)
+    pub async fn count_progress_by_module(
+        &self,
+        project_id: u64,
+    ) -> Result<Vec<(Option<u64>, String, i64, i64)>, AppError> {
+        sqlx::query_as::<_, (Option<u64>, String, i64, i64)>(&format!(
+            r#"
+            SELECT p.module_id, t.language,
+                   COUNT(CASE WHEN t.status IN ('pending', 'reviewed', 'published') THEN 1 END),
+                   COUNT(CASE WHEN t.status IN ('reviewed', 'published') THEN 1 END)
+            FROM {} t JOIN i18n_phrases p ON p.id = t.phrase_id
+            WHERE p.project_id = ? AND p.deleted_at IS NULL AND t.deleted_at IS NULL
+            GROUP BY p.module_id, t.language
+            "#,
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 词条下尚未过期的翻译的译者
+    pub async fn find_translators_by_phrase_with(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase_id: u64,
+    ) -> Result<Vec<u64>, AppError> {
+        sqlx::query_scalar::<_, u64>(&format!(
+            r#"
+            SELECT DISTINCT translated_by FROM {}
+            WHERE phrase_id = ? AND deleted_at IS NULL AND status <> 'outdated'
+              AND translated_by IS NOT NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(phrase_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 原文修改后将词条下的翻译标记为过期，同时递增版本号使正在编辑的客户端收到冲突
+    pub async fn mark_outdated_by_phrase(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET status = 'outdated', version = version + 1
+            WHERE phrase_id = ? AND deleted_at IS NULL AND status <> 'outdated'
+            "#,
+            self.get_table_name()
+        ))
+        .bind(phrase_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 级联软删除已删除词条下的翻译，使用与词条相同的删除时间
+    pub async fn soft_delete_by_deleted_phrases(
+        &self,
+        conn: &mut MySqlConnection,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET deleted_at = ?
+            WHERE deleted_at IS NULL
+              AND phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at = ?)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 恢复随词条一起删除的翻译（词条已恢复的部分）
+    pub async fn restore_by_restored_phrases(
+        &self,
+        conn: &mut MySqlConnection,
+        deleted_at: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET deleted_at = NULL
+            WHERE deleted_at = ?
+              AND phrase_id IN (SELECT id FROM i18n_phrases WHERE deleted_at IS NULL)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
 }
 
 #[async_trait]
@@ -75,7 +215,22 @@ impl BaseRepository<Translation> for TranslationRepository {
         "i18n_translations"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &[
+            "id", "phrase_id", "language", "status", "translated_by", "reviewed_by", "crt_by",
+            "crt_at", "upt_at", "deleted_at",
+        ]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    fn get_deleted_column_name(&self) -> Option<&str> {
+        Some("deleted_at")
+    }
+
+    fn get_version_column_name(&self) -> Option<&str> {
+        Some("version")
+    }
 }
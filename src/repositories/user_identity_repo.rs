@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+use crate::{errors::AppError, models::user_identity::UserIdentity};
+
+use super::BaseRepository;
+
+pub struct UserIdentityRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl UserIdentityRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 根据提供方和用户标识查找关联
+    pub async fn find_by_provider_and_subject(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<UserIdentity>, AppError> {
+        sqlx::query_as::<_, UserIdentity>(&format!(
+            r#"
+            SELECT * FROM {} WHERE provider = ? AND subject = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(provider)
+        .bind(subject)
+        .fetch_optional(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+}
+
+#[async_trait]
+impl BaseRepository<UserIdentity> for UserIdentityRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_user_identities"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
 
-use crate::{errors::AppError, models::user::User};
+use crate::{
+    errors::AppError,
+    models::{enums::UserRole, user::User},
+};
 
 use super::BaseRepository;
 
@@ -31,18 +35,144 @@ impl UserRepository {
         self.select_by_column_optional("phone", phone).await
     }
 
-    /// 根据用户名、手机号、邮箱、昵称、真实姓名搜索用户
-    pub async fn select_page_by_key(&self,page: u32, size: u32, key: &str) -> Result<(Vec<User>, i64), AppError> {
-        let query_count = format!("SELECT count(*) FROM {} WHERE username LIKE '%{}%' OR email LIKE '%{}%' OR phone LIKE '%{}%' OR realname LIKE '%{}%' OR nickname LIKE '%{}%'", self.get_table_name(), key, key, key, key, key);
-        let query = format!("SELECT * FROM {} WHERE username LIKE '%{}%' OR email LIKE '%{}%' OR phone LIKE '%{}%' OR realname LIKE '%{}%' OR nickname LIKE '%{}%' LIMIT ?, ?", self.get_table_name(), key, key, key, key, key);
-        let count = self.select_count(query_count).await?;
-        let users = sqlx::query_as::<_, User>(&query)
-            .bind((page - 1) * size)
-            .bind(size)
-            .fetch_all(self.get_pool())
-            .await
-            .map_err(|e| AppError::Database(e.to_string()))?;
-        Ok((users, count))
+    /// 登录成功：记录最后登录时间并清空失败次数
+    pub async fn record_login_success(&self, id: u64) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET last_login = NOW(), failed_attempts = 0, locked_until = NULL WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 登录失败：失败次数加一，达到上限时写入锁定截止时间
+    pub async fn record_login_failure(
+        &self,
+        id: u64,
+        locked_until: Option<NaiveDateTime>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET failed_attempts = failed_attempts + 1, locked_until = COALESCE(?, locked_until) WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(locked_until)
+        .bind(id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 解除账号锁定
+    pub async fn unlock(&self, id: u64) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET failed_attempts = 0, locked_until = NULL WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 查询用户的角色（Casbin 分组规则 user:{username} -> role:{role}）
+    pub async fn find_roles(&self, username: &str) -> Result<Vec<String>, AppError> {
+        let roles = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT v1 FROM casbin_rule WHERE ptype = 'g' AND v0 = ? AND v1 LIKE 'role:%'
+            "#,
+        )
+        .bind(format!("user:{}", username))
+        .fetch_all(self.get_pool())
+        .await?;
+        Ok(roles
+            .into_iter()
+            .map(|role| role.trim_start_matches("role:").to_string())
+            .collect())
+    }
+
+    /// 用户是否拥有任一指定角色
+    pub async fn has_any_role(&self, username: &str, roles: &[UserRole]) -> Result<bool, AppError> {
+        let owned = self.find_roles(username).await?;
+        Ok(roles.iter().any(|role| owned.contains(&role.to_string())))
+    }
+
+    /// 只允许管理员和项目经理操作，action 用于错误提示
+    pub async fn require_manager(&self, username: &str, action: &str) -> Result<(), AppError> {
+        let managers = [UserRole::Admin, UserRole::ProjectManager];
+        if !self.has_any_role(username, &managers).await? {
+            return Err(AppError::Forbidden(format!(
+                "only project managers can {}",
+                action
+            )));
+        }
+        Ok(())
+    }
+
+    /// 保存待启用的 TOTP 密钥，启用前不生效
+    pub async fn set_totp_secret(&self, id: u64, secret: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET totp_secret = ?, totp_last_step = NULL WHERE id = ? AND totp_enabled = FALSE
+            "#,
+            self.get_table_name()
+        ))
+        .bind(secret)
+        .bind(id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 启用两步验证
+    pub async fn enable_totp(&self, conn: &mut MySqlConnection, id: u64) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET totp_enabled = TRUE WHERE id = ? AND totp_secret IS NOT NULL
+            "#,
+            self.get_table_name()
+        ))
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 关闭两步验证并清除密钥
+    pub async fn disable_totp(&self, conn: &mut MySqlConnection, id: u64) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET totp_enabled = FALSE, totp_secret = NULL, totp_last_step = NULL WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 记录已使用的时间窗口，窗口不大于已记录值时返回 false（验证码被重放）
+    pub async fn record_totp_step(&self, id: u64, step: u64) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET totp_last_step = ? WHERE id = ? AND (totp_last_step IS NULL OR totp_last_step < ?)
+            "#,
+            self.get_table_name()
+        ))
+        .bind(step)
+        .bind(id)
+        .bind(step)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 }
 
@@ -52,6 +182,10 @@ impl BaseRepository<User> for UserRepository {
         "i18n_users"
     }
 
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "username", "email", "phone", "realname", "nickname", "status", "last_login", "crt_at", "upt_at"]
+    }
+
     fn get_pool(&self) -> &MySqlPool {
         &self.pool
     }
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::webhook_delivery::WebhookDelivery};
+
+use super::BaseRepository;
+
+pub struct WebhookDeliveryRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl WebhookDeliveryRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 到期待投递的记录
+    pub async fn find_due(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            SELECT * FROM {} WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at, id LIMIT ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 领取一条到期记录：推迟其下次投递时间，多实例部署时只有一个实例能领取成功
+    pub async fn claim(
+        &self,
+        id: u64,
+        now: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET next_attempt_at = ?
+            WHERE id = ? AND status = 'pending' AND next_attempt_at <= ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(lease_until)
+        .bind(id)
+        .bind(now)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 记录一次投递的结果
+    pub async fn record_attempt(&self, delivery: &WebhookDelivery) -> Result<bool, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            UPDATE {} SET status = ?, attempts = ?, next_attempt_at = ?, response_status = ?,
+                response_body = ?, error = ?, delivered_at = ?, upt_at = ?
+            WHERE id = ?
+            "#,
+            self.get_table_name()
+        ))
+        .bind(delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(delivery.response_status)
+        .bind(&delivery.response_body)
+        .bind(&delivery.error)
+        .bind(delivery.delivered_at)
+        .bind(delivery.upt_at)
+        .bind(delivery.id)
+        .execute(self.get_pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 删除 Webhook 的全部投递记录
+    pub async fn delete_by_webhook_with(
+        &self,
+        conn: &mut MySqlConnection,
+        webhook_id: u64,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE webhook_id = ?",
+            self.get_table_name()
+        ))
+        .bind(webhook_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 物理删除回收站中超过保留期的项目的 Webhook 投递记录
+    pub async fn purge_by_deleted_projects_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            r#"
+            DELETE FROM {} WHERE webhook_id IN (
+                SELECT w.id FROM i18n_webhooks w JOIN i18n_projects p ON p.id = w.project_id
+                WHERE p.deleted_at < ?
+            )
+            "#,
+            self.get_table_name()
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<WebhookDelivery> for WebhookDeliveryRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_webhook_deliveries"
+    }
+
+    fn get_query_columns(&self) -> &[&str] {
+        &["id", "webhook_id", "event", "status", "crt_at"]
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{errors::AppError, models::webhook::Webhook};
+
+use super::BaseRepository;
+
+pub struct WebhookRepository {
+    pool: Arc<MySqlPool>,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self { pool }
+    }
+
+    /// 项目的全部 Webhook
+    pub async fn find_by_project(&self, project_id: u64) -> Result<Vec<Webhook>, AppError> {
+        sqlx::query_as::<_, Webhook>(&format!(
+            r#"
+            SELECT * FROM {} WHERE project_id = ? ORDER BY id
+            "#,
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .fetch_all(self.get_pool())
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 在指定连接（或事务）中查询项目已启用的 Webhook
+    pub async fn find_active_by_project_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+    ) -> Result<Vec<Webhook>, AppError> {
+        sqlx::query_as::<_, Webhook>(&format!(
+            r#"
+            SELECT * FROM {} WHERE project_id = ? AND is_active = TRUE
+            "#,
+            self.get_table_name()
+        ))
+        .bind(project_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// 物理删除回收站中超过保留期的项目的 Webhook，需先删除投递记录
+    pub async fn purge_by_deleted_projects_with(
+        &self,
+        conn: &mut MySqlConnection,
+        before: NaiveDateTime,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE project_id IN (SELECT id FROM i18n_projects WHERE deleted_at < ?)",
+            self.get_table_name()
+        ))
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl BaseRepository<Webhook> for WebhookRepository {
+    fn get_table_name(&self) -> &str {
+        "i18n_webhooks"
+    }
+
+    fn get_pool(&self) -> &MySqlPool {
+        &self.pool
+    }
+}
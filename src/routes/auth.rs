@@ -9,8 +9,11 @@ use crate::constants::{
     USER_EMAIL_REGEX, USER_NAME_MAX_LENGTH, USER_NAME_MIN_LENGTH, USER_PASSWORD_MAX_LENGTH,
     USER_PASSWORD_MIN_LENGTH, USER_PHONE_REGEX,
 };
+use crate::dtos::mfa::{MfaChallengeDto, MfaVerifyDto};
 use crate::dtos::user::{CreateUserDto, RegisterRequest};
 use crate::errors::AppError;
+use crate::services::mfa_service::MfaService;
+use crate::services::oidc_service::OidcService;
 use crate::services::user_service::UserService;
 use crate::utils::{self, jwt, R};
 
@@ -29,11 +32,22 @@ pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OidcCallbackRequest {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct LoginResponse {
     pub access_token: String,
     pub expires_in: i64,
     pub refresh_token: String,
+    // 首次完成两步验证绑定时返回，仅展示一次
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
 }
 
 impl From<jwt::TokenPair> for LoginResponse {
@@ -42,6 +56,7 @@ impl From<jwt::TokenPair> for LoginResponse {
             access_token: token_pair.access_token,
             expires_in: token_pair.expires_in,
             refresh_token: token_pair.refresh_token,
+            recovery_codes: None,
         }
     }
 }
@@ -52,7 +67,11 @@ pub fn auth_routes(cfg: &mut web::ServiceConfig) {
             .service(login)
             .service(register)
             .service(refresh_token)
-            .service(generate_captcha),
+            .service(generate_captcha)
+            .service(oidc_authorize)
+            .service(oidc_callback)
+            .service(mfa_setup)
+            .service(mfa_verify),
     );
 }
 
@@ -60,14 +79,20 @@ pub fn auth_routes(cfg: &mut web::ServiceConfig) {
 #[allow(unused_assignments)]
 pub async fn login(
     user_service: web::Data<UserService>,
+    mfa_service: web::Data<MfaService>,
     login_req: web::Json<LoginRequest>,
     http_req: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
+    let ip = http_req
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
     // 验证用户名、邮箱、手机号、验证码
     let mut user = None;
     if let Some(username) = &login_req.username {
         user = user_service
-            .verify_password(username, &login_req.password)
+            .verify_password(username, &login_req.password, &ip)
             .await?;
     } else if let Some(email) = &login_req.email {
         if let Some(code) = &login_req.verify_code {
@@ -105,6 +130,10 @@ pub async fn login(
         }
     }
     let inner_user = user.unwrap();
+    // 需要两步验证时先返回挑战，验证通过后再签发令牌
+    if let Some(challenge) = mfa_service.challenge(&inner_user).await? {
+        return Ok(R::ok(challenge));
+    }
     let token_pair = jwt::generate_token_pair(
         inner_user.id.clone(),
         inner_user.username
@@ -245,3 +274,68 @@ pub async fn generate_captcha() -> Result<HttpResponse, AppError> {
         enabled: SETTINGS.server.captcha_enabled,
     },cookie))
 }
+
+#[get("/oidc/authorize")]
+pub async fn oidc_authorize(
+    oidc_service: web::Data<OidcService>,
+) -> Result<HttpResponse, AppError> {
+    let authorize = oidc_service.authorize().await?;
+    Ok(R::ok(authorize))
+}
+
+#[get("/oidc/callback")]
+pub async fn oidc_callback(
+    oidc_service: web::Data<OidcService>,
+    mfa_service: web::Data<MfaService>,
+    callback: web::Query<OidcCallbackRequest>,
+    http_req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    if let Some(error) = &callback.error {
+        let message = callback.error_description.as_deref().unwrap_or(error);
+        return Err(AppError::Auth(format!("Single sign-on failed: {}", message)));
+    }
+    let (code, state) = match (&callback.code, &callback.state) {
+        (Some(code), Some(state)) => (code, state),
+        _ => return Ok(R::<String>::failure("Code and state are required".into())),
+    };
+    let ip = http_req
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let user = oidc_service.callback(code, state, &ip).await?;
+    if let Some(challenge) = mfa_service.challenge(&user).await? {
+        return Ok(R::ok(challenge));
+    }
+    let token_pair = jwt::generate_token_pair(user.id, user.username)?;
+    Ok(R::ok(LoginResponse::from(token_pair)))
+}
+
+/// 登录过程中绑定两步验证（角色要求但尚未绑定时）
+#[post("/mfa/setup")]
+pub async fn mfa_setup(
+    mfa_service: web::Data<MfaService>,
+    req: web::Json<MfaChallengeDto>,
+) -> Result<HttpResponse, AppError> {
+    let setup = mfa_service.setup_for_challenge(&req.challenge_token).await?;
+    Ok(R::ok(setup))
+}
+
+/// 登录第二步：校验验证码或恢复码后签发令牌
+#[post("/mfa/verify")]
+pub async fn mfa_verify(
+    mfa_service: web::Data<MfaService>,
+    req: web::Json<MfaVerifyDto>,
+    http_req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let ip = http_req
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let (user, recovery_codes) = mfa_service.verify(&req, &ip).await?;
+    let token_pair = jwt::generate_token_pair(user.id, user.username)?;
+    let mut response = LoginResponse::from(token_pair);
+    response.recovery_codes = recovery_codes;
+    Ok(R::ok(response))
+}
@@ -0,0 +1,73 @@
+use actix_web::{
+    get,
+    http::header::{self, HeaderValue},
+    web, HttpRequest, HttpResponse,
+};
+
+use crate::{
+    constants::{COLLAB_MESSAGE_MAX_BYTES, COLLAB_TOKEN_PROTOCOL},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::{
+        collab_service::{CollabHub, Participant},
+        project_service::ProjectService,
+        BaseService,
+    },
+    utils::{api_token, jwt},
+};
+
+pub fn collab_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(connect),
+    );
+}
+
+/// 项目实时协作通道（WebSocket），浏览器无法设置请求头时可通过 access_token 子协议携带令牌认证
+#[get("/{project_id}")]
+async fn connect(
+    collab_hub: web::Data<CollabHub>,
+    project_service: web::Data<ProjectService>,
+    project_id: web::Path<u64>,
+    http_request: HttpRequest,
+    body: web::Payload,
+) -> Result<HttpResponse, AppError> {
+    let project_id = project_id.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    BaseService::select_by_id(project_service.get_ref(), project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Project not found".into()))?;
+    let claims = jwt::get_claims(&http_request)?;
+
+    let (mut response, session, stream) = actix_ws::handle(&http_request, body)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+    // 客户端以子协议携带令牌时必须回应所选子协议，否则浏览器会关闭连接
+    if offers_token_protocol(&http_request) {
+        response.headers_mut().insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(COLLAB_TOKEN_PROTOCOL),
+        );
+    }
+    let can_write = api_token::get_scope(&http_request).is_none_or(|scope| scope.allows_write());
+    let participant = Participant::new(
+        project_id,
+        claims.sub,
+        claims.username.unwrap_or_default(),
+        can_write,
+    );
+    actix_web::rt::spawn(collab_hub.into_inner().run_session(
+        participant,
+        session,
+        stream.max_frame_size(COLLAB_MESSAGE_MAX_BYTES),
+    ));
+    Ok(response)
+}
+
+fn offers_token_protocol(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .is_some_and(|protocol| protocol.trim() == COLLAB_TOKEN_PROTOCOL)
+}
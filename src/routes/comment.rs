@@ -0,0 +1,136 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::comment::{CommentQuery, CreateCommentDto, IssueQuery, UpdateCommentDto},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::comment_service::{CommentOperator, CommentService},
+    utils::{api_token, jwt, PageR, R},
+};
+
+pub fn comment_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(create_comment)
+            .service(get_comments)
+            .service(get_issues)
+            .service(update_comment)
+            .service(delete_comment)
+            .service(resolve_comment)
+            .service(reopen_comment),
+    );
+}
+
+/// 发表评论或回复讨论串，内容中的 @用户名 会被记为提及
+#[post("")]
+async fn create_comment(
+    comment_service: web::Data<CommentService>,
+    dto: web::Json<CreateCommentDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let operator = operator(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let comment = comment_service.insert(&dto, scope.as_ref(), &operator).await?;
+    Ok(R::ok(comment))
+}
+
+/// 词条或翻译的讨论串
+#[get("/list")]
+async fn get_comments(
+    comment_service: web::Data<CommentService>,
+    query: web::Query<CommentQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let threads = comment_service.select_threads(&query, scope.as_ref()).await?;
+    Ok(R::ok(threads))
+}
+
+/// 项目中未解决的讨论串
+#[get("/issues")]
+async fn get_issues(
+    comment_service: web::Data<CommentService>,
+    query: web::Query<IssueQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let issues = comment_service.select_issues(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(issues))
+}
+
+#[put("/{id}")]
+async fn update_comment(
+    comment_service: web::Data<CommentService>,
+    id: web::Path<u64>,
+    dto: web::Json<UpdateCommentDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let operator = operator(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let comment = comment_service
+        .update_by_id(*id, &dto, scope.as_ref(), &operator)
+        .await?;
+    Ok(R::ok(comment))
+}
+
+#[delete("/{id}")]
+async fn delete_comment(
+    comment_service: web::Data<CommentService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let operator = operator(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let deleted = comment_service.delete(*id, scope.as_ref(), &operator).await?;
+    Ok(R::ok(deleted))
+}
+
+/// 标记讨论串已解决
+#[post("/{id}/resolve")]
+async fn resolve_comment(
+    comment_service: web::Data<CommentService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    set_resolved(&comment_service, *id, true, &http_request).await
+}
+
+/// 重新打开讨论串
+#[post("/{id}/reopen")]
+async fn reopen_comment(
+    comment_service: web::Data<CommentService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    set_resolved(&comment_service, *id, false, &http_request).await
+}
+
+async fn set_resolved(
+    comment_service: &CommentService,
+    id: u64,
+    resolved: bool,
+    http_request: &HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let operator = operator(http_request)?;
+    let scope = api_token::get_scope(http_request);
+    let comment = comment_service
+        .set_resolved(id, resolved, scope.as_ref(), &operator)
+        .await?;
+    Ok(R::ok(comment))
+}
+
+/// 当前用户和客户端 IP，用于权限判断和操作日志
+fn operator(http_request: &HttpRequest) -> Result<CommentOperator, AppError> {
+    let claims = jwt::get_claims(http_request)?;
+    let ip = http_request
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    Ok(CommentOperator {
+        user_id: claims.sub,
+        username: claims.username.unwrap_or_default(),
+        ip,
+    })
+}
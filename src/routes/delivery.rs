@@ -0,0 +1,34 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::delivery::DeliveryQuery,
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::delivery_service::DeliveryService,
+    utils::{api_token, module_tree::PathFilter, R},
+};
+
+pub fn delivery_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(get_bundle),
+    );
+}
+
+/// 下发项目某个语言的语言包，缺失的词条按回退链解析，可按模块路径只导出部分词条
+#[get("/{project_id}/{language}")]
+async fn get_bundle(
+    delivery_service: web::Data<DeliveryService>,
+    path: web::Path<(u64, String)>,
+    query: web::Query<DeliveryQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let (project_id, language) = path.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    let module = query.module.as_deref().and_then(PathFilter::parse);
+    let bundle = delivery_service
+        .resolve(project_id, &language, module.as_ref())
+        .await?;
+    Ok(R::ok(bundle))
+}
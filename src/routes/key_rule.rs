@@ -0,0 +1,78 @@
+use actix_web::{delete, get, put, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::key_rule::{KeyLintQuery, UpdateKeyRuleDto},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::key_rule_service::KeyRuleService,
+    utils::{api_token, jwt, PageR, R},
+};
+
+pub fn key_rule_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(get_lint)
+            .service(get_rule)
+            .service(update_rule)
+            .service(delete_rule),
+    );
+}
+
+/// 项目中不符合标识符规则的词条及建议的新标识符
+#[get("/{project_id}/lint")]
+async fn get_lint(
+    key_rule_service: web::Data<KeyRuleService>,
+    project_id: web::Path<u64>,
+    query: web::Query<KeyLintQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let project_id = project_id.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    let items = key_rule_service.lint(project_id, &query).await?;
+    Ok(PageR::ok(items))
+}
+
+#[get("/{project_id}")]
+async fn get_rule(
+    key_rule_service: web::Data<KeyRuleService>,
+    project_id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let project_id = project_id.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    let rule = key_rule_service.select_rule(project_id).await?;
+    Ok(R::ok(rule))
+}
+
+/// 设置项目的标识符规则
+#[put("/{project_id}")]
+async fn update_rule(
+    key_rule_service: web::Data<KeyRuleService>,
+    project_id: web::Path<u64>,
+    dto: web::Json<UpdateKeyRuleDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let project_id = project_id.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let rule = key_rule_service
+        .update_rule(project_id, &dto, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(rule))
+}
+
+#[delete("/{project_id}")]
+async fn delete_rule(
+    key_rule_service: web::Data<KeyRuleService>,
+    project_id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let project_id = project_id.into_inner();
+    api_token::check_project(&http_request, project_id)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let deleted = key_rule_service
+        .delete_rule(project_id, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(deleted))
+}
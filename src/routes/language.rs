@@ -6,8 +6,8 @@ use crate::{
     services::{language_service::LanguageService, BaseService},
     AppState,
 };
-use actix_web::{delete, get, post, put, web, HttpResponse};
-use crate::utils::{PageR, R};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use crate::utils::{api_token, PageR, R};
 
 pub fn language_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -43,7 +43,9 @@ async fn get_language(
 async fn create_language(
     language_service: web::Data<LanguageService>,
     language: web::Json<CreateLanguageDto>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_global(&http_request)?;
     let language = language_service.insert(&language.into_inner()).await?;
     Ok(R::ok(language))
 }
@@ -53,7 +55,9 @@ async fn update_language(
     language_service: web::Data<LanguageService>,
     id: web::Path<u64>,
     language: web::Json<UpdateLanguageDto>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_global(&http_request)?;
     let language = language_service
         .update_by_id(id.into_inner(), &language.into_inner())
         .await?;
@@ -64,7 +68,9 @@ async fn update_language(
 async fn delete_language(
     language_service: web::Data<LanguageService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_global(&http_request)?;
     let result = language_service.delete_by_id(id.into_inner()).await?;
     Ok(R::ok(result))
 }
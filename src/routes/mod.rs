@@ -1,20 +1,40 @@
 mod auth;
+mod collab;
+mod comment;
+mod delivery;
+mod key_rule;
 mod language;
 mod module;
+mod notification;
 mod phrase;
 mod phrase_type;
 mod project;
+mod report;
 mod screenshot;
+mod search;
+mod task;
 mod term;
 mod translation;
 mod user;
+mod webhook;
+mod well_known;
 
 pub use auth::auth_routes;
+pub use collab::collab_routes;
+pub use comment::comment_routes;
+pub use delivery::delivery_routes;
+pub use key_rule::key_rule_routes;
 pub use language::language_routes;
 pub use module::module_routes;
+pub use notification::notification_routes;
 pub use phrase::phrase_routes;
 pub use project::project_routes;
+pub use report::report_routes;
 pub use screenshot::screenshot_routes;
+pub use search::search_routes;
+pub use task::task_routes;
 pub use term::term_routes;
 pub use translation::translation_routes;
 pub use user::user_routes;
+pub use webhook::webhook_routes;
+pub use well_known::well_known_routes;
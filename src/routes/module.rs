@@ -1,10 +1,11 @@
 use crate::{
-    dtos::module::{CreateModuleDto, ModuleQuery, UpdateModuleDto},
+    dtos::module::{CreateModuleDto, ModuleQuery, ModuleTreeQuery, MoveModuleDto, UpdateModuleDto},
     errors::AppError,
     middleware::auth::Authentication,
     services::module_service::ModuleService,
+    utils::{api_token, jwt, R},
 };
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Responder};
 
 pub fn module_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -12,9 +13,13 @@ pub fn module_routes(cfg: &mut web::ServiceConfig) {
             .wrap(Authentication::default())
             .service(create_module)
             .service(get_modules)
+            .service(get_trash_modules)
+            .service(get_module_tree)
             .service(get_module)
             .service(update_module)
-            .service(delete_module),
+            .service(move_module)
+            .service(delete_module)
+            .service(restore_module),
     );
 }
 
@@ -22,7 +27,9 @@ pub fn module_routes(cfg: &mut web::ServiceConfig) {
 async fn create_module(
     module_service: web::Data<ModuleService>,
     module: web::Json<CreateModuleDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_project(&http_request, module.project_id)?;
     let module = module_service.insert(&module.into_inner()).await?;
     Ok(HttpResponse::Created().json(module))
 }
@@ -31,16 +38,36 @@ async fn create_module(
 async fn get_modules(
     module_service: web::Data<ModuleService>,
     query: web::Query<ModuleQuery>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let modules = module_service.select_by_page(query.page, query.size).await?;
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let modules = module_service.select_by_page(&query, scope.as_ref()).await?;
     Ok(HttpResponse::Ok().json(modules))
 }
 
+/// 项目的模块树，带各节点（含子模块）的词条数和翻译进度
+#[get("/tree")]
+async fn get_module_tree(
+    module_service: web::Data<ModuleService>,
+    query: web::Query<ModuleTreeQuery>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    api_token::check_project(&http_request, query.project_id)?;
+    let tree = module_service.select_tree(query.project_id).await?;
+    Ok(R::ok(tree))
+}
+
 #[get("/{id}")]
 async fn get_module(
     module_service: web::Data<ModuleService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    module_service.check_access(*id, scope.as_ref()).await?;
     let module = module_service.select_by_id(id.into_inner()).await?;
     Ok(HttpResponse::Ok().json(module))
 }
@@ -50,17 +77,74 @@ async fn update_module(
     module_service: web::Data<ModuleService>,
     id: web::Path<u64>,
     module: web::Json<UpdateModuleDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-
-    let module = module_service.update_by_id(id.into_inner(), &module.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    module_service.check_access(*id, scope.as_ref()).await?;
+    let claims = jwt::get_claims(&http_request)?;
+    let module = module_service
+        .update_by_id(
+            id.into_inner(),
+            &module.into_inner(),
+            &claims.username.unwrap_or_default(),
+        )
+        .await?;
     Ok(HttpResponse::Ok().json(module))
 }
 
+/// 移动模块及其子模块
+#[post("/{id}/move")]
+async fn move_module(
+    module_service: web::Data<ModuleService>,
+    id: web::Path<u64>,
+    dto: web::Json<MoveModuleDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    module_service.check_access(*id, scope.as_ref()).await?;
+    let claims = jwt::get_claims(&http_request)?;
+    let moved = module_service
+        .move_module(id.into_inner(), &dto, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(HttpResponse::Ok().json(moved))
+}
+
 #[delete("/{id}")]
 async fn delete_module(
     module_service: web::Data<ModuleService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    module_service.delete_by_id(id.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    module_service.check_access(*id, scope.as_ref()).await?;
+    module_service.soft_delete(id.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// 回收站中的模块
+#[get("/trash")]
+async fn get_trash_modules(
+    module_service: web::Data<ModuleService>,
+    query: web::Query<ModuleQuery>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let modules = module_service.select_trash_page(&query, scope.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(modules))
+}
+
+/// 从回收站恢复模块
+#[post("/{id}/restore")]
+async fn restore_module(
+    module_service: web::Data<ModuleService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    module_service.check_access(*id, scope.as_ref()).await?;
+    let result = module_service.restore(id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
@@ -0,0 +1,92 @@
+use actix_web::{get, post, put, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::notification::{NotificationQuery, UnreadCountVo, UpdatePreferenceDto},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::notification_service::NotificationService,
+    utils::{jwt, PageR, R},
+};
+
+pub fn notification_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(get_notifications)
+            .service(get_unread_count)
+            .service(read_all)
+            .service(read_notification)
+            .service(get_preference)
+            .service(update_preference),
+    );
+}
+
+/// 当前用户的通知
+#[get("/list")]
+async fn get_notifications(
+    notification_service: web::Data<NotificationService>,
+    query: web::Query<NotificationQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let notifications = notification_service.select_page(claims.sub, &query).await?;
+    Ok(PageR::ok(notifications))
+}
+
+#[get("/unread-count")]
+async fn get_unread_count(
+    notification_service: web::Data<NotificationService>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let unread = notification_service.unread_count(claims.sub).await?;
+    Ok(R::ok(UnreadCountVo { unread }))
+}
+
+#[post("/read-all")]
+async fn read_all(
+    notification_service: web::Data<NotificationService>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let count = notification_service.mark_all_read(claims.sub).await?;
+    Ok(R::ok(count))
+}
+
+#[post("/{id}/read")]
+async fn read_notification(
+    notification_service: web::Data<NotificationService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let result = notification_service
+        .mark_read(claims.sub, id.into_inner())
+        .await?;
+    Ok(R::ok(result))
+}
+
+/// 当前用户的通知偏好
+#[get("/preferences")]
+async fn get_preference(
+    notification_service: web::Data<NotificationService>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let preference = notification_service.select_preference(claims.sub).await?;
+    Ok(R::ok(preference))
+}
+
+/// 修改通知偏好：关闭的通知类型不再产生通知，也可关闭每日邮件摘要
+#[put("/preferences")]
+async fn update_preference(
+    notification_service: web::Data<NotificationService>,
+    dto: web::Json<UpdatePreferenceDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let preference = notification_service
+        .update_preference(claims.sub, &dto)
+        .await?;
+    Ok(R::ok(preference))
+}
@@ -1,20 +1,33 @@
 use crate::{
-    dtos::phrase::{CreatePhraseDto, UpdatePhraseDto},
+    dtos::{
+        key_rule::BatchRenamePhraseDto,
+        phrase::{
+            BatchCreatePhraseDto, BatchDeletePhraseDto, BatchUpdatePhraseDto, CreatePhraseDto,
+            PhraseQuery, PhraseTrashQuery, UpdatePhraseDto,
+        },
+    },
     errors::AppError,
     middleware::auth::Authentication,
     services::{phrase_service::PhraseService, BaseService},
 };
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use crate::utils::{api_token, etag, jwt, PageR};
+use actix_web::{delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder};
 
 pub fn phrase_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("")
             .wrap(Authentication::default())
             .service(create_phrase)
+            .service(batch_create_phrases)
+            .service(batch_update_phrases)
+            .service(batch_rename_phrases)
+            .service(batch_delete_phrases)
             .service(get_phrases)
+            .service(get_trash_phrases)
             .service(get_phrase)
             .service(update_phrase)
-            .service(delete_phrase),
+            .service(delete_phrase)
+            .service(restore_phrase),
     );
 }
 
@@ -22,26 +35,101 @@ pub fn phrase_routes(cfg: &mut web::ServiceConfig) {
 async fn create_phrase(
     phrase_service: web::Data<PhraseService>,
     phrase: web::Json<CreatePhraseDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_project(&http_request, phrase.project_id)?;
     let phrase = phrase_service.insert(&phrase.into_inner()).await?;
     Ok(HttpResponse::Created().json(phrase))
 }
 
-#[get("")]
+/// 批量创建词条
+#[post("/batch/create")]
+async fn batch_create_phrases(
+    phrase_service: web::Data<PhraseService>,
+    dto: web::Json<BatchCreatePhraseDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let result = phrase_service.batch_insert(&dto, scope.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// 批量修改词条（移动模块、增删标签、修改类型和平台等）
+#[post("/batch/update")]
+async fn batch_update_phrases(
+    phrase_service: web::Data<PhraseService>,
+    dto: web::Json<BatchUpdatePhraseDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let claims = jwt::get_claims(&http_request)?;
+    let result = phrase_service
+        .batch_update(
+            &dto,
+            scope.as_ref(),
+            claims.sub,
+            &claims.username.unwrap_or_default(),
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// 批量重命名词条，修正不符合标识符规则的词条
+#[post("/batch/rename")]
+async fn batch_rename_phrases(
+    phrase_service: web::Data<PhraseService>,
+    dto: web::Json<BatchRenamePhraseDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let claims = jwt::get_claims(&http_request)?;
+    let result = phrase_service
+        .batch_rename(&dto, scope.as_ref(), &claims.username.unwrap_or_default())
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// 批量删除词条
+#[post("/batch/delete")]
+async fn batch_delete_phrases(
+    phrase_service: web::Data<PhraseService>,
+    dto: web::Json<BatchDeletePhraseDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let result = phrase_service.batch_delete(&dto, scope.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// 词条列表，支持按模块、类型、标签、平台、翻译状态等筛选
+#[get("/list")]
 async fn get_phrases(
     phrase_service: web::Data<PhraseService>,
+    query: web::Query<PhraseQuery>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let phrases = phrase_service.select_all().await?;
-    Ok(HttpResponse::Ok().json(phrases))
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let phrases = phrase_service.select_by_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(phrases))
 }
 
 #[get("/{id}")]
 async fn get_phrase(
     phrase_service: web::Data<PhraseService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    phrase_service.check_access(*id, scope.as_ref()).await?;
     let phrase = phrase_service.select_by_id(id.into_inner()).await?;
-    Ok(HttpResponse::Ok().json(phrase))
+    let mut response = HttpResponse::Ok();
+    if let Some(phrase) = &phrase {
+        response.insert_header((header::ETAG, etag::from_version(phrase.version)));
+    }
+    Ok(response.json(phrase))
 }
 
 #[put("/{id}")]
@@ -49,8 +137,23 @@ async fn update_phrase(
     phrase_service: web::Data<PhraseService>,
     id: web::Path<u64>,
     phrase: web::Json<UpdatePhraseDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let phrase = phrase_service.update_by_id(id.into_inner(), &phrase.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    phrase_service.check_access(*id, scope.as_ref()).await?;
+    let mut phrase = phrase.into_inner();
+    if let Some(version) = etag::if_match(&http_request)? {
+        phrase.version = Some(version);
+    }
+    let claims = jwt::get_claims(&http_request)?;
+    let phrase = phrase_service
+        .update_by_id(
+            id.into_inner(),
+            &phrase,
+            claims.sub,
+            &claims.username.unwrap_or_default(),
+        )
+        .await?;
     Ok(HttpResponse::Ok().json(phrase))
 }
 
@@ -58,7 +161,38 @@ async fn update_phrase(
 async fn delete_phrase(
     phrase_service: web::Data<PhraseService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    phrase_service.delete_by_id(id.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    phrase_service.check_access(*id, scope.as_ref()).await?;
+    phrase_service.soft_delete(id.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// 回收站中的词条
+#[get("/trash")]
+async fn get_trash_phrases(
+    phrase_service: web::Data<PhraseService>,
+    query: web::Query<PhraseTrashQuery>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let phrases = phrase_service.select_trash_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(phrases))
+}
+
+/// 从回收站恢复词条
+#[post("/{id}/restore")]
+async fn restore_phrase(
+    phrase_service: web::Data<PhraseService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    phrase_service.check_access(*id, scope.as_ref()).await?;
+    let result = phrase_service.restore(id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
@@ -2,12 +2,13 @@ use crate::{
     dtos::project::{CreateProjectDto, UpdateProjectDto},
     errors::AppError,
     middleware::auth::Authentication,
-    services::{project_service::ProjectService, BaseService},
+    services::{project_service::ProjectService, stats_service::StatsService},
 };
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
 use crate::dtos::common::PageRequest;
-use crate::dtos::project::ProjectVo;
-use crate::utils::{jwt, PageR, R};
+use crate::dtos::stats::StatsQuery;
+use crate::dtos::project::{AddProjectLanguageDto, ProjectVo, UpdateLanguageFallbacksDto};
+use crate::utils::{api_token, jwt, PageR, R};
 
 pub fn project_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -15,18 +16,28 @@ pub fn project_routes(cfg: &mut web::ServiceConfig) {
             .wrap(Authentication::default())
             .service(create_project)
             .service(get_projects)
+            .service(get_trash_projects)
             .service(get_project)
             .service(update_project)
-            .service(delete_project),
+            .service(delete_project)
+            .service(restore_project)
+            .service(get_project_languages)
+            .service(add_project_language)
+            .service(remove_project_language)
+            .service(set_default_project_language)
+            .service(update_language_fallbacks)
+            .service(get_project_stats),
     );
 }
 
 #[get("/list")]
 async fn get_projects(
     project_service: web::Data<ProjectService>,
-    req: web::Query<PageRequest>
+    req: web::Query<PageRequest>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
-    let projects = project_service.select_by_page(&req).await?;
+    let scope = api_token::get_scope(&http_request);
+    let projects = project_service.select_by_page(&req, scope.as_ref()).await?;
     Ok(PageR::ok(projects))
 }
 
@@ -34,7 +45,9 @@ async fn get_projects(
 async fn get_project(
     project_service: web::Data<ProjectService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
     let project = project_service.select_by_id(id.into_inner()).await?;
     Ok(R::ok(project))
 }
@@ -45,6 +58,7 @@ async fn create_project(
     mut project: web::Json<CreateProjectDto>,
     http_request: HttpRequest
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_global(&http_request)?;
     let claims = jwt::get_claims(&http_request)?;
     project.crt_by = claims.username;
     let project = project_service.insert(&project.into_inner()).await?;
@@ -58,6 +72,7 @@ async fn update_project(
     mut project: web::Json<UpdateProjectDto>,
     http_request: HttpRequest
 ) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
     let claims = jwt::get_claims(&http_request)?;
     project.upt_by = claims.username;
     let project = project_service.update_by_id(id.into_inner(), &project.into_inner()).await?;
@@ -68,7 +83,111 @@ async fn update_project(
 async fn delete_project(
     project_service: web::Data<ProjectService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<HttpResponse, AppError> {
-    let result = project_service.delete_by_id(id.into_inner()).await?;
+    api_token::check_project(&http_request, *id)?;
+    let result = project_service.soft_delete(id.into_inner()).await?;
     Ok(R::ok(result))
 }
+
+/// 回收站中的项目
+#[get("/trash")]
+async fn get_trash_projects(
+    project_service: web::Data<ProjectService>,
+    req: web::Query<PageRequest>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let projects = project_service.select_trash_page(&req, scope.as_ref()).await?;
+    Ok(PageR::ok(projects))
+}
+
+/// 从回收站恢复项目
+#[post("/{id}/restore")]
+async fn restore_project(
+    project_service: web::Data<ProjectService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
+    let result = project_service.restore(id.into_inner()).await?;
+    Ok(R::ok(result))
+}
+
+/// 项目的目标语言
+#[get("/{id}/languages")]
+async fn get_project_languages(
+    project_service: web::Data<ProjectService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
+    let languages = project_service.select_languages(id.into_inner()).await?;
+    Ok(R::ok(languages))
+}
+
+/// 添加目标语言
+#[post("/{id}/languages")]
+async fn add_project_language(
+    project_service: web::Data<ProjectService>,
+    id: web::Path<u64>,
+    dto: web::Json<AddProjectLanguageDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
+    let result = project_service.add_language(id.into_inner(), &dto).await?;
+    Ok(R::ok(result))
+}
+
+/// 移除目标语言
+#[delete("/{id}/languages/{language}")]
+async fn remove_project_language(
+    project_service: web::Data<ProjectService>,
+    path: web::Path<(u64, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let (id, language) = path.into_inner();
+    api_token::check_project(&http_request, id)?;
+    let result = project_service.remove_language(id, &language).await?;
+    Ok(R::ok(result))
+}
+
+/// 设置默认目标语言
+#[put("/{id}/languages/{language}/default")]
+async fn set_default_project_language(
+    project_service: web::Data<ProjectService>,
+    path: web::Path<(u64, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let (id, language) = path.into_inner();
+    api_token::check_project(&http_request, id)?;
+    let result = project_service.set_default_language(id, &language).await?;
+    Ok(R::ok(result))
+}
+
+/// 设置目标语言的回退链
+#[put("/{id}/languages/{language}/fallbacks")]
+async fn update_language_fallbacks(
+    project_service: web::Data<ProjectService>,
+    path: web::Path<(u64, String)>,
+    dto: web::Json<UpdateLanguageFallbacksDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let (id, language) = path.into_inner();
+    api_token::check_project(&http_request, id)?;
+    let result = project_service.update_fallbacks(id, &language, &dto).await?;
+    Ok(R::ok(result))
+}
+
+/// 项目各目标语言的翻译进度统计
+#[get("/{id}/stats")]
+async fn get_project_stats(
+    stats_service: web::Data<StatsService>,
+    id: web::Path<u64>,
+    query: web::Query<StatsQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, *id)?;
+    let stats = stats_service.project_stats(id.into_inner(), query.days).await?;
+    Ok(R::ok(stats))
+}
@@ -0,0 +1,69 @@
+use actix_web::{get, http::header, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::report::{ReportQuery, TranslatorReportVo},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::report_service::ReportService,
+    utils::{api_token, csv, jwt, R},
+};
+
+pub fn report_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(get_translator_report)
+            .service(export_translator_report),
+    );
+}
+
+/// 译员工作量与质量报表
+#[get("/translators")]
+async fn get_translator_report(
+    report_service: web::Data<ReportService>,
+    query: web::Query<ReportQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    check_scope(&http_request, &query)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let reports = report_service
+        .translator_report(&query, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(reports))
+}
+
+/// 导出译员报表为 CSV
+#[get("/translators/export")]
+async fn export_translator_report(
+    report_service: web::Data<ReportService>,
+    query: web::Query<ReportQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    check_scope(&http_request, &query)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let reports = report_service
+        .translator_report(&query, &claims.username.unwrap_or_default())
+        .await?;
+    let rows: Vec<Vec<String>> = reports.iter().map(TranslatorReportVo::to_csv_row).collect();
+    let filename = format!("translators_{}_{}.csv", query.from, query.to);
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        ))
+        .body(csv::to_csv(&TranslatorReportVo::CSV_HEADERS, &rows)))
+}
+
+/// 限定项目的 API 令牌必须指定可访问的项目
+fn check_scope(http_request: &HttpRequest, query: &ReportQuery) -> Result<(), AppError> {
+    match query.project_id {
+        Some(project_id) => api_token::check_project(http_request, project_id),
+        None if api_token::get_scope(http_request).is_some_and(|s| s.project_ids.is_some()) => {
+            Err(AppError::Forbidden(
+                "project_id is required for project-scoped API tokens".into(),
+            ))
+        }
+        None => Ok(()),
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    dtos::screenshot::{CreateScreenshotDto, UpdateScreenshotDto},
+    dtos::screenshot::{CreateScreenshotDto, ScreenshotQuery, UpdateScreenshotDto},
     errors::AppError,
     middleware::auth::Authentication,
     services::{screenshot_service::ScreenshotService, BaseService},
+    utils::{api_token, PageR},
 };
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Responder};
 
 pub fn screenshot_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -22,7 +23,12 @@ pub fn screenshot_routes(cfg: &mut web::ServiceConfig) {
 async fn create_screenshot(
     screenshot_service: web::Data<ScreenshotService>,
     screenshot: web::Json<CreateScreenshotDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    screenshot_service
+        .check_phrase_access(screenshot.phrase_id, scope.as_ref())
+        .await?;
     let screenshot = screenshot_service.insert(&screenshot.into_inner()).await?;
     Ok(HttpResponse::Created().json(screenshot))
 }
@@ -30,16 +36,25 @@ async fn create_screenshot(
 #[get("")]
 async fn get_screenshots(
     screenshot_service: web::Data<ScreenshotService>,
+    query: web::Query<ScreenshotQuery>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let screenshots = screenshot_service.select_all().await?;
-    Ok(HttpResponse::Ok().json(screenshots))
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let screenshots = screenshot_service.select_by_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(screenshots))
 }
 
 #[get("/{id}")]
 async fn get_screenshot(
     screenshot_service: web::Data<ScreenshotService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    screenshot_service.check_access(*id, scope.as_ref()).await?;
     let screenshot = screenshot_service.select_by_id(id.into_inner()).await?;
     Ok(HttpResponse::Ok().json(screenshot))
 }
@@ -49,7 +64,10 @@ async fn update_screenshot(
     screenshot_service: web::Data<ScreenshotService>,
     id: web::Path<u64>,
     screenshot: web::Json<UpdateScreenshotDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    screenshot_service.check_access(*id, scope.as_ref()).await?;
     let screenshot = screenshot_service.update_by_id(id.into_inner(), &screenshot.into_inner()).await?;
     Ok(HttpResponse::Ok().json(screenshot))
 }
@@ -58,7 +76,10 @@ async fn update_screenshot(
 async fn delete_screenshot(
     screenshot_service: web::Data<ScreenshotService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    screenshot_service.check_access(*id, scope.as_ref()).await?;
     screenshot_service.delete_by_id(id.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
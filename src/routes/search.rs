@@ -0,0 +1,32 @@
+use actix_web::{get, web, HttpRequest, Responder};
+
+use crate::{
+    dtos::search::SearchQuery,
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::search_service::SearchService,
+    utils::{api_token, PageR},
+};
+
+pub fn search_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(search),
+    );
+}
+
+/// 全文检索词条、翻译和术语，可按项目、语言和来源限定
+#[get("")]
+async fn search(
+    search_service: web::Data<SearchService>,
+    query: web::Query<SearchQuery>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let hits = search_service.search(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(hits))
+}
@@ -0,0 +1,137 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::task::{
+        CreateTaskDto, MyTaskQuery, OverdueTaskQuery, TaskPhraseQuery, TaskQuery, UpdateTaskDto,
+    },
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::task_service::TaskService,
+    utils::{api_token, jwt, PageR, R},
+};
+
+pub fn task_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(create_task)
+            .service(get_tasks)
+            .service(get_my_tasks)
+            .service(get_overdue_tasks)
+            .service(get_task)
+            .service(get_task_phrases)
+            .service(update_task)
+            .service(delete_task),
+    );
+}
+
+/// 创建任务，将范围内的词条指派给译者和审核人
+#[post("")]
+async fn create_task(
+    task_service: web::Data<TaskService>,
+    dto: web::Json<CreateTaskDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let task = task_service
+        .insert(&dto, scope.as_ref(), claims.sub, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(task))
+}
+
+#[get("/list")]
+async fn get_tasks(
+    task_service: web::Data<TaskService>,
+    query: web::Query<TaskQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let tasks = task_service.select_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(tasks))
+}
+
+/// 指派给当前用户的任务
+#[get("/mine")]
+async fn get_my_tasks(
+    task_service: web::Data<TaskService>,
+    query: web::Query<MyTaskQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let tasks = task_service.select_mine(claims.sub, &query).await?;
+    Ok(PageR::ok(tasks))
+}
+
+/// 逾期未完成的任务
+#[get("/overdue")]
+async fn get_overdue_tasks(
+    task_service: web::Data<TaskService>,
+    query: web::Query<OverdueTaskQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let tasks = task_service.select_overdue(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(tasks))
+}
+
+#[get("/{id}")]
+async fn get_task(
+    task_service: web::Data<TaskService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let task = task_service.select_task(*id, scope.as_ref()).await?;
+    Ok(R::ok(task))
+}
+
+/// 任务中的词条及其翻译状态
+#[get("/{id}/phrases")]
+async fn get_task_phrases(
+    task_service: web::Data<TaskService>,
+    id: web::Path<u64>,
+    query: web::Query<TaskPhraseQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let phrases = task_service
+        .select_phrases(*id, &query, scope.as_ref())
+        .await?;
+    Ok(PageR::ok(phrases))
+}
+
+#[put("/{id}")]
+async fn update_task(
+    task_service: web::Data<TaskService>,
+    id: web::Path<u64>,
+    dto: web::Json<UpdateTaskDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let task = task_service
+        .update_by_id(
+            *id,
+            &dto,
+            scope.as_ref(),
+            claims.sub,
+            &claims.username.unwrap_or_default(),
+        )
+        .await?;
+    Ok(R::ok(task))
+}
+
+#[delete("/{id}")]
+async fn delete_task(
+    task_service: web::Data<TaskService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let scope = api_token::get_scope(&http_request);
+    let deleted = task_service
+        .delete(*id, scope.as_ref(), &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(deleted))
+}
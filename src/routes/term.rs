@@ -1,10 +1,11 @@
 use crate::{
-    dtos::term::{CreateTermDto, UpdateTermDto},
+    dtos::term::{CreateTermDto, TermQuery, UpdateTermDto},
     errors::AppError,
     middleware::auth::Authentication,
     services::{term_service::TermService, BaseService},
+    utils::{api_token, PageR},
 };
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Responder};
 
 pub fn term_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -22,7 +23,9 @@ pub fn term_routes(cfg: &mut web::ServiceConfig) {
 async fn create_term(
     term_service: web::Data<TermService>,
     term: web::Json<CreateTermDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_project(&http_request, term.project_id)?;
     let term = term_service.insert(&term.into_inner()).await?;
     Ok(HttpResponse::Created().json(term))
 }
@@ -30,16 +33,25 @@ async fn create_term(
 #[get("")]
 async fn get_terms(
     term_service: web::Data<TermService>,
+    query: web::Query<TermQuery>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let terms = term_service.select_all().await?;
-    Ok(HttpResponse::Ok().json(terms))
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let terms = term_service.select_by_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(terms))
 }
 
 #[get("/{id}")]
 async fn get_term(
     term_service: web::Data<TermService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    term_service.check_access(*id, scope.as_ref()).await?;
     let term = term_service.select_by_id(id.into_inner()).await?;
     Ok(HttpResponse::Ok().json(term))
 }
@@ -49,7 +61,10 @@ async fn update_term(
     term_service: web::Data<TermService>,
     id: web::Path<u64>,
     term: web::Json<UpdateTermDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    term_service.check_access(*id, scope.as_ref()).await?;
     let term = term_service.update_by_id(id.into_inner(), &term.into_inner()).await?;
     Ok(HttpResponse::Ok().json(term))
 }
@@ -58,7 +73,10 @@ async fn update_term(
 async fn delete_term(
     term_service: web::Data<TermService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    term_service.check_access(*id, scope.as_ref()).await?;
     term_service.delete_by_id(id.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
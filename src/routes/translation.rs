@@ -1,10 +1,11 @@
 use crate::{
-    dtos::translation::{CreateTranslationDto, UpdateTranslationDto},
+    dtos::translation::{CreateTranslationDto, TranslationQuery, UpdateTranslationDto},
     errors::AppError,
     middleware::auth::Authentication,
     services::{translation_service::TranslationService, BaseService},
+    utils::{api_token, etag, jwt, PageR},
 };
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder};
 
 pub fn translation_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -13,6 +14,7 @@ pub fn translation_routes(cfg: &mut web::ServiceConfig) {
             .service(create_translation)
             .service(get_translations)
             .service(get_translation)
+            .service(get_translation_source)
             .service(update_translation)
             .service(delete_translation),
     );
@@ -22,26 +24,61 @@ pub fn translation_routes(cfg: &mut web::ServiceConfig) {
 async fn create_translation(
     translation_service: web::Data<TranslationService>,
     translation: web::Json<CreateTranslationDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let translation = translation_service.insert(&translation.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    translation_service
+        .check_phrase_access(translation.phrase_id, scope.as_ref())
+        .await?;
+    let claims = jwt::get_claims(&http_request)?;
+    let translation = translation_service
+        .insert(&translation.into_inner(), claims.sub, &claims.username.unwrap_or_default())
+        .await?;
     Ok(HttpResponse::Created().json(translation))
 }
 
 #[get("")]
 async fn get_translations(
     translation_service: web::Data<TranslationService>,
+    query: web::Query<TranslationQuery>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let translations = translation_service.select_all().await?;
-    Ok(HttpResponse::Ok().json(translations))
+    if let Some(project_id) = query.project_id {
+        api_token::check_project(&http_request, project_id)?;
+    }
+    let scope = api_token::get_scope(&http_request);
+    let translations = translation_service.select_by_page(&query, scope.as_ref()).await?;
+    Ok(PageR::ok(translations))
 }
 
 #[get("/{id}")]
 async fn get_translation(
     translation_service: web::Data<TranslationService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    translation_service.check_access(*id, scope.as_ref()).await?;
     let translation = translation_service.select_by_id(id.into_inner()).await?;
-    Ok(HttpResponse::Ok().json(translation))
+    let mut response = HttpResponse::Ok();
+    if let Some(translation) = &translation {
+        response.insert_header((header::ETAG, etag::from_version(translation.version)));
+    }
+    Ok(response.json(translation))
+}
+
+/// 翻译依据的原文与当前原文，原文修改后用于对比
+#[get("/{id}/source")]
+async fn get_translation_source(
+    translation_service: web::Data<TranslationService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let source = translation_service
+        .select_source(id.into_inner(), scope.as_ref())
+        .await?;
+    Ok(HttpResponse::Ok().json(source))
 }
 
 #[put("/{id}")]
@@ -49,8 +86,23 @@ async fn update_translation(
     translation_service: web::Data<TranslationService>,
     id: web::Path<u64>,
     translation: web::Json<UpdateTranslationDto>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let translation = translation_service.update_by_id(id.into_inner(), &translation.into_inner()).await?;
+    let scope = api_token::get_scope(&http_request);
+    translation_service.check_access(*id, scope.as_ref()).await?;
+    let mut translation = translation.into_inner();
+    if let Some(version) = etag::if_match(&http_request)? {
+        translation.version = Some(version);
+    }
+    let claims = jwt::get_claims(&http_request)?;
+    let translation = translation_service
+        .update_by_id(
+            id.into_inner(),
+            &translation,
+            claims.sub,
+            &claims.username.unwrap_or_default(),
+        )
+        .await?;
     Ok(HttpResponse::Ok().json(translation))
 }
 
@@ -58,7 +110,10 @@ async fn update_translation(
 async fn delete_translation(
     translation_service: web::Data<TranslationService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    translation_service.check_access(*id, scope.as_ref()).await?;
     translation_service.delete_by_id(id.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
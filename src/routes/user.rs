@@ -1,5 +1,9 @@
+use crate::dtos::api_token::CreateApiTokenDto;
+use crate::dtos::mfa::{MfaCodeDto, RecoveryCodesVo};
 use crate::dtos::user::{PageUserRequest, UserIdsRequest};
-use crate::utils::PageR;
+use crate::services::api_token_service::ApiTokenService;
+use crate::services::mfa_service::MfaService;
+use crate::utils::{api_token, PageR};
 use crate::{
     dtos::user::{CreateUserDto, UpdateUserDto, UserVo},
     errors::AppError,
@@ -22,7 +26,16 @@ pub fn user_routes(cfg: &mut web::ServiceConfig) {
             .service(delete_user)
             .service(create_user)
             .service(get_current_user)
-            .service(batch_delete_users),
+            .service(batch_delete_users)
+            .service(unlock_user)
+            .service(create_api_token)
+            .service(get_api_tokens)
+            .service(revoke_api_token)
+            .service(get_mfa_status)
+            .service(setup_mfa)
+            .service(enable_mfa)
+            .service(disable_mfa)
+            .service(regenerate_recovery_codes),
     );
 }
 #[get("/profile")]
@@ -39,7 +52,9 @@ async fn get_current_user(
 async fn get_users(
     user_service: web::Data<UserService>,
     user: web::Query<PageUserRequest>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     let users = user_service.select_by_page(&user).await?;
     Ok(PageR::ok(users))
 }
@@ -48,7 +63,9 @@ async fn get_users(
 async fn get_user(
     user_service: web::Data<UserService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     let user = user_service.select_by_id(id.into_inner()).await?;
     Ok(R::ok(UserVo::from(&user)))
 }
@@ -59,6 +76,7 @@ async fn create_user(
     mut user: web::Json<CreateUserDto>,
     http_request: HttpRequest
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     let claims = jwt::get_claims(&http_request)?;
     user.crt_by = claims.username;
     let user = user_service.create_user(&user.into_inner()).await?;
@@ -72,6 +90,7 @@ async fn update_user(
     mut user: web::Json<UpdateUserDto>,
     http_request: HttpRequest
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     let claims = jwt::get_claims(&http_request)?;
     user.upt_by = claims.username;
     let user = user_service
@@ -84,7 +103,9 @@ async fn update_user(
 async fn delete_user(
     user_service: web::Data<UserService>,
     id: web::Path<u64>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     user_service.delete_by_id(id.into_inner()).await?;
     Ok(R::ok(()))
 }
@@ -93,8 +114,155 @@ async fn delete_user(
 async fn batch_delete_users(
     user_service: web::Data<UserService>,
     req: web::Path<String>,
+    http_request: HttpRequest,
 ) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
     let ids = req.into_inner().split(",").map(|s| s.parse::<u64>().unwrap()).collect::<Vec<u64>>();
     let affected = user_service.delete_by_ids(&ids).await?;
     Ok(R::ok(affected))
 }
+
+#[post("/{id}/unlock")]
+async fn unlock_user(
+    user_service: web::Data<UserService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    api_token::check_global(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let operator = claims.username.unwrap_or_default();
+    let ip = http_request
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let result = user_service
+        .unlock(id.into_inner(), claims.sub, &operator, &ip)
+        .await?;
+    Ok(R::ok(result))
+}
+
+/// API 令牌不能用于管理令牌，避免令牌自我复制
+fn ensure_not_api_token(req: &HttpRequest) -> Result<(), AppError> {
+    if api_token::get_scope(req).is_some() {
+        return Err(AppError::Forbidden(
+            "API tokens cannot be managed with an API token".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[post("/tokens")]
+async fn create_api_token(
+    api_token_service: web::Data<ApiTokenService>,
+    token: web::Json<CreateApiTokenDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let crt_by = claims.username.unwrap_or_else(|| claims.sub.to_string());
+    let token = api_token_service
+        .create(claims.sub, &crt_by, &token.into_inner())
+        .await?;
+    Ok(R::ok(token))
+}
+
+#[get("/tokens")]
+async fn get_api_tokens(
+    api_token_service: web::Data<ApiTokenService>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let tokens = api_token_service.list(claims.sub).await?;
+    Ok(R::ok(tokens))
+}
+
+#[delete("/tokens/{id}")]
+async fn revoke_api_token(
+    api_token_service: web::Data<ApiTokenService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let upt_by = claims.username.unwrap_or_else(|| claims.sub.to_string());
+    let result = api_token_service
+        .revoke(id.into_inner(), claims.sub, &upt_by)
+        .await?;
+    Ok(R::ok(result))
+}
+
+#[get("/mfa")]
+async fn get_mfa_status(
+    mfa_service: web::Data<MfaService>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let claims = jwt::get_claims(&http_request)?;
+    let status = mfa_service.status(claims.sub).await?;
+    Ok(R::ok(status))
+}
+
+#[post("/mfa/setup")]
+async fn setup_mfa(
+    mfa_service: web::Data<MfaService>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let setup = mfa_service.setup(claims.sub).await?;
+    Ok(R::ok(setup))
+}
+
+#[post("/mfa/enable")]
+async fn enable_mfa(
+    mfa_service: web::Data<MfaService>,
+    req: web::Json<MfaCodeDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let ip = http_request
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let recovery_codes = mfa_service.enable(claims.sub, &req.code, &ip).await?;
+    Ok(R::ok(RecoveryCodesVo { recovery_codes }))
+}
+
+#[post("/mfa/disable")]
+async fn disable_mfa(
+    mfa_service: web::Data<MfaService>,
+    req: web::Json<MfaCodeDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let ip = http_request
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let result = mfa_service.disable(claims.sub, &req.code, &ip).await?;
+    Ok(R::ok(result))
+}
+
+#[post("/mfa/recovery-codes")]
+async fn regenerate_recovery_codes(
+    mfa_service: web::Data<MfaService>,
+    req: web::Json<MfaCodeDto>,
+    http_request: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_not_api_token(&http_request)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let ip = http_request
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let recovery_codes = mfa_service
+        .regenerate_recovery_codes(claims.sub, &req.code, &ip)
+        .await?;
+    Ok(R::ok(RecoveryCodesVo { recovery_codes }))
+}
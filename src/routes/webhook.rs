@@ -0,0 +1,132 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+
+use crate::{
+    dtos::webhook::{CreateWebhookDto, DeliveryQuery, UpdateWebhookDto, WebhookQuery},
+    errors::AppError,
+    middleware::auth::Authentication,
+    services::webhook_service::WebhookService,
+    utils::{api_token, jwt, PageR, R},
+};
+
+pub fn webhook_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication::default())
+            .service(create_webhook)
+            .service(get_webhooks)
+            .service(redeliver)
+            .service(get_webhook)
+            .service(update_webhook)
+            .service(delete_webhook)
+            .service(rotate_secret)
+            .service(get_deliveries),
+    );
+}
+
+/// 创建 Webhook，签名密钥只在创建时返回
+#[post("")]
+async fn create_webhook(
+    webhook_service: web::Data<WebhookService>,
+    dto: web::Json<CreateWebhookDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, dto.project_id)?;
+    let claims = jwt::get_claims(&http_request)?;
+    let webhook = webhook_service
+        .insert(&dto, &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(webhook))
+}
+
+#[get("/list")]
+async fn get_webhooks(
+    webhook_service: web::Data<WebhookService>,
+    query: web::Query<WebhookQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    api_token::check_project(&http_request, query.project_id)?;
+    let webhooks = webhook_service.select_by_project(query.project_id).await?;
+    Ok(R::ok(webhooks))
+}
+
+#[get("/{id}")]
+async fn get_webhook(
+    webhook_service: web::Data<WebhookService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let webhook = webhook_service.select_webhook(*id, scope.as_ref()).await?;
+    Ok(R::ok(webhook))
+}
+
+#[put("/{id}")]
+async fn update_webhook(
+    webhook_service: web::Data<WebhookService>,
+    id: web::Path<u64>,
+    dto: web::Json<UpdateWebhookDto>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let claims = jwt::get_claims(&http_request)?;
+    let updated = webhook_service
+        .update_by_id(*id, &dto, scope.as_ref(), &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(updated))
+}
+
+#[delete("/{id}")]
+async fn delete_webhook(
+    webhook_service: web::Data<WebhookService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let claims = jwt::get_claims(&http_request)?;
+    let deleted = webhook_service
+        .delete_webhook(*id, scope.as_ref(), &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(deleted))
+}
+
+/// 重置签名密钥
+#[post("/{id}/secret")]
+async fn rotate_secret(
+    webhook_service: web::Data<WebhookService>,
+    id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let claims = jwt::get_claims(&http_request)?;
+    let webhook = webhook_service
+        .rotate_secret(*id, scope.as_ref(), &claims.username.unwrap_or_default())
+        .await?;
+    Ok(R::ok(webhook))
+}
+
+/// 投递记录
+#[get("/{id}/deliveries")]
+async fn get_deliveries(
+    webhook_service: web::Data<WebhookService>,
+    id: web::Path<u64>,
+    query: web::Query<DeliveryQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let deliveries = webhook_service
+        .select_deliveries(*id, &query, scope.as_ref())
+        .await?;
+    Ok(PageR::ok(deliveries))
+}
+
+/// 重新投递，返回新的投递记录id
+#[post("/deliveries/{delivery_id}/redeliver")]
+async fn redeliver(
+    webhook_service: web::Data<WebhookService>,
+    delivery_id: web::Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let scope = api_token::get_scope(&http_request);
+    let id = webhook_service.redeliver(*delivery_id, scope.as_ref()).await?;
+    Ok(R::ok(id))
+}
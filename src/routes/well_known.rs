@@ -0,0 +1,13 @@
+use actix_web::{get, web, HttpResponse, Responder};
+
+use crate::utils::jwt;
+
+pub fn well_known_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(jwks);
+}
+
+/// 令牌校验公钥，供其他服务本地校验平台签发的令牌
+#[get("/jwks.json")]
+async fn jwks() -> impl Responder {
+    HttpResponse::Ok().json(jwt::jwks())
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::{
+    dtos::api_token::{ApiTokenVo, CreateApiTokenDto, CreatedApiTokenVo},
+    errors::AppError,
+    models::{api_token::ApiToken, enums::TokenPermission},
+    repositories::{api_token_repo::ApiTokenRepository, BaseRepository, UserRepository},
+    services::BaseService,
+    utils::{
+        api_token::{self, ApiTokenScope},
+        jwt::Claims,
+        password,
+    },
+};
+
+pub struct ApiTokenService {
+    repo: Arc<ApiTokenRepository>,
+    user_repo: Arc<UserRepository>,
+}
+
+impl ApiTokenService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: Arc::new(ApiTokenRepository::new(pool.clone())),
+            user_repo: Arc::new(UserRepository::new(pool)),
+        }
+    }
+
+    /// 创建令牌，明文只在此处返回一次
+    pub async fn create(
+        &self,
+        user_id: u64,
+        crt_by: &str,
+        dto: &CreateApiTokenDto,
+    ) -> Result<CreatedApiTokenVo, AppError> {
+        if dto.name.trim().is_empty() {
+            return Err(AppError::BadRequest("Token name is required".into()));
+        }
+        if let Some(expires_at) = dto.expires_at {
+            if expires_at <= Utc::now().naive_utc() {
+                return Err(AppError::BadRequest("Expiry must be in the future".into()));
+            }
+        }
+
+        let (prefix, token) = api_token::generate();
+        let (_, secret) = api_token::parse(&token)
+            .ok_or_else(|| AppError::Internal("Failed to generate token".into()))?;
+
+        let mut entity = ApiToken {
+            id: 0,
+            user_id,
+            name: dto.name.trim().to_string(),
+            token_prefix: prefix,
+            token_hash: password::hash_password(secret)?,
+            project_ids: dto
+                .project_ids
+                .as_ref()
+                .map(|ids| serde_json::json!(ids)),
+            permission: dto.permission.unwrap_or(TokenPermission::Read),
+            expires_at: dto.expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            crt_by: crt_by.to_string(),
+            crt_at: Utc::now(),
+            upt_by: None,
+            upt_at: Utc::now(),
+        };
+        entity.id = self.repo.insert(&entity).await?;
+
+        Ok(CreatedApiTokenVo {
+            token,
+            info: ApiTokenVo::from(&entity),
+        })
+    }
+
+    /// 当前用户的令牌列表
+    pub async fn list(&self, user_id: u64) -> Result<Vec<ApiTokenVo>, AppError> {
+        let tokens = self.repo.find_by_user_id(user_id).await?;
+        Ok(tokens.iter().map(ApiTokenVo::from).collect())
+    }
+
+    /// 吊销令牌
+    pub async fn revoke(&self, id: u64, user_id: u64, upt_by: &str) -> Result<bool, AppError> {
+        if !self.repo.revoke(id, user_id, upt_by).await? {
+            return Err(AppError::NotFound("Token not found".into()));
+        }
+        Ok(true)
+    }
+
+    /// 校验请求头中的令牌，返回令牌所属用户的 Claims 和访问范围
+    pub async fn authenticate(&self, token: &str) -> Result<(Claims, ApiTokenScope), AppError> {
+        let invalid = || AppError::Unauthorized("Invalid API token".into());
+
+        let (prefix, secret) = api_token::parse(token).ok_or_else(invalid)?;
+        let entity = self.repo.find_by_prefix(prefix).await?.ok_or_else(invalid)?;
+        if entity.revoked_at.is_some() {
+            return Err(invalid());
+        }
+        if let Some(expires_at) = entity.expires_at {
+            if expires_at <= Utc::now().naive_utc() {
+                return Err(AppError::Unauthorized("API token expired".into()));
+            }
+        }
+        if !password::verify_password(secret, &entity.token_hash)? {
+            return Err(invalid());
+        }
+
+        let user = self
+            .user_repo
+            .select_by_id(entity.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+        if !user.status {
+            return Err(invalid());
+        }
+        self.repo.touch(entity.id).await?;
+
+        let scope = ApiTokenScope {
+            token_id: entity.id,
+            project_ids: entity.project_id_list(),
+            permission: entity.permission,
+        };
+        Ok((Claims::new(user.id, user.username, false), scope))
+    }
+}
+
+impl BaseService<ApiToken> for ApiTokenService {
+    type Repository = ApiTokenRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
@@ -1,5 +1,7 @@
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use serde::Serialize;
+use sqlx::MySqlConnection;
 
 use crate::{errors::AppError, repositories::BaseRepository};
 
@@ -62,4 +64,28 @@ where
     async fn delete_by_ids(&self, ids: &[u64]) -> Result<u64, AppError> {
         self.get_repository().delete_by_ids(ids).await
     }
+
+    /// 在同一事务中执行多个操作，闭包返回 Ok 时提交，返回 Err 时回滚
+    ///
+    /// 闭包的第二个参数只用于约束生命周期（允许闭包借用 self），调用时忽略即可：
+    /// `self.with_transaction(|conn, _| Box::pin(async move { ... })).await`
+    async fn with_transaction<'a, F, R>(&'a self, f: F) -> Result<R, AppError>
+    where
+        F: for<'c> FnOnce(&'c mut MySqlConnection, &'c &'a ()) -> BoxFuture<'c, Result<R, AppError>>
+            + Send
+            + 'a,
+        R: Send + 'a,
+    {
+        let mut tx = self.get_repository().begin().await?;
+        match f(&mut tx, &&()).await {
+            Ok(result) => {
+                tx.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
 }
@@ -0,0 +1,485 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use actix_ws::{Message, MessageStream, Session};
+use chrono::Utc;
+use futures::StreamExt;
+use redis::{aio::MultiplexedConnection, AsyncCommands};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    constants::{
+        COLLAB_CLIENT_TIMEOUT_SECONDS, COLLAB_HEARTBEAT_SECONDS, COLLAB_LOCK_SECONDS,
+        COLLAB_PRESENCE_TTL_SECONDS, COLLAB_RECONNECT_SECONDS,
+    },
+    dtos::collab::{ClientMessage, CollabEvent, PhraseLockVo, PresenceState, PresenceVo},
+    errors::AppError,
+    utils::collab,
+};
+
+// 只有持有者才能释放、续期软锁
+const RELEASE_LOCK_SCRIPT: &str = r#"
+local value = redis.call('GET', KEYS[1])
+if value and cjson.decode(value).session_id == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+const RENEW_LOCK_SCRIPT: &str = r#"
+local value = redis.call('GET', KEYS[1])
+if value and cjson.decode(value).session_id == ARGV[1] then
+    return redis.call('EXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// 向项目的协作频道发布消息，经 Redis 发布订阅转发到所有实例上的连接
+#[derive(Clone)]
+pub struct CollabPublisher {
+    client: redis::Client,
+}
+
+impl CollabPublisher {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn publish(&self, project_id: u64, event: &CollabEvent) -> Result<(), AppError> {
+        let payload =
+            serde_json::to_string(event).map_err(|e| AppError::SerdeError(e.to_string()))?;
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        conn.publish::<_, _, ()>(collab::channel(project_id), payload)
+            .await?;
+        Ok(())
+    }
+}
+
+/// 协作连接的参与者
+#[derive(Debug, Clone)]
+pub struct Participant {
+    pub session_id: String,
+    pub project_id: u64,
+    pub user_id: u64,
+    pub username: String,
+    // 只读 API 令牌只能观察，不能申请软锁
+    pub can_write: bool,
+}
+
+impl Participant {
+    pub fn new(project_id: u64, user_id: u64, username: String, can_write: bool) -> Self {
+        Self {
+            session_id: Uuid::new_v4().to_string(),
+            project_id,
+            user_id,
+            username,
+            can_write,
+        }
+    }
+
+    fn presence(&self, phrase_id: Option<u64>) -> PresenceVo {
+        PresenceVo {
+            session_id: self.session_id.clone(),
+            user_id: self.user_id,
+            username: self.username.clone(),
+            phrase_id,
+            seen_at: Utc::now().timestamp(),
+        }
+    }
+
+    fn lock(&self, phrase_id: u64) -> PhraseLockVo {
+        PhraseLockVo {
+            phrase_id,
+            session_id: self.session_id.clone(),
+            user_id: self.user_id,
+            username: self.username.clone(),
+        }
+    }
+}
+
+/// 单个连接的编辑状态
+#[derive(Default)]
+struct SessionState {
+    focus: Option<u64>,
+    locks: HashSet<u64>,
+}
+
+/// 实时协作中心：管理本实例的 WebSocket 连接，订阅 Redis 频道并转发给对应项目的连接
+///
+/// 在线状态和软锁保存在 Redis 中，多实例部署时各实例看到的数据一致
+pub struct CollabHub {
+    client: redis::Client,
+    publisher: CollabPublisher,
+    // 项目ID -> 会话ID -> 推送通道
+    sessions: Mutex<HashMap<u64, HashMap<String, UnboundedSender<String>>>>,
+}
+
+impl CollabHub {
+    pub fn new(client: redis::Client) -> Self {
+        Self {
+            publisher: CollabPublisher::new(client.clone()),
+            client,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 订阅全部项目频道，断开后自动重连
+    pub async fn run_subscriber(self: Arc<Self>) {
+        loop {
+            if let Err(e) = self.subscribe().await {
+                error!("协作频道订阅失败: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(COLLAB_RECONNECT_SECONDS)).await;
+        }
+    }
+
+    async fn subscribe(&self) -> Result<(), AppError> {
+        let mut pubsub = self.client.get_async_connection().await?.into_pubsub();
+        pubsub.psubscribe(collab::channel_pattern()).await?;
+        info!("协作频道订阅已建立");
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let Some(project_id) = collab::project_id_of(message.get_channel_name()) else {
+                continue;
+            };
+            match message.get_payload::<String>() {
+                Ok(payload) => self.fan_out(project_id, payload),
+                Err(e) => warn!("协作消息解析失败: {}", e),
+            }
+        }
+        Err(AppError::Cache("collab subscription closed".into()))
+    }
+
+    /// 转发给本实例上该项目的全部连接，顺便清理已关闭的连接
+    fn fan_out(&self, project_id: u64, payload: String) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some(project_sessions) = sessions.get_mut(&project_id) {
+            project_sessions.retain(|_, sender| sender.send(payload.clone()).is_ok());
+            if project_sessions.is_empty() {
+                sessions.remove(&project_id);
+            }
+        }
+    }
+
+    fn register(&self, participant: &Participant) -> UnboundedReceiver<String> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.sessions
+            .lock()
+            .unwrap()
+            .entry(participant.project_id)
+            .or_default()
+            .insert(participant.session_id.clone(), sender);
+        receiver
+    }
+
+    fn unregister(&self, participant: &Participant) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some(project_sessions) = sessions.get_mut(&participant.project_id) {
+            project_sessions.remove(&participant.session_id);
+            if project_sessions.is_empty() {
+                sessions.remove(&participant.project_id);
+            }
+        }
+    }
+
+    /// 处理一个 WebSocket 连接直到断开，断开时清除在线状态并释放持有的软锁
+    pub async fn run_session(
+        self: Arc<Self>,
+        participant: Participant,
+        mut session: Session,
+        mut stream: MessageStream,
+    ) {
+        let mut receiver = self.register(&participant);
+        let mut state = SessionState::default();
+        if let Err(e) = self.join(&participant, &mut session).await {
+            warn!("协作连接初始化失败: {}", e);
+        }
+
+        let mut heartbeat = tokio::time::interval(Duration::from_secs(COLLAB_HEARTBEAT_SECONDS));
+        let mut last_seen = Instant::now();
+        loop {
+            tokio::select! {
+                message = stream.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        last_seen = Instant::now();
+                        let reply = match serde_json::from_str::<ClientMessage>(&text) {
+                            Ok(message) => self
+                                .handle_message(&participant, &mut state, message)
+                                .await
+                                .unwrap_or_else(|e| Some(CollabEvent::Error { message: e.to_string() })),
+                            Err(e) => Some(CollabEvent::Error { message: format!("Invalid message: {}", e) }),
+                        };
+                        if let Some(reply) = reply {
+                            if send(&mut session, &reply).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Ping(bytes))) => {
+                        last_seen = Instant::now();
+                        if session.pong(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) => last_seen = Instant::now(),
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                Some(payload) = receiver.recv() => {
+                    if session.text(payload).await.is_err() {
+                        break;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    if last_seen.elapsed() > Duration::from_secs(COLLAB_CLIENT_TIMEOUT_SECONDS) {
+                        break;
+                    }
+                    if session.ping(b"").await.is_err() {
+                        break;
+                    }
+                    if let Err(e) = self.refresh(&participant, &state).await {
+                        warn!("协作在线状态刷新失败: {}", e);
+                    }
+                }
+            }
+        }
+
+        self.unregister(&participant);
+        if let Err(e) = self.leave(&participant, &state).await {
+            warn!("协作连接清理失败: {}", e);
+        }
+        let _ = session.close(None).await;
+    }
+
+    async fn handle_message(
+        &self,
+        participant: &Participant,
+        state: &mut SessionState,
+        message: ClientMessage,
+    ) -> Result<Option<CollabEvent>, AppError> {
+        match message {
+            ClientMessage::Focus { phrase_id } => {
+                state.focus = Some(phrase_id);
+                self.update_presence(participant, state, PresenceState::Focus).await?;
+                Ok(None)
+            }
+            ClientMessage::Blur => {
+                state.focus = None;
+                self.update_presence(participant, state, PresenceState::Blur).await?;
+                Ok(None)
+            }
+            ClientMessage::Lock { .. } if !participant.can_write => {
+                Err(AppError::Forbidden("API token is read-only".into()))
+            }
+            ClientMessage::Lock { phrase_id } => {
+                match self.acquire_lock(participant, phrase_id).await? {
+                    None => {
+                        state.locks.insert(phrase_id);
+                        Ok(None)
+                    }
+                    Some(holder) => Ok(Some(CollabEvent::LockDenied { lock: holder })),
+                }
+            }
+            ClientMessage::Unlock { phrase_id } => {
+                state.locks.remove(&phrase_id);
+                self.release_lock(participant, phrase_id).await?;
+                Ok(None)
+            }
+            ClientMessage::Ping => {
+                self.refresh(participant, state).await?;
+                Ok(Some(CollabEvent::Pong))
+            }
+        }
+    }
+
+    /// 登记在线状态，向当前连接发送快照并通知其他成员
+    async fn join(&self, participant: &Participant, session: &mut Session) -> Result<(), AppError> {
+        let state = SessionState::default();
+        self.update_presence(participant, &state, PresenceState::Joined).await?;
+        let (presence, locks) = self.snapshot(participant.project_id).await?;
+        let snapshot = CollabEvent::Snapshot {
+            session_id: participant.session_id.clone(),
+            presence,
+            locks,
+        };
+        send(session, &snapshot)
+            .await
+            .map_err(|_| AppError::Internal("websocket closed".into()))
+    }
+
+    async fn leave(&self, participant: &Participant, state: &SessionState) -> Result<(), AppError> {
+        for phrase_id in &state.locks {
+            self.release_lock(participant, *phrase_id).await?;
+        }
+        let mut conn = self.connection().await?;
+        conn.hdel::<_, _, ()>(collab::presence_key(participant.project_id), &participant.session_id)
+            .await?;
+        let event = CollabEvent::Presence {
+            state: PresenceState::Left,
+            presence: participant.presence(None),
+        };
+        self.publisher.publish(participant.project_id, &event).await
+    }
+
+    async fn update_presence(
+        &self,
+        participant: &Participant,
+        state: &SessionState,
+        presence_state: PresenceState,
+    ) -> Result<(), AppError> {
+        let presence = participant.presence(state.focus);
+        self.save_presence(participant, &presence).await?;
+        let event = CollabEvent::Presence {
+            state: presence_state,
+            presence,
+        };
+        self.publisher.publish(participant.project_id, &event).await
+    }
+
+    async fn save_presence(
+        &self,
+        participant: &Participant,
+        presence: &PresenceVo,
+    ) -> Result<(), AppError> {
+        let value =
+            serde_json::to_string(presence).map_err(|e| AppError::SerdeError(e.to_string()))?;
+        let key = collab::presence_key(participant.project_id);
+        let mut conn = self.connection().await?;
+        conn.hset::<_, _, _, ()>(&key, &participant.session_id, value)
+            .await?;
+        conn.expire::<_, ()>(&key, COLLAB_PRESENCE_TTL_SECONDS).await?;
+        Ok(())
+    }
+
+    /// 心跳：刷新在线时间并续期持有的软锁
+    async fn refresh(&self, participant: &Participant, state: &SessionState) -> Result<(), AppError> {
+        self.save_presence(participant, &participant.presence(state.focus))
+            .await?;
+        let mut conn = self.connection().await?;
+        let script = redis::Script::new(RENEW_LOCK_SCRIPT);
+        for phrase_id in &state.locks {
+            script
+                .key(collab::lock_key(participant.project_id, *phrase_id))
+                .arg(&participant.session_id)
+                .arg(COLLAB_LOCK_SECONDS)
+                .invoke_async::<_, ()>(&mut conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 在线成员和软锁，同时清理心跳超时的在线记录
+    async fn snapshot(
+        &self,
+        project_id: u64,
+    ) -> Result<(Vec<PresenceVo>, Vec<PhraseLockVo>), AppError> {
+        let mut conn = self.connection().await?;
+        let key = collab::presence_key(project_id);
+        let entries: HashMap<String, String> = conn.hgetall(&key).await?;
+        let stale_before = Utc::now().timestamp() - COLLAB_PRESENCE_TTL_SECONDS;
+        let mut presence = Vec::with_capacity(entries.len());
+        for (session_id, value) in entries {
+            match serde_json::from_str::<PresenceVo>(&value) {
+                Ok(p) if p.seen_at >= stale_before => presence.push(p),
+                _ => conn.hdel::<_, _, ()>(&key, session_id).await?,
+            }
+        }
+        presence.sort_by_key(|p| p.seen_at);
+
+        let mut keys = Vec::new();
+        {
+            let mut iter = conn
+                .scan_match::<_, String>(collab::lock_pattern(project_id))
+                .await?;
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+        }
+        let mut locks = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value: Option<String> = conn.get(key).await?;
+            if let Some(lock) = value.and_then(|v| serde_json::from_str(&v).ok()) {
+                locks.push(lock);
+            }
+        }
+        Ok((presence, locks))
+    }
+
+    /// 申请软锁，已被其他成员持有时返回持有者
+    async fn acquire_lock(
+        &self,
+        participant: &Participant,
+        phrase_id: u64,
+    ) -> Result<Option<PhraseLockVo>, AppError> {
+        let lock = participant.lock(phrase_id);
+        let value = serde_json::to_string(&lock).map_err(|e| AppError::SerdeError(e.to_string()))?;
+        let key = collab::lock_key(participant.project_id, phrase_id);
+        let mut conn = self.connection().await?;
+        loop {
+            let acquired: Option<String> = redis::cmd("SET")
+                .arg(&key)
+                .arg(&value)
+                .arg("NX")
+                .arg("EX")
+                .arg(COLLAB_LOCK_SECONDS)
+                .query_async(&mut conn)
+                .await?;
+            if acquired.is_some() {
+                break;
+            }
+            let holder: Option<PhraseLockVo> = conn
+                .get::<_, Option<String>>(&key)
+                .await?
+                .and_then(|v| serde_json::from_str(&v).ok());
+            match holder {
+                // 重复申请视为续期
+                Some(holder) if holder.session_id == participant.session_id => {
+                    conn.expire::<_, ()>(&key, COLLAB_LOCK_SECONDS as i64).await?;
+                    break;
+                }
+                Some(holder) => return Ok(Some(holder)),
+                // 恰好过期，重新申请
+                None => continue,
+            }
+        }
+        self.publisher
+            .publish(participant.project_id, &CollabEvent::Locked { lock })
+            .await?;
+        Ok(None)
+    }
+
+    async fn release_lock(&self, participant: &Participant, phrase_id: u64) -> Result<(), AppError> {
+        let mut conn = self.connection().await?;
+        let released: i64 = redis::Script::new(RELEASE_LOCK_SCRIPT)
+            .key(collab::lock_key(participant.project_id, phrase_id))
+            .arg(&participant.session_id)
+            .invoke_async(&mut conn)
+            .await?;
+        if released > 0 {
+            let event = CollabEvent::Unlocked {
+                phrase_id,
+                session_id: participant.session_id.clone(),
+            };
+            self.publisher.publish(participant.project_id, &event).await?;
+        }
+        Ok(())
+    }
+
+    async fn connection(&self) -> Result<MultiplexedConnection, AppError> {
+        Ok(self.client.get_multiplexed_async_connection().await?)
+    }
+}
+
+async fn send(session: &mut Session, event: &CollabEvent) -> Result<(), actix_ws::Closed> {
+    match serde_json::to_string(event) {
+        Ok(payload) => session.text(payload).await,
+        Err(e) => {
+            warn!("协作消息序列化失败: {}", e);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{Local, Utc};
+use serde_json::json;
+use sqlx::{MySqlConnection, MySqlPool};
+use tracing::warn;
+
+use crate::{
+    constants::{
+        AUDIT_ACTION_COMMENT_CREATED, AUDIT_ACTION_COMMENT_DELETED, AUDIT_ACTION_COMMENT_REOPENED,
+        AUDIT_ACTION_COMMENT_RESOLVED, AUDIT_ACTION_COMMENT_UPDATED, AUDIT_TARGET_COMMENT,
+        COMMENT_MAX_CHARS, COMMENT_MAX_MENTIONS, NOTIFICATION_CONTENT_MAX_CHARS,
+        NOTIFICATION_TARGET_COMMENT,
+    },
+    dtos::{
+        collab::CollabEvent,
+        comment::{
+            CommentQuery, CommentThreadVo, CommentVo, CreateCommentDto, IssueQuery, MentionVo,
+            UpdateCommentDto,
+        },
+    },
+    errors::AppError,
+    models::{
+        comment::Comment, enums::NotificationKind, notification::Notification,
+        operation_log::OperationLog,
+    },
+    repositories::{
+        comment_repo,
+        query::{Filter, PageQuery, Sort},
+        BaseRepository, CommentRepository, OperationLogRepository, PhraseRepository,
+        TranslationRepository, UserRepository,
+    },
+    services::{collab_service::CollabPublisher, notification_service::Notifier, BaseService},
+    utils::{
+        api_token::{check_scope, ApiTokenScope},
+        mention, text,
+    },
+};
+
+/// 操作人信息，用于权限判断和操作日志
+pub struct CommentOperator {
+    pub user_id: u64,
+    pub username: String,
+    pub ip: String,
+}
+
+pub struct CommentService {
+    repo: Arc<CommentRepository>,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    user_repo: UserRepository,
+    log_repo: OperationLogRepository,
+    collab: CollabPublisher,
+    notifier: Notifier,
+}
+
+impl CommentService {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
+        Self {
+            repo: Arc::new(CommentRepository::new(pool.clone())),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool.clone()),
+            log_repo: OperationLogRepository::new(pool.clone()),
+            collab: CollabPublisher::new(redis_client),
+            notifier: Notifier::new(pool),
+        }
+    }
+
+    /// 词条或翻译的讨论串
+    pub async fn select_threads(
+        &self,
+        req: &CommentQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<Vec<CommentThreadVo>, AppError> {
+        let phrase = self
+            .phrase_repo
+            .select_by_id(req.phrase_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        check_scope(scope, phrase.project_id)?;
+        let comments = self.repo.find_by_phrase(req.phrase_id, req.translation_id).await?;
+        let (roots, replies): (Vec<_>, Vec<_>) =
+            comments.into_iter().partition(Comment::is_root);
+        Ok(into_threads(roots, replies))
+    }
+
+    /// 项目中未解决的讨论串，最新的在前；词条或翻译已进入回收站的讨论串不列出
+    pub async fn select_issues(
+        &self,
+        req: &IssueQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<CommentThreadVo>, i64), AppError> {
+        check_scope(scope, req.project_id)?;
+        let filter = Filter::new()
+            .eq("project_id", req.project_id)
+            .eq("is_resolved", false)
+            .eq_opt("is_question", req.is_question)
+            .sql("parent_id IS NULL", vec![])
+            .sql(comment_repo::ACTIVE_TARGET_CONDITION, vec![]);
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::desc("crt_at"), Sort::desc("id")]);
+        let (roots, total) = self.repo.select_page(&query).await?;
+        let root_ids: Vec<u64> = roots.iter().map(|c| c.id).collect();
+        let replies = self.repo.find_replies(&root_ids).await?;
+        Ok((into_threads(roots, replies), total))
+    }
+
+    /// 发表评论或回复讨论串
+    pub async fn insert(
+        &self,
+        dto: &CreateCommentDto,
+        scope: Option<&ApiTokenScope>,
+        operator: &CommentOperator,
+    ) -> Result<CommentVo, AppError> {
+        let content = check_content(&dto.content)?;
+        let (project_id, phrase_id, translation_id) = self.resolve_target(dto).await?;
+        check_scope(scope, project_id)?;
+
+        let now = Local::now().naive_local();
+        let mut entity = Comment {
+            id: 0,
+            project_id,
+            phrase_id,
+            translation_id,
+            parent_id: dto.parent_id,
+            user_id: operator.user_id,
+            mentions: Some(json!(self.resolve_mentions(&content).await?)),
+            content,
+            is_question: dto.is_question.unwrap_or(false),
+            is_resolved: false,
+            resolved_by: None,
+            resolved_at: None,
+            crt_by: operator.username.clone(),
+            crt_at: now,
+            upt_by: None,
+            upt_at: now,
+            deleted_at: None,
+        };
+        let vo = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    entity.id = self.repo.insert_with(conn, &entity).await?;
+                    self.audit(conn, &entity, AUDIT_ACTION_COMMENT_CREATED, operator)
+                        .await?;
+                    self.notify_mentions(conn, &entity, &[], operator).await?;
+                    Ok(CommentVo::from(&entity))
+                })
+            })
+            .await?;
+        self.broadcast(AUDIT_ACTION_COMMENT_CREATED, &vo).await;
+        Ok(vo)
+    }
+
+    /// 修改评论，只有评论人可以修改
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        dto: &UpdateCommentDto,
+        scope: Option<&ApiTokenScope>,
+        operator: &CommentOperator,
+    ) -> Result<CommentVo, AppError> {
+        let mut exist = self.find_own(id, scope, operator).await?;
+        let mentioned = exist.mentioned_user_ids();
+        if let Some(content) = &dto.content {
+            exist.content = check_content(content)?;
+            exist.mentions = Some(json!(self.resolve_mentions(&exist.content).await?));
+        }
+        if let Some(is_question) = dto.is_question {
+            exist.is_question = is_question;
+        }
+        exist.upt_by = Some(operator.username.clone());
+        exist.upt_at = Local::now().naive_local();
+        let vo = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    self.repo.update_by_id_with(conn, id, &exist).await?;
+                    self.audit(conn, &exist, AUDIT_ACTION_COMMENT_UPDATED, operator)
+                        .await?;
+                    self.notify_mentions(conn, &exist, &mentioned, operator).await?;
+                    Ok(CommentVo::from(&exist))
+                })
+            })
+            .await?;
+        self.broadcast(AUDIT_ACTION_COMMENT_UPDATED, &vo).await;
+        Ok(vo)
+    }
+
+    /// 删除评论，只有评论人可以删除；删除根评论时一并删除回复
+    pub async fn delete(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+        operator: &CommentOperator,
+    ) -> Result<bool, AppError> {
+        let exist = self.find_own(id, scope, operator).await?;
+        let deleted_at = Utc::now().naive_utc();
+        let vo = CommentVo::from(&exist);
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.repo.soft_delete_by_ids_with(conn, &[id], deleted_at).await?;
+                if exist.is_root() {
+                    self.repo.soft_delete_replies_with(conn, id, deleted_at).await?;
+                }
+                self.audit(conn, &exist, AUDIT_ACTION_COMMENT_DELETED, operator)
+                    .await?;
+                Ok(())
+            })
+        })
+        .await?;
+        self.broadcast(AUDIT_ACTION_COMMENT_DELETED, &vo).await;
+        Ok(true)
+    }
+
+    /// 标记讨论串已解决或重新打开
+    pub async fn set_resolved(
+        &self,
+        id: u64,
+        resolved: bool,
+        scope: Option<&ApiTokenScope>,
+        operator: &CommentOperator,
+    ) -> Result<CommentVo, AppError> {
+        let mut exist = self.find(id, scope).await?;
+        if !exist.is_root() {
+            return Err(AppError::BusinessError(
+                "only a thread's first comment can be resolved".into(),
+            ));
+        }
+        if exist.is_resolved == resolved {
+            return Ok(CommentVo::from(&exist));
+        }
+        exist.is_resolved = resolved;
+        exist.resolved_by = resolved.then_some(operator.user_id);
+        exist.resolved_at = resolved.then(|| Local::now().naive_local());
+        let action = if resolved {
+            AUDIT_ACTION_COMMENT_RESOLVED
+        } else {
+            AUDIT_ACTION_COMMENT_REOPENED
+        };
+        let vo = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    self.repo
+                        .set_resolved_with(
+                            conn,
+                            id,
+                            exist.resolved_by,
+                            exist.resolved_at,
+                            &operator.username,
+                        )
+                        .await?;
+                    self.audit(conn, &exist, action, operator).await?;
+                    Ok(CommentVo::from(&exist))
+                })
+            })
+            .await?;
+        self.broadcast(action, &vo).await;
+        Ok(vo)
+    }
+
+    /// 评论所属的项目、词条和翻译：回复沿用讨论串的，评论翻译时词条取自翻译
+    async fn resolve_target(
+        &self,
+        dto: &CreateCommentDto,
+    ) -> Result<(u64, u64, Option<u64>), AppError> {
+        if let Some(parent_id) = dto.parent_id {
+            let parent = self
+                .repo
+                .select_by_id(parent_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Comment not found".into()))?;
+            if !parent.is_root() {
+                return Err(AppError::BusinessError("reply to the thread's first comment".into()));
+            }
+            return Ok((parent.project_id, parent.phrase_id, parent.translation_id));
+        }
+
+        let phrase_id = match dto.translation_id {
+            Some(translation_id) => {
+                let translation = self
+                    .translation_repo
+                    .select_by_id(translation_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Translation not found".into()))?;
+                if dto.phrase_id.is_some_and(|id| id != translation.phrase_id) {
+                    return Err(AppError::BadRequest(
+                        "Translation does not belong to the phrase".into(),
+                    ));
+                }
+                translation.phrase_id
+            }
+            None => dto
+                .phrase_id
+                .ok_or_else(|| AppError::BadRequest("phrase_id or translation_id is required".into()))?,
+        };
+        let phrase = self
+            .phrase_repo
+            .select_by_id(phrase_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        Ok((phrase.project_id, phrase.id, dto.translation_id))
+    }
+
+    /// 将 @用户名 解析为用户，不存在的用户名忽略
+    async fn resolve_mentions(&self, content: &str) -> Result<Vec<MentionVo>, AppError> {
+        let mut mentions = Vec::new();
+        for username in mention::extract_mentions(content)
+            .into_iter()
+            .take(COMMENT_MAX_MENTIONS)
+        {
+            if let Some(user) = self.user_repo.find_by_username(&username).await? {
+                mentions.push(MentionVo {
+                    user_id: user.id,
+                    username,
+                });
+            }
+        }
+        Ok(mentions)
+    }
+
+    async fn find(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<Comment, AppError> {
+        let comment = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Comment not found".into()))?;
+        check_scope(scope, comment.project_id)?;
+        Ok(comment)
+    }
+
+    async fn find_own(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+        operator: &CommentOperator,
+    ) -> Result<Comment, AppError> {
+        let comment = self.find(id, scope).await?;
+        if comment.user_id != operator.user_id {
+            return Err(AppError::Forbidden("only the author can change a comment".into()));
+        }
+        Ok(comment)
+    }
+
+    /// 评论操作写入操作日志，与评论在同一事务中
+    async fn audit(
+        &self,
+        conn: &mut MySqlConnection,
+        comment: &Comment,
+        action: &str,
+        operator: &CommentOperator,
+    ) -> Result<(), AppError> {
+        let details = json!({
+            "project_id": comment.project_id,
+            "phrase_id": comment.phrase_id,
+            "translation_id": comment.translation_id,
+            "parent_id": comment.parent_id,
+        });
+        let log = OperationLog::new(
+            operator.user_id,
+            action,
+            AUDIT_TARGET_COMMENT,
+            comment.id.to_string(),
+            Some(details),
+            &operator.ip,
+        );
+        self.log_repo.insert_with(conn, &log).await?;
+        Ok(())
+    }
+
+    /// 通知被@提及的用户，修改评论时已提及过的用户不再重复通知
+    async fn notify_mentions(
+        &self,
+        conn: &mut MySqlConnection,
+        comment: &Comment,
+        mentioned: &[u64],
+        operator: &CommentOperator,
+    ) -> Result<(), AppError> {
+        let recipients: Vec<u64> = comment
+            .mentioned_user_ids()
+            .into_iter()
+            .filter(|user_id| !mentioned.contains(user_id))
+            .collect();
+        let mut template = Notification::new(
+            NotificationKind::Mention,
+            format!("{} mentioned you in a comment", operator.username),
+            NOTIFICATION_TARGET_COMMENT,
+            comment.id,
+        );
+        template.content = Some(text::truncate(&comment.content, NOTIFICATION_CONTENT_MAX_CHARS));
+        template.project_id = Some(comment.project_id);
+        template.actor_id = Some(operator.user_id);
+        template.actor = Some(operator.username.clone());
+        self.notifier.notify_with(conn, &recipients, &template).await?;
+        Ok(())
+    }
+
+    /// 推送给项目协作通道中的成员，失败只记录日志
+    async fn broadcast(&self, action: &str, comment: &CommentVo) {
+        let event = CollabEvent::Comment {
+            action: action.to_string(),
+            comment: comment.clone(),
+        };
+        if let Err(e) = self.collab.publish(comment.project_id, &event).await {
+            warn!("协作消息发布失败: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl BaseService<Comment> for CommentService {
+    type Repository = CommentRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
+
+fn check_content(content: &str) -> Result<String, AppError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(AppError::BadRequest("Comment content is required".into()));
+    }
+    if content.chars().count() > COMMENT_MAX_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Comment must be at most {} characters",
+            COMMENT_MAX_CHARS
+        )));
+    }
+    Ok(content.to_string())
+}
+
+/// 将回复归入各自的讨论串，保持根评论的顺序
+fn into_threads(roots: Vec<Comment>, replies: Vec<Comment>) -> Vec<CommentThreadVo> {
+    let mut replies_by_root: HashMap<u64, Vec<CommentVo>> = HashMap::new();
+    for reply in &replies {
+        if let Some(parent_id) = reply.parent_id {
+            replies_by_root
+                .entry(parent_id)
+                .or_default()
+                .push(CommentVo::from(reply));
+        }
+    }
+    roots
+        .iter()
+        .map(|root| CommentThreadVo {
+            comment: CommentVo::from(root),
+            replies: replies_by_root.remove(&root.id).unwrap_or_default(),
+        })
+        .collect()
+}
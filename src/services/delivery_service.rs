@@ -0,0 +1,108 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use sqlx::MySqlPool;
+
+use crate::{
+    dtos::delivery::{DeliveryMetaVo, DeliveryVo},
+    errors::AppError,
+    models::{enums::TranslationStatus, project_language},
+    repositories::{
+        BaseRepository, ModuleRepository, PhraseRepository, ProjectLanguageRepository,
+        ProjectRepository, TranslationRepository,
+    },
+    utils::{locale, module_tree::PathFilter},
+};
+
+pub struct DeliveryService {
+    project_repo: ProjectRepository,
+    phrase_repo: PhraseRepository,
+    module_repo: ModuleRepository,
+    translation_repo: TranslationRepository,
+    project_language_repo: ProjectLanguageRepository,
+}
+
+impl DeliveryService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            project_repo: ProjectRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool),
+        }
+    }
+
+    /// 生成语言包：每个词条沿回退链取第一个已发布的翻译，都没有时使用基础语言内容
+    ///
+    /// 指定模块时只包含该模块（或其子树）下的词条
+    pub async fn resolve(
+        &self,
+        project_id: u64,
+        language: &str,
+        module: Option<&PathFilter>,
+    ) -> Result<DeliveryVo, AppError> {
+        let project = self
+            .project_repo
+            .select_by_id(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("project not found".into()))?;
+        let language = locale::canonicalize(language).map_err(AppError::BadRequest)?;
+        let languages = self.project_language_repo.find_by_project(project_id).await?;
+        if language != project.base_language && !languages.iter().any(|l| l.language == language) {
+            return Err(AppError::NotFound(format!(
+                "Language {} is not configured for the project",
+                language
+            )));
+        }
+        let chain = project_language::resolve_chain(&languages, &language, &project.base_language)?;
+
+        // (词条ID, 语言) → 内容
+        let translations: HashMap<(u64, String), String> = self
+            .translation_repo
+            .find_by_project_and_languages(project_id, &chain)
+            .await?
+            .into_iter()
+            .filter(|t| matches!(t.status, TranslationStatus::Published))
+            .map(|t| ((t.phrase_id, t.language), t.content))
+            .collect();
+
+        let mut phrases = self.phrase_repo.find_by_project_id(project_id).await?;
+        if let Some(module) = module {
+            let module_ids: HashSet<u64> = self
+                .module_repo
+                .find_by_project_id(project_id)
+                .await?
+                .into_iter()
+                .filter(|m| m.path.as_deref().is_some_and(|path| module.matches(path)))
+                .map(|m| m.id)
+                .collect();
+            phrases.retain(|phrase| phrase.module_id.is_some_and(|id| module_ids.contains(&id)));
+        }
+
+        let mut messages = BTreeMap::new();
+        let mut fallbacks = BTreeMap::new();
+        for phrase in phrases {
+            let (used, content) = chain
+                .iter()
+                .find_map(|l| {
+                    translations
+                        .get(&(phrase.id, l.clone()))
+                        .map(|content| (l.clone(), content.clone()))
+                })
+                .unwrap_or_else(|| (project.base_language.clone(), phrase.base_content.clone()));
+            if used != language {
+                fallbacks.insert(phrase.key.clone(), used);
+            }
+            messages.insert(phrase.key, content);
+        }
+
+        Ok(DeliveryVo {
+            language,
+            messages,
+            meta: DeliveryMetaVo { chain, fallbacks },
+        })
+    }
+}
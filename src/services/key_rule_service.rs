@@ -0,0 +1,245 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    constants::{
+        KEY_RULE_DEFAULT_SEPARATOR, KEY_RULE_FORBIDDEN_MAX_CHARS, KEY_RULE_PATTERN_MAX_CHARS,
+        KEY_RULE_SEPARATOR_MAX_CHARS,
+    },
+    dtos::key_rule::{KeyLintItem, KeyLintQuery, UpdateKeyRuleDto},
+    errors::AppError,
+    models::key_rule::KeyRule,
+    repositories::{
+        query::PageQuery, BaseRepository, KeyRuleRepository, ModuleRepository, PhraseRepository,
+        ProjectRepository, UserRepository,
+    },
+    services::BaseService,
+    utils::key_rule::KeyViolation,
+};
+
+pub struct KeyRuleService {
+    repo: Arc<KeyRuleRepository>,
+    project_repo: ProjectRepository,
+    module_repo: ModuleRepository,
+    phrase_repo: PhraseRepository,
+    user_repo: UserRepository,
+}
+
+impl KeyRuleService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: Arc::new(KeyRuleRepository::new(pool.clone())),
+            project_repo: ProjectRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool),
+        }
+    }
+
+    /// 项目的标识符规则，未设置时为 None
+    pub async fn select_rule(&self, project_id: u64) -> Result<Option<KeyRule>, AppError> {
+        self.repo.select_by_id(project_id).await
+    }
+
+    /// 设置项目的标识符规则，只对之后创建、导入和重命名的词条生效，已有词条通过检查接口修正
+    pub async fn update_rule(
+        &self,
+        project_id: u64,
+        dto: &UpdateKeyRuleDto,
+        operator: &str,
+    ) -> Result<Option<KeyRule>, AppError> {
+        self.user_repo.require_manager(operator, "manage key rules").await?;
+        self.project_repo
+            .select_by_id(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".into()))?;
+        let now = Local::now().naive_local();
+        let rule = KeyRule {
+            project_id,
+            pattern: dto.pattern.clone().filter(|pattern| !pattern.is_empty()),
+            require_module_prefix: dto.require_module_prefix,
+            key_separator: dto
+                .separator
+                .clone()
+                .filter(|separator| !separator.is_empty())
+                .unwrap_or_else(|| KEY_RULE_DEFAULT_SEPARATOR.to_string()),
+            max_depth: dto.max_depth,
+            forbidden_chars: dto.forbidden_chars.clone().filter(|chars| !chars.is_empty()),
+            crt_by: operator.to_string(),
+            crt_at: now,
+            upt_by: Some(operator.to_string()),
+            upt_at: now,
+        };
+        check_rule(&rule)?;
+        self.repo.upsert(&rule).await?;
+        self.select_rule(project_id).await
+    }
+
+    /// 删除项目的标识符规则，之后不再校验
+    pub async fn delete_rule(&self, project_id: u64, operator: &str) -> Result<bool, AppError> {
+        self.user_repo.require_manager(operator, "manage key rules").await?;
+        self.repo.delete_by_id(project_id).await
+    }
+
+    /// 检查项目中不符合规则的词条并给出建议的新标识符，按词条id排序
+    pub async fn lint(
+        &self,
+        project_id: u64,
+        req: &KeyLintQuery,
+    ) -> Result<(Vec<KeyLintItem>, i64), AppError> {
+        let Some(rule) = self.select_rule(project_id).await? else {
+            return Ok((vec![], 0));
+        };
+        let checker = rule.checker()?;
+        let modules: HashMap<u64, _> = self
+            .module_repo
+            .find_by_project_id(project_id)
+            .await?
+            .into_iter()
+            .map(|module| (module.id, module))
+            .collect();
+        let mut phrases = self.phrase_repo.find_by_project_id(project_id).await?;
+        phrases.retain(|phrase| req.module_id.is_none() || phrase.module_id == req.module_id);
+        phrases.sort_by_key(|phrase| phrase.id);
+
+        let mut items = Vec::new();
+        for phrase in phrases {
+            let prefix = rule.prefix_for(phrase.module_id.and_then(|id| modules.get(&id)));
+            let violations = checker.check(&phrase.key, prefix.as_deref());
+            if violations.is_empty() {
+                continue;
+            }
+            items.push(KeyLintItem {
+                phrase_id: phrase.id,
+                module_id: phrase.module_id,
+                suggestion: checker.suggest(&phrase.key, prefix.as_deref()),
+                key: phrase.key,
+                violations,
+                conflict: false,
+            });
+        }
+        self.mark_conflicts(project_id, &mut items).await?;
+
+        let total = items.len() as i64;
+        let (offset, limit) = PageQuery::new(req.page, req.size).limit();
+        let items = items
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+        Ok((items, total))
+    }
+
+    /// 建议的标识符已被占用或彼此重复时标记为冲突，与唯一索引一样不区分大小写
+    async fn mark_conflicts(&self, project_id: u64, items: &mut [KeyLintItem]) -> Result<(), AppError> {
+        let taken: HashSet<String> = self
+            .phrase_repo
+            .find_keys_by_project(project_id)
+            .await?
+            .iter()
+            .map(|key| key.to_lowercase())
+            .collect();
+        let mut suggested: HashMap<String, usize> = HashMap::new();
+        for suggestion in items.iter().filter_map(|item| item.suggestion.as_ref()) {
+            *suggested.entry(suggestion.to_lowercase()).or_default() += 1;
+        }
+        for item in items.iter_mut() {
+            if let Some(suggestion) = &item.suggestion {
+                let suggestion = suggestion.to_lowercase();
+                item.conflict = (taken.contains(&suggestion) && suggestion != item.key.to_lowercase())
+                    || suggested.get(&suggestion).is_some_and(|count| *count > 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_rule(rule: &KeyRule) -> Result<(), AppError> {
+    if rule.pattern.as_ref().is_some_and(|pattern| pattern.chars().count() > KEY_RULE_PATTERN_MAX_CHARS) {
+        return Err(AppError::BadRequest(format!(
+            "Key pattern must be at most {} characters",
+            KEY_RULE_PATTERN_MAX_CHARS
+        )));
+    }
+    if rule.key_separator.chars().count() > KEY_RULE_SEPARATOR_MAX_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Separator must be at most {} characters",
+            KEY_RULE_SEPARATOR_MAX_CHARS
+        )));
+    }
+    if let Some(chars) = &rule.forbidden_chars {
+        if chars.chars().count() > KEY_RULE_FORBIDDEN_MAX_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "At most {} forbidden characters are allowed",
+                KEY_RULE_FORBIDDEN_MAX_CHARS
+            )));
+        }
+        if rule.key_separator.chars().any(|c| chars.contains(c)) {
+            return Err(AppError::BadRequest("Separator cannot be a forbidden character".into()));
+        }
+    }
+    if rule.max_depth == Some(0) {
+        return Err(AppError::BadRequest("Max depth must be at least 1".into()));
+    }
+    rule.checker()?;
+    Ok(())
+}
+
+#[async_trait]
+impl BaseService<KeyRule> for KeyRuleService {
+    type Repository = KeyRuleRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
+
+/// 在业务事务中按项目规则校验词条标识符
+pub struct KeyGuard {
+    repo: KeyRuleRepository,
+    module_repo: ModuleRepository,
+}
+
+impl KeyGuard {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: KeyRuleRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool),
+        }
+    }
+
+    /// 项目未设置规则时不校验；不符合时返回 BadRequest，能自动修正的附带建议的标识符
+    pub async fn check_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        module_id: Option<u64>,
+        key: &str,
+    ) -> Result<(), AppError> {
+        let Some(rule) = self.repo.select_by_id_with(conn, project_id).await? else {
+            return Ok(());
+        };
+        let module = match module_id.filter(|_| rule.require_module_prefix) {
+            Some(module_id) => self.module_repo.select_by_id_with(conn, module_id).await?,
+            None => None,
+        };
+        let prefix = rule.prefix_for(module.as_ref());
+        let checker = rule.checker()?;
+        let violations = checker.check(key, prefix.as_deref());
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let reasons: Vec<&str> = violations.iter().map(KeyViolation::describe).collect();
+        let mut message = format!("Key {} {}", key, reasons.join(", "));
+        if let Some(suggestion) = checker.suggest(key, prefix.as_deref()) {
+            message.push_str(&format!(", suggested: {}", suggestion));
+        }
+        Err(AppError::BadRequest(message))
+    }
+}
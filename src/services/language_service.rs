@@ -1,4 +1,5 @@
 use chrono::Utc;
+use serde_json::json;
 use sqlx::MySqlPool;
 use std::sync::Arc;
 
@@ -8,8 +9,13 @@ use crate::{
     dtos::language::LanguageVo,
     errors::AppError,
     models::language::Language,
-    repositories::{base_repo::BaseRepository, language_repo::LanguageRepository},
+    repositories::{
+        base_repo::BaseRepository,
+        language_repo::LanguageRepository,
+        query::{Filter, PageQuery, Sort},
+    },
     services::BaseService,
+    utils::locale::LanguageTag,
 };
 
 pub struct LanguageService {
@@ -25,23 +31,18 @@ impl LanguageService {
 
     /// 分页查询语言列表
     pub async fn select_by_page(&self, req: &PageRequest) -> Result<(Vec<LanguageVo>, i64), AppError> {
-        if let Some(search_key) = &req.search_key {
-            let languages = self
-                .repo
-                .select_page_by_key(req.page, req.size, search_key)
-                .await?;
-            let list = languages.0.iter().map(|language| LanguageVo::from(language)).collect();
-            Ok((list, languages.1))
-        } else {
-            let languages = self.repo.select_by_page(req.page, req.size).await?;
-            let list = languages.0.iter().map(|language| LanguageVo::from(language)).collect();
-            Ok((list, languages.1))
-        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(Filter::new().search_opt(&["code", "name"], req.search_key.as_deref()))
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        let languages = self.repo.select_page(&query).await?;
+        let list = languages.0.iter().map(|language| LanguageVo::from(language)).collect();
+        Ok((list, languages.1))
     }
     
     /// 插入语言
     pub async fn insert(&self, language: &CreateLanguageDto) -> Result<u64, AppError> {
-        let code = language.code.clone();
+        let tag = LanguageTag::parse(&language.code).map_err(AppError::BadRequest)?;
+        let code = tag.to_string();
         let name = language.name.clone();
         if let Some(_) = self.repo.find_by_code(&code,0).await? {
             return Err(AppError::BusinessError("repeat language code".into()));
@@ -53,6 +54,9 @@ impl LanguageService {
             id: 0,
             code,
             name,
+            native_name: language.native_name.clone(),
+            direction: tag.direction().to_string(),
+            plural_categories: Some(json!(tag.plural_categories())),
             is_active: language.is_active.unwrap_or(false),
             is_native: language.is_native.unwrap_or(false),
             crt_by: language.crt_by.clone().unwrap_or("admin".to_owned()),
@@ -76,11 +80,17 @@ impl LanguageService {
             return Err(AppError::BusinessError("language not found".into()));
         }
         let mut exist = exist.unwrap();
-        if let Some(code) = code { 
-            if let Some(_) = self.repo.find_by_code(&code,id).await? {
-             return Err(AppError::BusinessError("repeat language code".into())); 
+        // 翻译、术语和项目通过代码引用语言，代码创建后不能修改
+        if let Some(code) = code {
+            let code = LanguageTag::parse(&code).map_err(AppError::BadRequest)?.to_string();
+            if code != exist.code {
+                return Err(AppError::BusinessError(
+                    "language code can not be changed, create a new language instead".into(),
+                ));
             }
-            exist.code = code;
+        }
+        if let Some(native_name) = &language.native_name {
+            exist.native_name = Some(native_name.clone());
         }
         if let Some(name) = name {
             if let Some(_) = self.repo.find_by_name(&name,id).await? {
@@ -92,7 +102,7 @@ impl LanguageService {
             exist.is_active = is_active
         }
         if let Some(is_native) = is_native {
-            exist.is_active = is_native
+            exist.is_native = is_native
         }
         if let Some(upt_by) = upt_by {
             exist.upt_by = Some(upt_by)
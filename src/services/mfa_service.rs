@@ -0,0 +1,321 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    config::SETTINGS,
+    constants::{
+        AUDIT_ACTION_MFA_DISABLED, AUDIT_ACTION_MFA_ENABLED, AUDIT_ACTION_MFA_FAILED,
+        AUDIT_ACTION_MFA_VERIFIED, AUDIT_ACTION_RECOVERY_CODES_REGENERATED,
+        AUDIT_ACTION_RECOVERY_CODE_USED, AUDIT_TARGET_USER, MFA_CHALLENGE_PREFIX,
+        MFA_CHALLENGE_SECONDS, MFA_INVALID_CODE, MFA_MAX_ATTEMPTS,
+    },
+    dtos::mfa::{MfaChallengeVo, MfaSetupVo, MfaStatusVo, MfaVerifyDto},
+    errors::AppError,
+    models::{operation_log::OperationLog, recovery_code::RecoveryCode, user::User},
+    repositories::{
+        BaseRepository, OperationLogRepository, RecoveryCodeRepository, UserRepository,
+    },
+    services::BaseService,
+    utils::{oidc, totp},
+};
+
+pub struct MfaService {
+    redis_client: redis::Client,
+    user_repo: UserRepository,
+    code_repo: RecoveryCodeRepository,
+    log_repo: OperationLogRepository,
+}
+
+impl MfaService {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
+        Self {
+            redis_client,
+            user_repo: UserRepository::new(pool.clone()),
+            code_repo: RecoveryCodeRepository::new(pool.clone()),
+            log_repo: OperationLogRepository::new(pool),
+        }
+    }
+
+    /// 用户所属角色是否强制要求两步验证
+    pub async fn is_required(&self, user: &User) -> Result<bool, AppError> {
+        let username = match &user.username {
+            Some(username) => username,
+            None => return Ok(false),
+        };
+        let roles = self.user_repo.find_roles(username).await?;
+        Ok(roles
+            .iter()
+            .any(|role| SETTINGS.mfa.required_roles.contains(role)))
+    }
+
+    /// 第一步认证通过后调用：需要两步验证时创建挑战，否则返回 None
+    pub async fn challenge(&self, user: &User) -> Result<Option<MfaChallengeVo>, AppError> {
+        if !user.totp_enabled && !self.is_required(user).await? {
+            return Ok(None);
+        }
+
+        let challenge_token = oidc::random_token();
+        let key = challenge_key(&challenge_token);
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        conn.hset::<_, _, _, ()>(&key, "user_id", user.id).await?;
+        conn.expire::<_, ()>(&key, MFA_CHALLENGE_SECONDS as i64)
+            .await?;
+
+        Ok(Some(MfaChallengeVo {
+            mfa_required: true,
+            challenge_token,
+            enrolled: user.totp_enabled,
+            expires_in: MFA_CHALLENGE_SECONDS,
+        }))
+    }
+
+    /// 登录过程中为尚未绑定的用户生成密钥
+    pub async fn setup_for_challenge(&self, challenge_token: &str) -> Result<MfaSetupVo, AppError> {
+        let user = self.challenge_user(challenge_token).await?;
+        self.setup_user(&user).await
+    }
+
+    /// 已登录用户生成新的 TOTP 密钥，验证通过后才会启用
+    pub async fn setup(&self, user_id: u64) -> Result<MfaSetupVo, AppError> {
+        let user = self.find_user(user_id).await?;
+        self.setup_user(&user).await
+    }
+
+    /// 已登录用户用验证码确认绑定，返回恢复码
+    pub async fn enable(&self, user_id: u64, code: &str, ip: &str) -> Result<Vec<String>, AppError> {
+        let user = self.find_user(user_id).await?;
+        if user.totp_enabled {
+            return Err(AppError::BadRequest("Two-factor authentication is already enabled".into()));
+        }
+        if !self.check_totp(&user, code).await? {
+            return Err(AppError::BadRequest(MFA_INVALID_CODE.into()));
+        }
+        self.activate(&user, ip).await
+    }
+
+    /// 登录第二步：校验验证码或恢复码
+    ///
+    /// 尚未绑定的用户在此完成绑定，同时返回恢复码
+    pub async fn verify(
+        &self,
+        dto: &MfaVerifyDto,
+        ip: &str,
+    ) -> Result<(User, Option<Vec<String>>), AppError> {
+        let user = self.challenge_user(&dto.challenge_token).await?;
+
+        let verified = match (&dto.code, &dto.recovery_code) {
+            (Some(code), _) => self.check_totp(&user, code).await?,
+            (None, Some(recovery_code)) if user.totp_enabled => {
+                let used = self
+                    .code_repo
+                    .consume(user.id, &totp::hash_recovery_code(recovery_code))
+                    .await?;
+                if used {
+                    self.audit(user.id, AUDIT_ACTION_RECOVERY_CODE_USED, ip).await?;
+                }
+                used
+            }
+            _ => return Err(AppError::BadRequest("Verification code is required".into())),
+        };
+
+        if !verified {
+            self.record_failure(&dto.challenge_token).await?;
+            self.audit(user.id, AUDIT_ACTION_MFA_FAILED, ip).await?;
+            return Err(AppError::Auth(MFA_INVALID_CODE.into()));
+        }
+
+        // 挑战只能使用一次
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        conn.del::<_, ()>(challenge_key(&dto.challenge_token))
+            .await?;
+
+        let recovery_codes = if user.totp_enabled {
+            self.audit(user.id, AUDIT_ACTION_MFA_VERIFIED, ip).await?;
+            None
+        } else {
+            Some(self.activate(&user, ip).await?)
+        };
+        Ok((user, recovery_codes))
+    }
+
+    /// 关闭两步验证，角色策略要求时不允许关闭
+    pub async fn disable(&self, user_id: u64, code: &str, ip: &str) -> Result<bool, AppError> {
+        let user = self.find_user(user_id).await?;
+        if !user.totp_enabled {
+            return Err(AppError::BadRequest("Two-factor authentication is not enabled".into()));
+        }
+        if self.is_required(&user).await? {
+            return Err(AppError::Forbidden(
+                "Two-factor authentication is required for your role".into(),
+            ));
+        }
+        if !self.check_totp(&user, code).await? {
+            return Err(AppError::BadRequest(MFA_INVALID_CODE.into()));
+        }
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.user_repo.disable_totp(conn, user.id).await?;
+                self.code_repo.delete_by_user_id(conn, user.id).await?;
+                Ok(())
+            })
+        })
+        .await?;
+        self.audit(user.id, AUDIT_ACTION_MFA_DISABLED, ip).await?;
+        Ok(true)
+    }
+
+    /// 重新生成恢复码，旧恢复码全部失效
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: u64,
+        code: &str,
+        ip: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let user = self.find_user(user_id).await?;
+        if !user.totp_enabled {
+            return Err(AppError::BadRequest("Two-factor authentication is not enabled".into()));
+        }
+        if !self.check_totp(&user, code).await? {
+            return Err(AppError::BadRequest(MFA_INVALID_CODE.into()));
+        }
+        let codes = self
+            .with_transaction(|conn, _| Box::pin(self.issue_recovery_codes(conn, user.id)))
+            .await?;
+        self.audit(user.id, AUDIT_ACTION_RECOVERY_CODES_REGENERATED, ip).await?;
+        Ok(codes)
+    }
+
+    pub async fn status(&self, user_id: u64) -> Result<MfaStatusVo, AppError> {
+        let user = self.find_user(user_id).await?;
+        Ok(MfaStatusVo {
+            enabled: user.totp_enabled,
+            required: self.is_required(&user).await?,
+            recovery_codes_remaining: self.code_repo.count_unused(user.id).await?,
+        })
+    }
+
+    async fn find_user(&self, user_id: u64) -> Result<User, AppError> {
+        self.user_repo
+            .select_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+
+    async fn challenge_user(&self, challenge_token: &str) -> Result<User, AppError> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let user_id: Option<u64> = conn.hget(challenge_key(challenge_token), "user_id").await?;
+        let user_id = user_id
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired challenge".into()))?;
+        let user = self.find_user(user_id).await?;
+        if !user.status {
+            return Err(AppError::Auth("User is disabled".into()));
+        }
+        Ok(user)
+    }
+
+    /// 记录一次错误，超过上限后作废挑战，需重新输入密码
+    async fn record_failure(&self, challenge_token: &str) -> Result<(), AppError> {
+        let key = challenge_key(challenge_token);
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let attempts: u64 = conn.hincr(&key, "attempts", 1).await?;
+        if attempts >= MFA_MAX_ATTEMPTS {
+            conn.del::<_, ()>(&key).await?;
+        }
+        Ok(())
+    }
+
+    async fn setup_user(&self, user: &User) -> Result<MfaSetupVo, AppError> {
+        if user.totp_enabled {
+            return Err(AppError::BadRequest("Two-factor authentication is already enabled".into()));
+        }
+        let secret = totp::generate_secret();
+        self.user_repo.set_totp_secret(user.id, &secret).await?;
+
+        let account = user
+            .username
+            .clone()
+            .or(user.email.clone())
+            .unwrap_or_else(|| user.id.to_string());
+        Ok(MfaSetupVo {
+            otpauth_uri: totp::otpauth_uri(&SETTINGS.mfa.issuer, &account, &secret),
+            secret,
+        })
+    }
+
+    /// 校验 TOTP 验证码，同一时间窗口的验证码只能使用一次
+    async fn check_totp(&self, user: &User, code: &str) -> Result<bool, AppError> {
+        let secret = user
+            .totp_secret
+            .as_deref()
+            .ok_or_else(|| AppError::BadRequest("Two-factor authentication is not set up".into()))?;
+        match totp::verify(secret, code, Utc::now().timestamp(), user.totp_last_step) {
+            Some(step) => self.user_repo.record_totp_step(user.id, step).await,
+            None => Ok(false),
+        }
+    }
+
+    async fn activate(&self, user: &User, ip: &str) -> Result<Vec<String>, AppError> {
+        // 启用与生成恢复码在同一事务中，避免启用后没有可用的恢复码
+        let codes = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    self.user_repo.enable_totp(conn, user.id).await?;
+                    self.issue_recovery_codes(conn, user.id).await
+                })
+            })
+            .await?;
+        self.audit(user.id, AUDIT_ACTION_MFA_ENABLED, ip).await?;
+        Ok(codes)
+    }
+
+    async fn issue_recovery_codes(
+        &self,
+        conn: &mut MySqlConnection,
+        user_id: u64,
+    ) -> Result<Vec<String>, AppError> {
+        self.code_repo.delete_by_user_id(conn, user_id).await?;
+        let codes = totp::generate_recovery_codes();
+        for code in &codes {
+            let entity = RecoveryCode {
+                id: 0,
+                user_id,
+                code_hash: totp::hash_recovery_code(code),
+                used_at: None,
+                crt_by: user_id.to_string(),
+                crt_at: Utc::now(),
+                upt_by: None,
+                upt_at: Utc::now(),
+            };
+            self.code_repo.insert_with(conn, &entity).await?;
+        }
+        Ok(codes)
+    }
+
+    async fn audit(&self, user_id: u64, action: &str, ip: &str) -> Result<(), AppError> {
+        let log = OperationLog::new(
+            user_id,
+            action,
+            AUDIT_TARGET_USER,
+            user_id.to_string(),
+            None,
+            ip,
+        );
+        self.log_repo.insert(&log).await?;
+        Ok(())
+    }
+}
+
+fn challenge_key(challenge_token: &str) -> String {
+    format!("{}{}", MFA_CHALLENGE_PREFIX, challenge_token)
+}
+
+impl BaseService<RecoveryCode> for MfaService {
+    type Repository = RecoveryCodeRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.code_repo
+    }
+}
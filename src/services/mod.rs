@@ -3,11 +3,25 @@ pub mod user_service;
 
 pub use base_service::BaseService;
 
+pub mod api_token_service;
+pub mod collab_service;
+pub mod comment_service;
+pub mod delivery_service;
+pub mod key_rule_service;
 pub mod language_service;
+pub mod mfa_service;
 pub mod module_service;
+pub mod notification_service;
+pub mod oidc_service;
 pub mod phrase_service;
 pub mod phrase_type_service;
 pub mod project_service;
+pub mod report_service;
 pub mod screenshot_service;
+pub mod search_service;
+pub mod stats_service;
+pub mod task_service;
 pub mod term_service;
 pub mod translation_service;
+pub mod trash_service;
+pub mod webhook_service;
@@ -1,22 +1,53 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use sqlx::MySqlPool;
+use chrono::{Local, Utc};
+use sqlx::{MySqlConnection, MySqlPool};
+use tracing::warn;
 
 use crate::{
-    dtos::module::{CreateModuleDto, UpdateModuleDto},
+    constants::MODULE_PATH_MAX_CHARS,
+    dtos::module::{
+        CreateModuleDto, ModuleProgressVo, ModuleQuery, ModuleTreeNodeVo, MoveModuleDto,
+        UpdateModuleDto,
+    },
     errors::AppError,
     models::module::Module,
-    repositories::{module_repo::ModuleRepository, BaseRepository}, services::BaseService,
+    repositories::{
+        module_repo::ModuleRepository,
+        query::{Filter, PageQuery, Sort},
+        BaseRepository, PhraseRepository, ProjectLanguageRepository, ProjectRepository,
+        ScreenshotRepository, TranslationRepository, UserRepository,
+    },
+    services::BaseService,
+    utils::{
+        api_token::{check_scope, ApiTokenScope},
+        module_tree,
+        stats_cache::StatsCache,
+    },
 };
 
 pub struct ModuleService {
     repo: Arc<ModuleRepository>,
+    project_repo: ProjectRepository,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    screenshot_repo: ScreenshotRepository,
+    project_language_repo: ProjectLanguageRepository,
+    user_repo: UserRepository,
+    stats_cache: StatsCache,
 }
 
 impl ModuleService {
-    pub fn new(pool: Arc<MySqlPool>) -> Self {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
         Self {
-            repo: Arc::new(ModuleRepository::new(pool)),
+            repo: Arc::new(ModuleRepository::new(pool.clone())),
+            project_repo: ProjectRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            screenshot_repo: ScreenshotRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool),
+            stats_cache: StatsCache::new(redis_client),
         }
     }
 
@@ -24,33 +55,383 @@ impl ModuleService {
         self.repo.select_by_id(id).await
     }
 
+    /// 校验令牌范围能否访问模块所属的项目，JWT 认证或模块不存在时不校验
+    pub async fn check_access(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.repo.find_project_id(id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
+
     pub async fn select_all(&self) -> Result<Vec<Module>, AppError> {
         self.repo.select_all().await
     }
 
+    /// 按项目、名称过滤的分页查询
     pub async fn select_by_page(
         &self,
-        page: u32,
-        page_size: u32,
+        req: &ModuleQuery,
+        scope: Option<&ApiTokenScope>,
     ) -> Result<(Vec<Module>, i64), AppError> {
-        self.repo.select_by_page(page, page_size).await
+        self.repo.select_page(&page_query(req, scope)).await
     }
 
+    /// 回收站中的模块
+    pub async fn select_trash_page(
+        &self,
+        req: &ModuleQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Module>, i64), AppError> {
+        self.repo.select_deleted_page(&page_query(req, scope)).await
+    }
+
+    /// 创建模块，路径由父模块路径和名称组成
     pub async fn insert(&self, module: &CreateModuleDto) -> Result<u64, AppError> {
-        self.repo.insert(&Module::from(module)).await
+        let mut module = Module::from(module);
+        module.name = module.name.trim().to_string();
+        check_name(&module.name)?;
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                let parent_path = match module.parent_id {
+                    Some(parent_id) => self.find_parent(conn, parent_id, module.project_id).await?.path,
+                    None => None,
+                };
+                let path = module_tree::join(parent_path.as_deref(), &module.name);
+                self.check_path(conn, module.project_id, &path, 0).await?;
+                module.path = Some(path);
+                self.repo.insert_with(conn, &module).await
+            })
+        })
+        .await
+    }
+
+    /// 修改名称时同步修改模块及其子孙模块的路径，与移动模块一样只允许项目经理操作
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        module: &UpdateModuleDto,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        let mut exist = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("module not found".into()))?;
+        let name = module.name.as_deref().map(str::trim).filter(|name| *name != exist.name);
+        if let Some(name) = name {
+            self.user_repo.require_manager(operator, "rename modules").await?;
+            check_name(name)?;
+        }
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                if let Some(name) = name {
+                    let parent_path = exist
+                        .path
+                        .as_deref()
+                        .and_then(|path| path.rsplit_once('/'))
+                        .map(|(parent_path, _)| parent_path.to_string());
+                    exist.name = name.to_string();
+                    self.relocate(conn, &mut exist, parent_path.as_deref()).await?;
+                }
+                if let Some(description) = &module.description {
+                    exist.description = Some(description.clone());
+                }
+                exist.upt_at = Local::now().naive_local();
+                self.repo.update_by_id_with(conn, id, &exist).await
+            })
+        })
+        .await
     }
 
-    pub async fn update_by_id(&self, id: u64, module: &UpdateModuleDto) -> Result<bool, AppError> {
-        self.repo.update_by_id(id, &Module::from(module)).await
+    /// 将模块连同子孙模块移动到另一个父模块下，parent_id 为空时移动到根级
+    pub async fn move_module(
+        &self,
+        id: u64,
+        dto: &MoveModuleDto,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        self.user_repo.require_manager(operator, "move modules").await?;
+        let mut module = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("module not found".into()))?;
+        if module.parent_id == dto.parent_id {
+            return Ok(false);
+        }
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                let parent_path = match dto.parent_id {
+                    Some(parent_id) => {
+                        let parent = self.find_parent(conn, parent_id, module.project_id).await?;
+                        let parent_path = parent.path.unwrap_or_default();
+                        let path = module.path.as_deref().unwrap_or_default();
+                        if parent.id == module.id || module_tree::is_descendant(&parent_path, path) {
+                            return Err(AppError::BadRequest(
+                                "cannot move a module into itself or its descendants".into(),
+                            ));
+                        }
+                        Some(parent_path)
+                    }
+                    None => None,
+                };
+                module.parent_id = dto.parent_id;
+                self.relocate(conn, &mut module, parent_path.as_deref()).await?;
+                Ok(true)
+            })
+        })
+        .await
     }
 
-    pub async fn delete_by_id(&self, id: u64) -> Result<bool, AppError> {
-        self.repo.delete_by_id(id).await
+    /// 按父模块路径和名称重新计算路径，子孙模块（包括回收站中的）一起更新
+    async fn relocate(
+        &self,
+        conn: &mut MySqlConnection,
+        module: &mut Module,
+        parent_path: Option<&str>,
+    ) -> Result<(), AppError> {
+        let old_path = module.path.clone().unwrap_or_else(|| module.name.clone());
+        let new_path = module_tree::join(parent_path, &module.name);
+        if new_path != old_path {
+            self.check_path(conn, module.project_id, &new_path, module.id).await?;
+            let subtree = self.repo.find_subtree_with(conn, module.project_id, &old_path).await?;
+            let too_long = subtree.iter().filter_map(|m| m.path.as_deref()).any(|path| {
+                module_tree::rebase(path, &old_path, &new_path)
+                    .is_some_and(|path| path.chars().count() > MODULE_PATH_MAX_CHARS)
+            });
+            if too_long {
+                return Err(AppError::BadRequest(format!(
+                    "Module path must be at most {} characters",
+                    MODULE_PATH_MAX_CHARS
+                )));
+            }
+            self.repo
+                .rebase_descendants_with(conn, module.project_id, &old_path, &new_path)
+                .await?;
+        }
+        module.path = Some(new_path);
+        self.repo.update_location_with(conn, module).await?;
+        Ok(())
+    }
+
+    /// 父模块须未删除且属于同一项目
+    async fn find_parent(
+        &self,
+        conn: &mut MySqlConnection,
+        parent_id: u64,
+        project_id: u64,
+    ) -> Result<Module, AppError> {
+        self.repo
+            .select_by_id_with(conn, parent_id)
+            .await?
+            .filter(|parent| parent.project_id == project_id)
+            .ok_or_else(|| AppError::BadRequest("Parent module not found in this project".into()))
+    }
+
+    async fn check_path(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        path: &str,
+        exclude_id: u64,
+    ) -> Result<(), AppError> {
+        if path.chars().count() > MODULE_PATH_MAX_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "Module path must be at most {} characters",
+                MODULE_PATH_MAX_CHARS
+            )));
+        }
+        if self.repo.exists_path_with(conn, project_id, path, exclude_id).await? {
+            return Err(AppError::BusinessError(format!("Module {} already exists", path)));
+        }
+        Ok(())
+    }
+
+    /// 删除模块，子孙模块及其下的词条、翻译和截图一起移入回收站
+    pub async fn soft_delete(&self, id: u64) -> Result<bool, AppError> {
+        let deleted_at = Utc::now().naive_utc();
+        let project_id = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    let Some(module) = self.repo.select_by_id_with(conn, id).await? else {
+                        return Ok(None);
+                    };
+                    let path = module.path.unwrap_or(module.name);
+                    let ids: Vec<u64> = self
+                        .repo
+                        .find_subtree_with(conn, module.project_id, &path)
+                        .await?
+                        .into_iter()
+                        .filter(|m| m.deleted_at.is_none())
+                        .map(|m| m.id)
+                        .collect();
+                    self.repo.soft_delete_by_ids_with(conn, &ids, deleted_at).await?;
+                    for module_id in &ids {
+                        self.phrase_repo.soft_delete_by_module(conn, *module_id, deleted_at).await?;
+                    }
+                    self.translation_repo
+                        .soft_delete_by_deleted_phrases(conn, deleted_at)
+                        .await?;
+                    self.screenshot_repo
+                        .soft_delete_by_deleted_phrases(conn, deleted_at)
+                        .await?;
+                    Ok(Some(module.project_id))
+                })
+            })
+            .await?;
+        if let Some(project_id) = project_id {
+            self.invalidate_stats(project_id).await;
+        }
+        Ok(project_id.is_some())
+    }
+
+    /// 从回收站恢复模块及随它一起删除的子孙模块，所属项目和父模块必须未被删除
+    pub async fn restore(&self, id: u64) -> Result<bool, AppError> {
+        let module = self
+            .repo
+            .select_deleted_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("module not found in trash".into()))?;
+        if self.project_repo.select_by_id(module.project_id).await?.is_none() {
+            return Err(AppError::BusinessError("restore the project first".into()));
+        }
+        if let Some(parent_id) = module.parent_id {
+            if self.repo.select_by_id(parent_id).await?.is_none() {
+                return Err(AppError::BusinessError("restore the parent module first".into()));
+            }
+        }
+        let deleted_at = module.deleted_at.unwrap_or_default();
+        let project_id = module.project_id;
+        let path = module.path.unwrap_or(module.name);
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                let ids: Vec<u64> = self
+                    .repo
+                    .find_subtree_with(conn, project_id, &path)
+                    .await?
+                    .into_iter()
+                    .filter(|m| m.deleted_at == Some(deleted_at))
+                    .map(|m| m.id)
+                    .collect();
+                for module_id in ids {
+                    self.repo.restore_by_id_with(conn, module_id).await?;
+                    self.phrase_repo.restore_by_module(conn, module_id, deleted_at).await?;
+                }
+                self.translation_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                self.screenshot_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                Ok(())
+            })
+        })
+        .await?;
+        self.invalidate_stats(project_id).await;
+        Ok(true)
+    }
+
+    /// 项目的模块树，每个节点带词条数和各目标语言的进度（均包含子模块）
+    pub async fn select_tree(&self, project_id: u64) -> Result<Vec<ModuleTreeNodeVo>, AppError> {
+        self.project_repo
+            .select_by_id(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("project not found".into()))?;
+        let languages: Vec<String> = self
+            .project_language_repo
+            .find_by_project(project_id)
+            .await?
+            .into_iter()
+            .map(|l| l.language)
+            .collect();
+        let phrase_counts: HashMap<Option<u64>, i64> = self
+            .phrase_repo
+            .count_by_module(project_id)
+            .await?
+            .into_iter()
+            .collect();
+        // (模块ID, 语言) → (已翻译, 已审核)
+        let progress: HashMap<(Option<u64>, String), (i64, i64)> = self
+            .translation_repo
+            .count_progress_by_module(project_id)
+            .await?
+            .into_iter()
+            .map(|(module_id, language, translated, reviewed)| {
+                ((module_id, language), (translated, reviewed))
+            })
+            .collect();
+
+        let nodes = self
+            .repo
+            .find_by_project_id(project_id)
+            .await?
+            .into_iter()
+            .map(|module| {
+                let id = Some(module.id);
+                ModuleTreeNodeVo {
+                    id: module.id,
+                    parent_id: module.parent_id,
+                    path: module.path.unwrap_or_else(|| module.name.clone()),
+                    name: module.name,
+                    phrase_count: phrase_counts.get(&id).copied().unwrap_or_default() as u64,
+                    total_phrase_count: 0,
+                    progress: languages
+                        .iter()
+                        .map(|language| {
+                            let (translated, reviewed) = progress
+                                .get(&(id, language.clone()))
+                                .copied()
+                                .unwrap_or_default();
+                            ModuleProgressVo {
+                                language: language.clone(),
+                                translated: translated as u64,
+                                reviewed: reviewed as u64,
+                                percent: 0.0,
+                            }
+                        })
+                        .collect(),
+                    children: vec![],
+                }
+            })
+            .collect();
+        Ok(module_tree::build_tree(nodes))
     }
 
     pub async fn delete_by_ids(&self, ids: &[u64]) -> Result<u64, AppError> {
         self.repo.delete_by_ids(ids).await
     }
+
+    /// 模块下的词条移入或移出回收站后使项目统计缓存失效，失败只记录日志
+    async fn invalidate_stats(&self, project_id: u64) {
+        if let Err(e) = self.stats_cache.invalidate(project_id).await {
+            warn!("项目统计缓存失效失败: {}", e);
+        }
+    }
+}
+
+/// 名称是路径的一段，不能为空或包含 "/"
+fn check_name(name: &str) -> Result<(), AppError> {
+    if name.is_empty() || name.contains('/') {
+        return Err(AppError::BadRequest("Module name cannot be empty or contain '/'".into()));
+    }
+    Ok(())
+}
+
+fn page_query(req: &ModuleQuery, scope: Option<&ApiTokenScope>) -> PageQuery {
+    let mut filter = Filter::new().eq_opt("project_id", req.project_id);
+    if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+        filter = filter.is_in("project_id", project_ids);
+    }
+    let filter = match req.name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => filter.like("name", name),
+        None => filter,
+    };
+    PageQuery::new(req.page, req.size)
+        .filter(filter)
+        .sort(Sort::parse_list(req.sort.as_deref()))
 }
 
 impl BaseService<Module> for ModuleService {
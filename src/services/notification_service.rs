@@ -0,0 +1,245 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use serde_json::json;
+use sqlx::{MySqlConnection, MySqlPool};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    config::SETTINGS,
+    constants::NOTIFICATION_DIGEST_CHECK_SECONDS,
+    dtos::notification::{NotificationQuery, NotificationVo, PreferenceVo, UpdatePreferenceDto},
+    errors::AppError,
+    models::{notification::Notification, notification_preference::NotificationPreference},
+    repositories::{
+        query::{Filter, PageQuery, Sort},
+        BaseRepository, NotificationPreferenceRepository, NotificationRepository, UserRepository,
+    },
+    services::BaseService,
+    utils::{
+        mail::{self, Email, MailSender},
+        notification,
+    },
+};
+
+pub struct NotificationService {
+    repo: Arc<NotificationRepository>,
+    preference_repo: NotificationPreferenceRepository,
+    user_repo: UserRepository,
+    mailer: Arc<dyn MailSender>,
+}
+
+impl NotificationService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: Arc::new(NotificationRepository::new(pool.clone())),
+            preference_repo: NotificationPreferenceRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool),
+            mailer: mail::build_sender(&SETTINGS.mail).expect("failed to build mail sender"),
+        }
+    }
+
+    /// 用户的通知，最新的在前
+    pub async fn select_page(
+        &self,
+        user_id: u64,
+        req: &NotificationQuery,
+    ) -> Result<(Vec<NotificationVo>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq("user_id", user_id)
+            .eq_opt("kind", req.kind.map(|kind| kind.to_string()));
+        if req.unread_only {
+            filter = filter.eq("is_read", false);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::desc("id")]);
+        let (notifications, total) = self.repo.select_page(&query).await?;
+        Ok((notifications.iter().map(NotificationVo::from).collect(), total))
+    }
+
+    pub async fn unread_count(&self, user_id: u64) -> Result<i64, AppError> {
+        self.repo.count_unread(user_id).await
+    }
+
+    /// 标记已读，只能操作自己的通知
+    pub async fn mark_read(&self, user_id: u64, id: u64) -> Result<bool, AppError> {
+        let notification = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .filter(|notification| notification.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Notification not found".into()))?;
+        if notification.is_read {
+            return Ok(false);
+        }
+        self.repo.mark_read(user_id, id, Local::now().naive_local()).await
+    }
+
+    /// 全部标记已读，返回标记条数
+    pub async fn mark_all_read(&self, user_id: u64) -> Result<u64, AppError> {
+        self.repo.mark_all_read(user_id, Local::now().naive_local()).await
+    }
+
+    /// 通知偏好，未设置时返回默认值
+    pub async fn select_preference(&self, user_id: u64) -> Result<PreferenceVo, AppError> {
+        Ok(self
+            .preference_repo
+            .select_by_id(user_id)
+            .await?
+            .map(|preference| PreferenceVo::from(&preference))
+            .unwrap_or_default())
+    }
+
+    pub async fn update_preference(
+        &self,
+        user_id: u64,
+        dto: &UpdatePreferenceDto,
+    ) -> Result<PreferenceVo, AppError> {
+        let mut preference = self.select_preference(user_id).await?;
+        if let Some(muted_kinds) = &dto.muted_kinds {
+            preference.muted_kinds = Vec::new();
+            for kind in muted_kinds {
+                if !preference.muted_kinds.contains(kind) {
+                    preference.muted_kinds.push(*kind);
+                }
+            }
+        }
+        if let Some(email_digest) = dto.email_digest {
+            preference.email_digest = email_digest;
+        }
+        let now = Local::now().naive_local();
+        self.preference_repo
+            .upsert(&NotificationPreference {
+                user_id,
+                muted_kinds: Some(json!(preference.muted_kinds)),
+                email_digest: preference.email_digest,
+                crt_at: now,
+                upt_at: now,
+            })
+            .await?;
+        Ok(preference)
+    }
+
+    /// 每天在配置的时间发送通知摘要邮件
+    pub async fn run_digest_schedule(self) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(NOTIFICATION_DIGEST_CHECK_SECONDS));
+        let mut last_sent: Option<NaiveDate> = None;
+        loop {
+            interval.tick().await;
+            let now = Local::now().naive_local();
+            if !notification::digest_due(now, last_sent, SETTINGS.mail.digest_hour) {
+                continue;
+            }
+            last_sent = Some(now.date());
+            match self.send_digests().await {
+                Ok(count) => info!("通知摘要发送完成，共 {} 封", count),
+                Err(e) => error!("通知摘要发送失败: {}", e),
+            }
+        }
+    }
+
+    /// 给有未读通知的用户发送摘要，返回发送封数；单个用户失败不影响其他用户
+    pub async fn send_digests(&self) -> Result<usize, AppError> {
+        let mut sent = 0;
+        for user_id in self.repo.find_digest_recipients().await? {
+            match self.send_digest(user_id).await {
+                Ok(true) => sent += 1,
+                Ok(false) => {}
+                Err(e) => error!("用户 {} 的通知摘要发送失败: {}", user_id, e),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// 领取用户尚未进入摘要的未读通知并发送；关闭了摘要或没有邮箱的用户只领取不发送
+    async fn send_digest(&self, user_id: u64) -> Result<bool, AppError> {
+        let digest_id = Uuid::new_v4().to_string();
+        if self.repo.claim_for_digest(user_id, &digest_id).await? == 0 {
+            return Ok(false);
+        }
+        let enabled = self
+            .preference_repo
+            .select_by_id(user_id)
+            .await?
+            .is_none_or(|preference| preference.email_digest);
+        if !enabled {
+            return Ok(false);
+        }
+        let Some(user) = self.user_repo.select_by_id(user_id).await? else {
+            return Ok(false);
+        };
+        let Some(to) = user.email.filter(|email| !email.is_empty()) else {
+            return Ok(false);
+        };
+        let notifications = self.repo.find_by_digest(&digest_id).await?;
+        let username = user.username.unwrap_or_else(|| to.clone());
+        let (subject, body) = notification::render_digest(&username, &notifications);
+        if let Err(e) = self.mailer.send(&Email { to, subject, body }).await {
+            self.repo.release_digest(&digest_id).await?;
+            return Err(e);
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl BaseService<Notification> for NotificationService {
+    type Repository = NotificationRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
+
+/// 在业务事务中写入通知，与业务数据一起提交或回滚
+pub struct Notifier {
+    repo: NotificationRepository,
+    preference_repo: NotificationPreferenceRepository,
+}
+
+impl Notifier {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: NotificationRepository::new(pool.clone()),
+            preference_repo: NotificationPreferenceRepository::new(pool),
+        }
+    }
+
+    /// 按模板给每个接收人写入一条通知，跳过触发人自己和关闭了该类型的用户，返回写入条数
+    pub async fn notify_with(
+        &self,
+        conn: &mut MySqlConnection,
+        recipients: &[u64],
+        template: &Notification,
+    ) -> Result<usize, AppError> {
+        let mut seen = HashSet::new();
+        let recipients: Vec<u64> = recipients
+            .iter()
+            .copied()
+            .filter(|user_id| Some(*user_id) != template.actor_id && seen.insert(*user_id))
+            .collect();
+        if recipients.is_empty() {
+            return Ok(0);
+        }
+        let muted: HashSet<u64> = self
+            .preference_repo
+            .find_by_users_with(conn, &recipients)
+            .await?
+            .into_iter()
+            .filter(|preference| preference.is_muted(template.kind))
+            .map(|preference| preference.user_id)
+            .collect();
+        let mut count = 0;
+        for user_id in recipients.into_iter().filter(|user_id| !muted.contains(user_id)) {
+            let mut notification = template.clone();
+            notification.user_id = user_id;
+            self.repo.insert_with(conn, &notification).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
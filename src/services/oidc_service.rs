@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    config::SETTINGS,
+    constants::{AUDIT_ACTION_SSO_LOGIN, AUDIT_TARGET_USER, OIDC_STATE_PREFIX},
+    errors::AppError,
+    models::{operation_log::OperationLog, user::User, user_identity::UserIdentity},
+    repositories::{
+        BaseRepository, OperationLogRepository, UserIdentityRepository, UserRepository,
+    },
+    services::BaseService,
+    utils::{
+        oidc::{self, IdTokenClaims, OidcClient},
+        password,
+    },
+};
+
+const OIDC_STATE_TTL_SECONDS: u64 = 600;
+
+/// 授权请求中需要在回调时取回的参数
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OidcAuthorizeVo {
+    pub authorization_url: String,
+    pub state: String,
+}
+
+pub struct OidcService {
+    client: Option<OidcClient>,
+    auto_provision: bool,
+    redis_client: redis::Client,
+    user_repo: UserRepository,
+    identity_repo: UserIdentityRepository,
+    log_repo: OperationLogRepository,
+}
+
+impl OidcService {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
+        Self {
+            client: SETTINGS.oidc.as_ref().map(OidcClient::from_settings),
+            auto_provision: SETTINGS.oidc.as_ref().map_or(false, |o| o.auto_provision),
+            redis_client,
+            user_repo: UserRepository::new(pool.clone()),
+            identity_repo: UserIdentityRepository::new(pool.clone()),
+            log_repo: OperationLogRepository::new(pool),
+        }
+    }
+
+    fn client(&self) -> Result<&OidcClient, AppError> {
+        self.client
+            .as_ref()
+            .ok_or_else(|| AppError::BadRequest("Single sign-on is not enabled".into()))
+    }
+
+    /// 发起登录：生成 state、nonce 和 PKCE 参数并暂存到 Redis
+    pub async fn authorize(&self) -> Result<OidcAuthorizeVo, AppError> {
+        let client = self.client()?;
+        let state = oidc::random_token();
+        let pending = PendingLogin {
+            nonce: oidc::random_token(),
+            code_verifier: oidc::random_token(),
+        };
+        let authorization_url = client
+            .authorization_url(
+                &state,
+                &pending.nonce,
+                &oidc::pkce_challenge(&pending.code_verifier),
+            )
+            .await?;
+
+        let payload = serde_json::to_string(&pending)
+            .map_err(|e| AppError::SerdeError(e.to_string()))?;
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        conn.set_ex::<_, _, ()>(
+            format!("{}{}", OIDC_STATE_PREFIX, state),
+            payload,
+            OIDC_STATE_TTL_SECONDS,
+        )
+        .await?;
+
+        Ok(OidcAuthorizeVo {
+            authorization_url,
+            state,
+        })
+    }
+
+    /// 处理回调：校验 state，换取并校验 ID Token，然后关联或创建平台用户
+    pub async fn callback(&self, code: &str, state: &str, ip: &str) -> Result<User, AppError> {
+        let client = self.client()?;
+
+        let key = format!("{}{}", OIDC_STATE_PREFIX, state);
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let payload: Option<String> = conn.get(&key).await?;
+        // state 只能使用一次
+        conn.del::<_, ()>(&key).await?;
+        let pending: PendingLogin = payload
+            .and_then(|p| serde_json::from_str(&p).ok())
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired login state".into()))?;
+
+        let id_token = client.exchange_code(code, &pending.code_verifier).await?;
+        let claims = client.validate_id_token(&id_token, &pending.nonce).await?;
+
+        let user = self.resolve_user(client.issuer(), &claims).await?;
+        if !user.status {
+            return Err(AppError::Auth("User is disabled".into()));
+        }
+        self.user_repo.record_login_success(user.id).await?;
+        let log = OperationLog::new(
+            user.id,
+            AUDIT_ACTION_SSO_LOGIN,
+            AUDIT_TARGET_USER,
+            user.id.to_string(),
+            Some(json!({ "provider": client.issuer(), "subject": claims.sub })),
+            ip,
+        );
+        self.log_repo.insert(&log).await?;
+        Ok(user)
+    }
+
+    /// 按 (issuer, sub) 查找已关联用户；否则按已验证邮箱关联；都没有时自动创建
+    async fn resolve_user(&self, issuer: &str, claims: &IdTokenClaims) -> Result<User, AppError> {
+        if let Some(identity) = self
+            .identity_repo
+            .find_by_provider_and_subject(issuer, &claims.sub)
+            .await?
+        {
+            return self
+                .user_repo
+                .select_by_id(identity.user_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("User not found".into()));
+        }
+
+        let email = claims
+            .email
+            .as_deref()
+            .filter(|_| claims.email_verified == Some(true))
+            .ok_or_else(|| AppError::Auth("Identity provider did not return a verified email".into()))?;
+
+        let existing = self.user_repo.find_by_email(email).await?;
+        if existing.is_none() && !self.auto_provision {
+            return Err(AppError::Auth("No account is linked to this identity".into()));
+        }
+
+        // 创建用户与关联身份在同一事务中，避免留下没有身份关联的用户
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                let user = match existing {
+                    Some(user) => user,
+                    None => self.provision_user(conn, email, claims).await?,
+                };
+                let identity = UserIdentity {
+                    id: 0,
+                    user_id: user.id,
+                    provider: issuer.to_string(),
+                    subject: claims.sub.clone(),
+                    email: Some(email.to_string()),
+                    crt_by: "oidc".to_string(),
+                    crt_at: Utc::now(),
+                    upt_by: None,
+                    upt_at: Utc::now(),
+                };
+                self.identity_repo.insert_with(conn, &identity).await?;
+                Ok(user)
+            })
+        })
+        .await
+    }
+
+    async fn provision_user(
+        &self,
+        conn: &mut MySqlConnection,
+        email: &str,
+        claims: &IdTokenClaims,
+    ) -> Result<User, AppError> {
+        let mut user = User {
+            id: 0,
+            tenant_id: 0,
+            username: None,
+            // 单点登录用户没有本地密码，写入随机哈希使密码登录不可用
+            password: password::hash_password(&oidc::random_token())?,
+            email: Some(email.to_string()),
+            phone: None,
+            realname: None,
+            id_card: None,
+            nickname: claims.name.clone().or(claims.preferred_username.clone()),
+            avatar: None,
+            gender: None,
+            birthday: None,
+            status: true,
+            last_login: None,
+            failed_attempts: 0,
+            locked_until: None,
+            totp_secret: None,
+            totp_enabled: false,
+            totp_last_step: None,
+            crt_by: "oidc".to_string(),
+            crt_at: Utc::now(),
+            upt_by: None,
+            upt_at: Utc::now(),
+        };
+        user.id = self.user_repo.insert_with(conn, &user).await?;
+        Ok(user)
+    }
+}
+
+impl BaseService<UserIdentity> for OidcService {
+    type Repository = UserIdentityRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.identity_repo
+    }
+}
@@ -1,30 +1,699 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::{Local, NaiveDateTime, Utc};
+use serde_json::json;
+use sqlx::{MySql, MySqlConnection, MySqlPool, Transaction};
+use tracing::warn;
 
 use crate::{
-    dtos::phrase::{CreatePhraseDto, UpdatePhraseDto}, errors::AppError, models::phrase::Phrase, repositories::{base_repo::BaseRepository, phrase_repo::PhraseRepository}, services::BaseService
+    constants::{
+        NOTIFICATION_CONTENT_MAX_CHARS, NOTIFICATION_TARGET_PHRASE, PHRASE_BATCH_MAX_ITEMS, WEBHOOK_EVENT_PHRASE_CREATED, WEBHOOK_EVENT_PHRASE_DELETED,
+        WEBHOOK_EVENT_PHRASE_UPDATED,
+    },
+    dtos::{
+        common::{BatchItemResult, BatchResultVo},
+        key_rule::{BatchRenamePhraseDto, RenamePhraseItem},
+        phrase::{
+            BatchCreatePhraseDto, BatchDeletePhraseDto, BatchUpdatePhraseDto,
+            BatchUpdatePhraseItem, CreatePhraseDto, PhraseQuery, PhraseTrashQuery,
+            UpdatePhraseDto,
+        },
+    },
+    errors::AppError,
+    models::{enums::NotificationKind, notification::Notification, phrase::Phrase},
+    repositories::{
+        base_repo::BaseRepository,
+        phrase_repo::PhraseRepository,
+        query::{self, Filter, FilterValue, PageQuery, Sort},
+        ModuleRepository, PhraseSourceRepository, PhraseTypeRepository, ProjectRepository,
+        ScreenshotRepository, TranslationHistoryRepository, TranslationRepository,
+    },
+    services::{
+        key_rule_service::KeyGuard, notification_service::Notifier, task_service::TaskTracker,
+        webhook_service::WebhookDispatcher, BaseService,
+    },
+    utils::{
+        api_token::{check_scope, ApiTokenScope},
+        module_tree::PathFilter,
+        stats_cache::StatsCache,
+        text,
+    },
 };
 
 pub struct PhraseService {
     repo: Arc<PhraseRepository>,
+    project_repo: ProjectRepository,
+    module_repo: ModuleRepository,
+    type_repo: PhraseTypeRepository,
+    translation_repo: TranslationRepository,
+    screenshot_repo: ScreenshotRepository,
+    source_repo: PhraseSourceRepository,
+    history_repo: TranslationHistoryRepository,
+    stats_cache: StatsCache,
+    webhooks: WebhookDispatcher,
+    notifier: Notifier,
+    tasks: TaskTracker,
+    key_guard: KeyGuard,
 }
 
 impl PhraseService {
-    pub fn new(pool: Arc<MySqlPool>) -> Self {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
         Self {
-            repo: Arc::new(PhraseRepository::new(pool)),
+            repo: Arc::new(PhraseRepository::new(pool.clone())),
+            project_repo: ProjectRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool.clone()),
+            type_repo: PhraseTypeRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            screenshot_repo: ScreenshotRepository::new(pool.clone()),
+            source_repo: PhraseSourceRepository::new(pool.clone()),
+            history_repo: TranslationHistoryRepository::new(pool.clone()),
+            stats_cache: StatsCache::new(redis_client),
+            webhooks: WebhookDispatcher::new(pool.clone()),
+            notifier: Notifier::new(pool.clone()),
+            tasks: TaskTracker::new(pool.clone()),
+            key_guard: KeyGuard::new(pool),
+        }
+    }
+
+    /// 校验令牌范围能否访问词条所属的项目，JWT 认证或词条不存在时不校验
+    pub async fn check_access(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.repo.find_project_id(id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
+
+    /// 多维度筛选的分页查询，令牌限定了项目时只查询可访问的项目
+    pub async fn select_by_page(
+        &self,
+        req: &PhraseQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Phrase>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq_opt("project_id", req.project_id)
+            .eq_opt("module_id", req.module_id)
+            .eq_opt("type_id", req.type_id)
+            .json_contains_any("tags", &query::parse_csv(req.tags.as_deref()))
+            .json_contains_any("platforms", &query::parse_csv(req.platforms.as_deref()))
+            .search_opt(&["key", "base_content"], req.search_key.as_deref())
+            .eq_opt("crt_by", req.crt_by.clone());
+        if let Some(updated_since) = req.updated_since {
+            filter = filter.range("upt_at", Some(updated_since), None::<NaiveDateTime>);
+        }
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            filter = filter.is_in("project_id", project_ids);
+        }
+        if let Some(module) = req.module.as_deref().and_then(PathFilter::parse) {
+            let column = format!("{}.module_id", self.repo.get_table_name());
+            let (condition, params) = self.module_repo.path_condition(&column, &module);
+            filter = filter.sql(&condition, params);
+        }
+        filter = self.translation_filter(filter, req)?;
+
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        self.repo.select_page(&query).await
+    }
+
+    /// 按目标语言的翻译情况过滤：未翻译或处于指定状态
+    fn translation_filter(&self, filter: Filter, req: &PhraseQuery) -> Result<Filter, AppError> {
+        if !req.untranslated && req.status.is_none() {
+            return Ok(filter);
+        }
+        let language = req
+            .language
+            .clone()
+            .ok_or_else(|| AppError::BadRequest("language is required to filter by translation".into()))?;
+        let exists = format!(
+            "SELECT 1 FROM {} t WHERE t.phrase_id = {}.id AND t.language = ? AND t.deleted_at IS NULL",
+            self.translation_repo.get_table_name(),
+            self.repo.get_table_name()
+        );
+
+        let filter = if req.untranslated {
+            filter.sql(&format!("NOT EXISTS ({})", exists), vec![language.clone().into()])
+        } else {
+            filter
+        };
+        match req.status.as_deref() {
+            Some(status @ ("pending" | "reviewed" | "published" | "outdated")) => Ok(filter.sql(
+                &format!("EXISTS ({} AND t.status = ?)", exists),
+                vec![FilterValue::from(language), FilterValue::from(status)],
+            )),
+            Some(status) => Err(AppError::BadRequest(format!("Unsupported status: {}", status))),
+            None => Ok(filter),
         }
     }
 
     pub async fn insert(&self, phrase: &CreatePhraseDto) -> Result<u64, AppError> {
-        self.repo.insert(&Phrase::from(phrase)).await
+        let mut entity = Phrase::from(phrase);
+        let project_id = entity.project_id;
+        let id = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    self.check_new_key(conn, &entity).await?;
+                    entity.id = self.repo.insert_with(conn, &entity).await?;
+                    self.notify(conn, &entity, WEBHOOK_EVENT_PHRASE_CREATED).await?;
+                    Ok(entity.id)
+                })
+            })
+            .await?;
+        self.invalidate_stats(project_id).await;
+        Ok(id)
+    }
+
+    /// 更新词条，携带版本号时与当前版本不一致将返回冲突
+    ///
+    /// 修改原文时，除非标记为微调，已有翻译都会被标记为过期
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        phrase: &UpdatePhraseDto,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        let mut exist = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        let project_id = exist.project_id;
+        let (updated, outdated) = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    if let Some(key) = phrase.key.as_ref().filter(|key| **key != exist.key) {
+                        self.key_guard
+                            .check_with(conn, exist.project_id, exist.module_id, key)
+                            .await?;
+                        self.check_key_unique(conn, exist.project_id, key, id).await?;
+                        exist.key = key.clone();
+                    }
+                    let mut outdated = false;
+                    if let Some(source_text) = &phrase.source_text {
+                        outdated = self
+                            .change_source(conn, &mut exist, source_text, phrase.minor_edit, operator)
+                            .await?;
+                    }
+                    if let Some(context) = &phrase.context {
+                        exist.context = Some(context.clone());
+                    }
+                    exist.version = phrase.version.unwrap_or(exist.version);
+                    exist.upt_by = Some(operator.to_string());
+                    exist.upt_at = Local::now().naive_local();
+                    let updated = self.repo.update_by_id_with(conn, id, &exist).await?;
+                    if outdated {
+                        self.mark_translations_outdated(conn, &exist, operator_id, operator)
+                            .await?;
+                    }
+                    self.notify_updated(conn, id).await?;
+                    Ok((updated, outdated))
+                })
+            })
+            .await?;
+        if outdated {
+            self.invalidate_stats(project_id).await;
+        }
+        Ok(updated)
+    }
+
+    /// 修改原文：非微调时归档旧原文并递增原文版本，返回是否需要将翻译标记为过期
+    async fn change_source(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase: &mut Phrase,
+        source_text: &str,
+        minor_edit: bool,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        match phrase.change_source(source_text, minor_edit, operator) {
+            Some(source) => {
+                self.source_repo.insert_with(conn, &source).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 将词条下的翻译标记为过期并写入历史，同时通知译者
+    async fn mark_translations_outdated(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase: &Phrase,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<(), AppError> {
+        let translators = self
+            .translation_repo
+            .find_translators_by_phrase_with(conn, phrase.id)
+            .await?;
+        self.history_repo
+            .insert_outdated_by_phrase(conn, phrase.id, operator_id, operator)
+            .await?;
+        self.translation_repo.mark_outdated_by_phrase(conn, phrase.id).await?;
+        self.tasks.refresh_by_phrase_with(conn, phrase.id, None).await?;
+
+        let mut template = Notification::new(
+            NotificationKind::Outdated,
+            format!("Source text of {} changed, your translations need review", phrase.key),
+            NOTIFICATION_TARGET_PHRASE,
+            phrase.id,
+        );
+        template.content = Some(text::truncate(&phrase.base_content, NOTIFICATION_CONTENT_MAX_CHARS));
+        template.project_id = Some(phrase.project_id);
+        template.actor_id = Some(operator_id);
+        template.actor = Some(operator.to_string());
+        self.notifier.notify_with(conn, &translators, &template).await?;
+        Ok(())
+    }
+
+    /// 新词条的模块须属于同一项目，标识符须符合项目规则
+    async fn check_new_key(&self, conn: &mut MySqlConnection, phrase: &Phrase) -> Result<(), AppError> {
+        if let Some(module_id) = phrase.module_id {
+            self.check_module(conn, module_id, phrase.project_id).await?;
+        }
+        self.key_guard
+            .check_with(conn, phrase.project_id, phrase.module_id, &phrase.key)
+            .await?;
+        self.check_key_unique(conn, phrase.project_id, &phrase.key, 0).await
+    }
+
+    /// 标识符已被占用时返回 BusinessError，占用者在回收站中时提示先恢复或彻底删除
+    async fn check_key_unique(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        key: &str,
+        exclude_id: u64,
+    ) -> Result<(), AppError> {
+        match self.repo.find_by_key_with(conn, project_id, key, exclude_id).await? {
+            Some(existing) if existing.deleted_at.is_some() => Err(AppError::BusinessError(
+                format!("Key {} exists in trash, restore or purge it", key),
+            )),
+            Some(_) => Err(AppError::BusinessError(format!("Key {} already exists", key))),
+            None => Ok(()),
+        }
+    }
+
+    async fn check_module(
+        &self,
+        conn: &mut MySqlConnection,
+        module_id: u64,
+        project_id: u64,
+    ) -> Result<(), AppError> {
+        self.module_repo
+            .select_by_id_with(conn, module_id)
+            .await?
+            .filter(|module| module.project_id == project_id)
+            .ok_or_else(|| AppError::BadRequest("Module not found in this project".into()))?;
+        Ok(())
+    }
+
+    /// 在事务中写入词条事件的 Webhook 投递记录
+    async fn notify(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase: &Phrase,
+        event: &str,
+    ) -> Result<(), AppError> {
+        self.webhooks
+            .dispatch_with(conn, phrase.project_id, event, json!({ "phrase": phrase }))
+            .await?;
+        Ok(())
+    }
+
+    /// 通知词条已修改，携带修改后的数据
+    async fn notify_updated(&self, conn: &mut MySqlConnection, id: u64) -> Result<(), AppError> {
+        if let Some(phrase) = self.repo.select_by_id_with(conn, id).await? {
+            self.notify(conn, &phrase, WEBHOOK_EVENT_PHRASE_UPDATED).await?;
+        }
+        Ok(())
+    }
+
+    /// 词条增删、移动或翻译状态变化后使项目统计缓存失效，失败只记录日志
+    async fn invalidate_stats(&self, project_id: u64) {
+        if let Err(e) = self.stats_cache.invalidate(project_id).await {
+            warn!("项目统计缓存失效失败: {}", e);
+        }
+    }
+
+    async fn invalidate_projects(&self, project_ids: HashSet<u64>) {
+        for project_id in project_ids {
+            self.invalidate_stats(project_id).await;
+        }
+    }
+
+    /// 删除词条，翻译和截图一起移入回收站
+    pub async fn soft_delete(&self, id: u64) -> Result<bool, AppError> {
+        let deleted_at = Utc::now().naive_utc();
+        let project_id = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    let Some(phrase) = self.repo.select_by_id_with(conn, id).await? else {
+                        return Ok(None);
+                    };
+                    if self.repo.soft_delete_by_ids_with(conn, &[id], deleted_at).await? == 0 {
+                        return Ok(None);
+                    }
+                    self.translation_repo
+                        .soft_delete_by_deleted_phrases(conn, deleted_at)
+                        .await?;
+                    self.screenshot_repo
+                        .soft_delete_by_deleted_phrases(conn, deleted_at)
+                        .await?;
+                    self.tasks.refresh_by_phrase_with(conn, id, None).await?;
+                    self.notify(conn, &phrase, WEBHOOK_EVENT_PHRASE_DELETED).await?;
+                    Ok(Some(phrase.project_id))
+                })
+            })
+            .await?;
+        if let Some(project_id) = project_id {
+            self.invalidate_stats(project_id).await;
+        }
+        Ok(project_id.is_some())
+    }
+
+    /// 从回收站恢复词条，所属项目和模块必须未被删除
+    pub async fn restore(&self, id: u64) -> Result<bool, AppError> {
+        let phrase = self
+            .repo
+            .select_deleted_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found in trash".into()))?;
+        if self.project_repo.select_by_id(phrase.project_id).await?.is_none() {
+            return Err(AppError::BusinessError("restore the project first".into()));
+        }
+        if let Some(module_id) = phrase.module_id {
+            if self.module_repo.select_by_id(module_id).await?.is_none() {
+                return Err(AppError::BusinessError("restore the module first".into()));
+            }
+        }
+        let deleted_at = phrase.deleted_at.unwrap_or_default();
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.repo.restore_by_id_with(conn, id).await?;
+                self.translation_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                self.screenshot_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                self.tasks.refresh_by_phrase_with(conn, id, None).await?;
+                Ok(())
+            })
+        })
+        .await?;
+        self.invalidate_stats(phrase.project_id).await;
+        Ok(true)
+    }
+
+    /// 批量创建词条，任一条失败时整批回滚
+    pub async fn batch_insert(
+        &self,
+        dto: &BatchCreatePhraseDto,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<BatchResultVo, AppError> {
+        check_batch_size(dto.items.len())?;
+        let mut tx = self.repo.begin().await?;
+        let mut results = Vec::with_capacity(dto.items.len());
+        for (index, item) in dto.items.iter().enumerate() {
+            let result = self.batch_insert_one(&mut tx, item, scope).await;
+            results.push(BatchItemResult::new(index, result));
+        }
+        let result = finish_batch(tx, results).await?;
+        if result.committed {
+            let projects: HashSet<u64> = dto.items.iter().map(|item| item.project_id).collect();
+            self.invalidate_projects(projects).await;
+        }
+        Ok(result)
+    }
+
+    /// 批量修改词条：内容、所属模块、类型、平台和标签，任一条失败时整批回滚
+    pub async fn batch_update(
+        &self,
+        dto: &BatchUpdatePhraseDto,
+        scope: Option<&ApiTokenScope>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<BatchResultVo, AppError> {
+        check_batch_size(dto.items.len())?;
+        let mut tx = self.repo.begin().await?;
+        let mut results = Vec::with_capacity(dto.items.len());
+        // 有词条移动模块或翻译被标记为过期的项目
+        let mut changed_projects = HashSet::new();
+        for (index, item) in dto.items.iter().enumerate() {
+            let result = self
+                .batch_update_one(&mut tx, item, scope, operator_id, operator)
+                .await
+                .map(|(id, changed_project)| {
+                    changed_projects.extend(changed_project);
+                    id
+                });
+            results.push(BatchItemResult::new(index, result));
+        }
+        let result = finish_batch(tx, results).await?;
+        if result.committed {
+            self.invalidate_projects(changed_projects).await;
+        }
+        Ok(result)
+    }
+
+    /// 批量重命名词条，用于按检查结果修正标识符，任一条失败时整批回滚
+    pub async fn batch_rename(
+        &self,
+        dto: &BatchRenamePhraseDto,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<BatchResultVo, AppError> {
+        check_batch_size(dto.items.len())?;
+        let mut tx = self.repo.begin().await?;
+        let mut results = Vec::with_capacity(dto.items.len());
+        for (index, item) in dto.items.iter().enumerate() {
+            let result = self.batch_rename_one(&mut tx, item, scope, operator).await;
+            results.push(BatchItemResult::new(index, result));
+        }
+        finish_batch(tx, results).await
+    }
+
+    /// 批量删除词条（移入回收站），任一条失败时整批回滚
+    pub async fn batch_delete(
+        &self,
+        dto: &BatchDeletePhraseDto,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<BatchResultVo, AppError> {
+        check_batch_size(dto.ids.len())?;
+        let deleted_at = Utc::now().naive_utc();
+        let mut tx = self.repo.begin().await?;
+        let mut results = Vec::with_capacity(dto.ids.len());
+        let mut projects = HashSet::new();
+        for (index, id) in dto.ids.iter().enumerate() {
+            let result = self
+                .batch_delete_one(&mut tx, *id, deleted_at, scope)
+                .await
+                .map(|project_id| {
+                    projects.insert(project_id);
+                    *id
+                });
+            results.push(BatchItemResult::new(index, result));
+        }
+        if results.iter().all(|r| r.success) {
+            self.translation_repo
+                .soft_delete_by_deleted_phrases(&mut tx, deleted_at)
+                .await?;
+            self.screenshot_repo
+                .soft_delete_by_deleted_phrases(&mut tx, deleted_at)
+                .await?;
+        }
+        let result = finish_batch(tx, results).await?;
+        if result.committed {
+            self.invalidate_projects(projects).await;
+        }
+        Ok(result)
+    }
+
+    async fn batch_insert_one(
+        &self,
+        conn: &mut MySqlConnection,
+        item: &CreatePhraseDto,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<u64, AppError> {
+        check_scope(scope, item.project_id)?;
+        if item.key.trim().is_empty() {
+            return Err(AppError::BadRequest("Phrase key is required".into()));
+        }
+        let mut phrase = Phrase::from(item);
+        self.check_new_key(conn, &phrase).await?;
+        phrase.id = self.repo.insert_with(conn, &phrase).await?;
+        self.notify(conn, &phrase, WEBHOOK_EVENT_PHRASE_CREATED).await?;
+        Ok(phrase.id)
     }
 
-    pub async fn update_by_id(&self, id: u64, phrase: &UpdatePhraseDto) -> Result<bool, AppError> {
-        self.repo.update_by_id(id, &Phrase::from(phrase)).await
+    /// 返回词条ID，以及移动了模块或翻译被标记为过期时所属的项目ID
+    async fn batch_update_one(
+        &self,
+        conn: &mut MySqlConnection,
+        item: &BatchUpdatePhraseItem,
+        scope: Option<&ApiTokenScope>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<(u64, Option<u64>), AppError> {
+        let mut phrase = self
+            .repo
+            .select_by_id_with(conn, item.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        check_scope(scope, phrase.project_id)?;
+
+        // 修改标识符或移动模块后按项目规则重新校验标识符
+        let key_changed = item.key.as_ref().is_some_and(|key| *key != phrase.key);
+        let module_changed = item.module_id.is_some_and(|module_id| Some(module_id) != phrase.module_id);
+        let recheck_key = key_changed || module_changed;
+        if let Some(key) = &item.key {
+            phrase.key = key.clone();
+        }
+        if key_changed {
+            self.check_key_unique(conn, phrase.project_id, &phrase.key, item.id)
+                .await?;
+        }
+        let mut outdated = false;
+        if let Some(source_text) = &item.source_text {
+            outdated = self
+                .change_source(conn, &mut phrase, source_text, item.minor_edit, operator)
+                .await?;
+        }
+        if let Some(context) = &item.context {
+            phrase.context = Some(context.clone());
+        }
+        if let Some(module_id) = item.module_id {
+            // 只能在同一项目内移动
+            self.check_module(conn, module_id, phrase.project_id).await?;
+            phrase.module_id = Some(module_id);
+        }
+        if recheck_key {
+            self.key_guard
+                .check_with(conn, phrase.project_id, phrase.module_id, &phrase.key)
+                .await?;
+        }
+        if let Some(type_id) = item.type_id {
+            self.type_repo
+                .select_by_id_with(conn, type_id)
+                .await?
+                .ok_or_else(|| AppError::BadRequest("Phrase type not found".into()))?;
+            phrase.type_id = type_id;
+        }
+        if let Some(platforms) = &item.platforms {
+            phrase.platforms = json!(platforms);
+        }
+        if !item.add_tags.is_empty() || !item.remove_tags.is_empty() {
+            phrase.update_tags(&item.add_tags, &item.remove_tags);
+        }
+        phrase.version = item.version.unwrap_or(phrase.version);
+        phrase.upt_by = Some(operator.to_string());
+        phrase.upt_at = Local::now().naive_local();
+        self.repo.update_by_id_with(conn, item.id, &phrase).await?;
+        if outdated {
+            self.mark_translations_outdated(conn, &phrase, operator_id, operator)
+                .await?;
+        }
+        self.notify_updated(conn, item.id).await?;
+        Ok((item.id, (outdated || module_changed).then_some(phrase.project_id)))
+    }
+
+    /// 只修改标识符，词条id不变，翻译和截图随词条保留
+    async fn batch_rename_one(
+        &self,
+        conn: &mut MySqlConnection,
+        item: &RenamePhraseItem,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<u64, AppError> {
+        let mut phrase = self
+            .repo
+            .select_by_id_with(conn, item.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        check_scope(scope, phrase.project_id)?;
+        if item.key == phrase.key {
+            return Ok(item.id);
+        }
+        self.key_guard
+            .check_with(conn, phrase.project_id, phrase.module_id, &item.key)
+            .await?;
+        self.check_key_unique(conn, phrase.project_id, &item.key, item.id)
+            .await?;
+        phrase.key = item.key.clone();
+        phrase.upt_by = Some(operator.to_string());
+        phrase.upt_at = Local::now().naive_local();
+        self.repo.update_by_id_with(conn, item.id, &phrase).await?;
+        self.notify_updated(conn, item.id).await?;
+        Ok(item.id)
+    }
+
+    /// 返回词条所属的项目ID
+    async fn batch_delete_one(
+        &self,
+        conn: &mut MySqlConnection,
+        id: u64,
+        deleted_at: NaiveDateTime,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<u64, AppError> {
+        let phrase = self
+            .repo
+            .select_by_id_with(conn, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        check_scope(scope, phrase.project_id)?;
+        self.repo.soft_delete_by_ids_with(conn, &[id], deleted_at).await?;
+        self.tasks.refresh_by_phrase_with(conn, id, None).await?;
+        self.notify(conn, &phrase, WEBHOOK_EVENT_PHRASE_DELETED).await?;
+        Ok(phrase.project_id)
+    }
+
+    /// 回收站中的词条
+    pub async fn select_trash_page(
+        &self,
+        req: &PhraseTrashQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Phrase>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq_opt("project_id", req.project_id)
+            .search_opt(&["key", "base_content"], req.search_key.as_deref());
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            filter = filter.is_in("project_id", project_ids);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        self.repo.select_deleted_page(&query).await
+    }
+}
+
+fn check_batch_size(len: usize) -> Result<(), AppError> {
+    if len == 0 || len > PHRASE_BATCH_MAX_ITEMS {
+        return Err(AppError::BadRequest(format!(
+            "Batch must contain 1 to {} items",
+            PHRASE_BATCH_MAX_ITEMS
+        )));
+    }
+    Ok(())
+}
+
+/// 全部成功时提交，否则回滚
+async fn finish_batch(
+    tx: Transaction<'static, MySql>,
+    results: Vec<BatchItemResult>,
+) -> Result<BatchResultVo, AppError> {
+    let result = BatchResultVo::new(results);
+    if result.committed {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
     }
+    Ok(result)
 }
 
 #[async_trait]
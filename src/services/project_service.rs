@@ -1,65 +1,81 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
 use sqlx::MySqlPool;
+use tracing::warn;
 
 use crate::dtos::common::PageRequest;
-use crate::repositories::UserRepository;
+use crate::models::project_language::{self, ProjectLanguage};
+use crate::repositories::{
+    LanguageRepository, ModuleRepository, PhraseRepository, ProjectLanguageRepository,
+    ScreenshotRepository, TranslationRepository, UserRepository,
+};
 use crate::{
-    dtos::project::{CreateProjectDto, ProjectVo, UpdateProjectDto},
+    dtos::project::{
+        AddProjectLanguageDto, CreateProjectDto, ProjectLanguageVo, ProjectVo,
+        UpdateLanguageFallbacksDto, UpdateProjectDto,
+    },
     errors::AppError,
     models::project::Project,
-    repositories::{base_repo::BaseRepository, project_repo::ProjectRepository},
+    repositories::{
+        base_repo::BaseRepository,
+        project_repo::ProjectRepository,
+        query::{Filter, PageQuery, Sort},
+    },
     services::BaseService,
+    utils::{api_token::ApiTokenScope, locale, stats_cache::StatsCache},
 };
 
 pub struct ProjectService {
     repo: Arc<ProjectRepository>,
     user_repo: Arc<UserRepository>,
+    module_repo: ModuleRepository,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    screenshot_repo: ScreenshotRepository,
+    language_repo: LanguageRepository,
+    project_language_repo: ProjectLanguageRepository,
+    stats_cache: StatsCache,
 }
 
 impl ProjectService {
-    pub fn new(pool: Arc<MySqlPool>) -> Self {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
         Self {
             repo: Arc::new(ProjectRepository::new(pool.clone())),
-            user_repo: Arc::new(UserRepository::new(pool)),
+            user_repo: Arc::new(UserRepository::new(pool.clone())),
+            module_repo: ModuleRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            screenshot_repo: ScreenshotRepository::new(pool.clone()),
+            language_repo: LanguageRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool),
+            stats_cache: StatsCache::new(redis_client),
         }
     }
 
     pub async fn select_by_page(
         &self,
         req: &PageRequest,
+        scope: Option<&ApiTokenScope>,
     ) -> Result<(Vec<ProjectVo>, i64), AppError> {
-        if let Some(search_key) = &req.search_key {
-            let projects = self
-                .repo
-                .select_page_by_key(req.page, req.size, search_key)
-                .await?;
-            let mut list = Vec::with_capacity(projects.0.len());
-            for project in &projects.0 {
-                let mut vo = ProjectVo::from(project);
-                if let Some(owner) = self.user_repo.select_by_id(project.owner_id).await? {
-                    if let Some(username) = owner.username {
-                        vo.owner = username;
-                    }
-                }
-                list.push(vo);
-            }
-            Ok((list, projects.1))
-        } else {
-            let projects = self.repo.select_by_page(req.page, req.size).await?;
-            let mut list = Vec::with_capacity(projects.0.len());
-            for project in &projects.0 {
-                let mut vo = ProjectVo::from(project);
-                if let Some(owner) = self.user_repo.select_by_id(project.owner_id).await? {
-                    if let Some(username) = owner.username {
-                        vo.owner = username;
-                    }
+        let filter = Filter::new().search_opt(&["code", "name"], req.search_key.as_deref());
+        let query = PageQuery::new(req.page, req.size)
+            .filter(scoped(filter, scope))
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        let projects = self.repo.select_page(&query).await?;
+        let mut list = Vec::with_capacity(projects.0.len());
+        for project in &projects.0 {
+            let mut vo = ProjectVo::from(project);
+            if let Some(owner) = self.user_repo.select_by_id(project.owner_id).await? {
+                if let Some(username) = owner.username {
+                    vo.owner = username;
                 }
-                list.push(vo);
             }
-            Ok((list, projects.1))
+            list.push(vo);
         }
+        Ok((list, projects.1))
     }
 
     pub async fn select_by_id(&self, id: u64) -> Result<ProjectVo, AppError> {
@@ -78,14 +94,12 @@ impl ProjectService {
     }
 
     pub async fn insert(&self, project: &CreateProjectDto) -> Result<u64, AppError> {
-        // 判断名称和编码唯一性
-        if let Some(_) = self.repo.find_by_name(&project.name, 0).await? {
-            return Err(AppError::BusinessError("repeat project name".into()));
-        }
-        if let Some(_) = self.repo.find_by_code(&project.code, 0).await? {
-            return Err(AppError::BusinessError("repeat project code".into()));
-        }
-        self.repo.insert(&Project::from(project)).await
+        // 判断名称和编码唯一性，回收站中的项目同样占用
+        check_unique(self.repo.find_by_name(&project.name, 0).await?, "name")?;
+        check_unique(self.repo.find_by_code(&project.code, 0).await?, "code")?;
+        let mut entity = Project::from(project);
+        entity.base_language = locale::canonicalize(&project.base_language).map_err(AppError::BadRequest)?;
+        self.repo.insert(&entity).await
     }
 
     pub async fn update_by_id(
@@ -93,19 +107,256 @@ impl ProjectService {
         id: u64,
         project: &UpdateProjectDto,
     ) -> Result<bool, AppError> {
-        // 判断名称和编码唯一性
+        // 判断名称和编码唯一性，回收站中的项目同样占用
         if let Some(code) = &project.code {
-            if let Some(_) = self.repo.find_by_code(code, id).await? {
-                return Err(AppError::BusinessError("repeat project code".into()));
-            }
+            check_unique(self.repo.find_by_code(code, id).await?, "code")?;
         }
         if let Some(name) = &project.name {
-            if let Some(_) = self.repo.find_by_name(name, id).await? {
-                return Err(AppError::BusinessError("repeat project name".into()));
-            }
+            check_unique(self.repo.find_by_name(name, id).await?, "name")?;
         }
         self.repo.update_by_id(id, &Project::from(project)).await
     }
+
+    /// 删除项目，模块、词条、翻译和截图一起移入回收站
+    pub async fn soft_delete(&self, id: u64) -> Result<bool, AppError> {
+        let deleted_at = Utc::now().naive_utc();
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                if self.repo.soft_delete_by_ids_with(conn, &[id], deleted_at).await? == 0 {
+                    return Ok(false);
+                }
+                self.module_repo.soft_delete_by_project(conn, id, deleted_at).await?;
+                self.phrase_repo.soft_delete_by_project(conn, id, deleted_at).await?;
+                self.translation_repo
+                    .soft_delete_by_deleted_phrases(conn, deleted_at)
+                    .await?;
+                self.screenshot_repo
+                    .soft_delete_by_deleted_phrases(conn, deleted_at)
+                    .await?;
+                Ok(true)
+            })
+        })
+        .await
+    }
+
+    /// 从回收站恢复项目，以及随项目一起删除的数据
+    pub async fn restore(&self, id: u64) -> Result<bool, AppError> {
+        let deleted_at = self
+            .repo
+            .select_deleted_by_id(id)
+            .await?
+            .and_then(|project| project.deleted_at)
+            .ok_or_else(|| AppError::NotFound("project not found in trash".into()))?
+            .naive_utc();
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.repo.restore_by_id_with(conn, id).await?;
+                self.module_repo.restore_by_project(conn, id, deleted_at).await?;
+                self.phrase_repo.restore_by_project(conn, id, deleted_at).await?;
+                self.translation_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                self.screenshot_repo
+                    .restore_by_restored_phrases(conn, deleted_at)
+                    .await?;
+                Ok(true)
+            })
+        })
+        .await
+    }
+
+    /// 回收站中的项目
+    pub async fn select_trash_page(
+        &self,
+        req: &PageRequest,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<ProjectVo>, i64), AppError> {
+        let filter = Filter::new().search_opt(&["code", "name"], req.search_key.as_deref());
+        let query = PageQuery::new(req.page, req.size)
+            .filter(scoped(filter, scope))
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        let projects = self.repo.select_deleted_page(&query).await?;
+        let list = projects.0.iter().map(ProjectVo::from).collect();
+        Ok((list, projects.1))
+    }
+
+    /// 项目的目标语言
+    pub async fn select_languages(&self, id: u64) -> Result<Vec<ProjectLanguageVo>, AppError> {
+        let project = self.live_project(id).await?;
+        let languages = self.project_language_repo.find_by_project(id).await?;
+        languages
+            .iter()
+            .map(|l| {
+                Ok(ProjectLanguageVo {
+                    language: l.language.clone(),
+                    is_default: l.is_default,
+                    fallbacks: l.fallback_languages(),
+                    chain: project_language::resolve_chain(
+                        &languages,
+                        &l.language,
+                        &project.base_language,
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    /// 添加目标语言，语言必须已启用且不能是项目的基础语言
+    pub async fn add_language(&self, id: u64, dto: &AddProjectLanguageDto) -> Result<bool, AppError> {
+        let project = self.live_project(id).await?;
+        let code = locale::canonicalize(&dto.language).map_err(AppError::BadRequest)?;
+        let language = self
+            .language_repo
+            .find_by_code(&code, 0)
+            .await?
+            .filter(|l| l.is_active)
+            .ok_or_else(|| AppError::BusinessError("language is not found or inactive".into()))?;
+        if language.code == project.base_language {
+            return Err(AppError::BusinessError(
+                "base language can not be a target language".into(),
+            ));
+        }
+        if self.project_language_repo.exists(id, &language.code).await? {
+            return Err(AppError::BusinessError("repeat project language".into()));
+        }
+
+        let entity = ProjectLanguage {
+            project_id: id,
+            language: language.code,
+            is_default: dto.is_default,
+            fallbacks: None,
+        };
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.project_language_repo.insert_with(conn, &entity).await?;
+                if entity.is_default {
+                    self.project_language_repo
+                        .set_default_with(conn, id, &entity.language)
+                        .await?;
+                }
+                Ok(())
+            })
+        })
+        .await?;
+        self.invalidate_stats(id).await;
+        Ok(true)
+    }
+
+    /// 移除目标语言，已有的翻译保留
+    pub async fn remove_language(&self, id: u64, language: &str) -> Result<bool, AppError> {
+        self.live_project(id).await?;
+        if !self.project_language_repo.delete(id, language).await? {
+            return Err(AppError::NotFound("project language not found".into()));
+        }
+        self.invalidate_stats(id).await;
+        Ok(true)
+    }
+
+    /// 设置默认目标语言
+    pub async fn set_default_language(&self, id: u64, language: &str) -> Result<bool, AppError> {
+        self.live_project(id).await?;
+        if !self.project_language_repo.exists(id, language).await? {
+            return Err(AppError::NotFound("project language not found".into()));
+        }
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.project_language_repo
+                    .set_default_with(conn, id, language)
+                    .await?;
+                Ok(true)
+            })
+        })
+        .await
+    }
+
+    /// 设置目标语言的回退链，回退语言必须已启用且不能形成循环
+    pub async fn update_fallbacks(
+        &self,
+        id: u64,
+        language: &str,
+        dto: &UpdateLanguageFallbacksDto,
+    ) -> Result<bool, AppError> {
+        let project = self.live_project(id).await?;
+        let mut languages = self.project_language_repo.find_by_project(id).await?;
+        let target = languages
+            .iter_mut()
+            .find(|l| l.language == language)
+            .ok_or_else(|| AppError::NotFound("project language not found".into()))?;
+
+        let fallbacks = match &dto.fallbacks {
+            Some(fallbacks) if !fallbacks.is_empty() => {
+                let fallbacks = fallbacks
+                    .iter()
+                    .map(|f| locale::canonicalize(f))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(AppError::BadRequest)?;
+                for fallback in &fallbacks {
+                    if fallback == language {
+                        return Err(AppError::BusinessError(
+                            "language can not fall back to itself".into(),
+                        ));
+                    }
+                    let active = self
+                        .language_repo
+                        .find_by_code(fallback, 0)
+                        .await?
+                        .is_some_and(|l| l.is_active);
+                    if !active {
+                        return Err(AppError::BusinessError(format!(
+                            "fallback language {} is not found or inactive",
+                            fallback
+                        )));
+                    }
+                }
+                Some(json!(fallbacks))
+            }
+            _ => None,
+        };
+        target.fallbacks = fallbacks.clone();
+
+        // 新配置下每个目标语言的回退链都不能有循环
+        for l in &languages {
+            project_language::resolve_chain(&languages, &l.language, &project.base_language)?;
+        }
+        self.project_language_repo
+            .update_fallbacks(id, language, fallbacks)
+            .await?;
+        Ok(true)
+    }
+
+    async fn live_project(&self, id: u64) -> Result<Project, AppError> {
+        self.repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("project not found".into()))
+    }
+
+    /// 目标语言变化后使项目统计缓存失效，失败只记录日志
+    async fn invalidate_stats(&self, project_id: u64) {
+        if let Err(e) = self.stats_cache.invalidate(project_id).await {
+            warn!("项目统计缓存失效失败: {}", e);
+        }
+    }
+}
+
+/// 令牌限定了项目时只查询可访问的项目
+fn scoped(filter: Filter, scope: Option<&ApiTokenScope>) -> Filter {
+    match scope.and_then(|s| s.project_ids.clone()) {
+        Some(project_ids) => filter.is_in("id", project_ids),
+        None => filter,
+    }
+}
+
+/// 名称或编码已被占用时返回 BusinessError，占用者在回收站中时提示先恢复或彻底删除
+fn check_unique(existing: Option<Project>, field: &str) -> Result<(), AppError> {
+    match existing {
+        Some(project) if project.deleted_at.is_some() => Err(AppError::BusinessError(format!(
+            "project {} exists in trash, restore or purge it",
+            field
+        ))),
+        Some(_) => Err(AppError::BusinessError(format!("repeat project {}", field))),
+        None => Ok(()),
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,161 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{Duration, NaiveDateTime};
+use sqlx::MySqlPool;
+
+use crate::{
+    dtos::report::{ReportQuery, TranslatorReportVo},
+    errors::AppError,
+    models::{
+        enums::TranslationStatus,
+        translation_history::TranslationHistory,
+    },
+    repositories::{BaseRepository, TranslationHistoryRepository, UserRepository},
+    utils::text,
+};
+
+// 单次报表允许的最大天数
+const REPORT_MAX_DAYS: i64 = 366;
+
+pub struct ReportService {
+    history_repo: TranslationHistoryRepository,
+    user_repo: UserRepository,
+}
+
+/// 单个用户的统计过程数据
+#[derive(Default)]
+struct Tally {
+    report: TranslatorReportVo,
+    publish_hours: Vec<f64>,
+}
+
+impl ReportService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            history_repo: TranslationHistoryRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool),
+        }
+    }
+
+    /// 按翻译历史统计每个用户在时间范围内的翻译、审核情况
+    pub async fn translator_report(
+        &self,
+        req: &ReportQuery,
+        operator: &str,
+    ) -> Result<Vec<TranslatorReportVo>, AppError> {
+        self.user_repo.require_manager(operator, "view reports").await?;
+        if req.to < req.from || (req.to - req.from).num_days() >= REPORT_MAX_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "Report range must be between 1 and {} days",
+                REPORT_MAX_DAYS
+            )));
+        }
+        let from = req.from.and_hms_opt(0, 0, 0).unwrap_or_default();
+        let to = (req.to + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or_default();
+        let history = self
+            .history_repo
+            .find_touched_between(from, to, req.project_id)
+            .await?;
+
+        let mut tallies = tally(&history, from, to);
+        if let Some(user_id) = req.user_id {
+            tallies.retain(|id, _| *id == user_id);
+        }
+
+        let mut reports = Vec::with_capacity(tallies.len());
+        for (user_id, tally) in tallies {
+            let mut report = tally.report;
+            report.user_id = user_id;
+            if let Some(user) = self.user_repo.select_by_id(user_id).await? {
+                report.username = user.username.or(user.email).unwrap_or_default();
+            }
+            if report.translations > 0 {
+                report.rejection_rate = round2(report.rejections as f64 / report.translations as f64);
+            }
+            if !tally.publish_hours.is_empty() {
+                let total: f64 = tally.publish_hours.iter().sum();
+                report.avg_hours_to_publish = Some(round2(total / tally.publish_hours.len() as f64));
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+}
+
+/// 逐条回放每个翻译的历史，只统计发生在 [from, to) 内的修改
+///
+/// 内容变化记为译者的一次提交；状态变为已审核/已发布记为审核，
+/// 由已审核/已发布退回待审核记为审核并计入最近一次提交者的被退回次数；
+/// 原文修改导致的过期不计入审核
+fn tally(
+    history: &[TranslationHistory],
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> BTreeMap<u64, Tally> {
+    let mut tallies: BTreeMap<u64, Tally> = BTreeMap::new();
+    for rows in history.chunk_by(|a, b| a.translation_id == b.translation_id) {
+        let created_at = rows[0].crt_at;
+        let mut prev: Option<&TranslationHistory> = None;
+        let mut author: Option<u64> = None;
+        let mut reviewed = false;
+        let mut published = false;
+
+        for row in rows {
+            let in_range = row.crt_at >= from && row.crt_at < to;
+            let content_changed = prev.is_none_or(|p| p.content != row.content);
+            let status_changed = prev.is_some_and(|p| p.status != row.status);
+
+            if content_changed {
+                if let (true, Some(user_id)) = (in_range, row.user_id) {
+                    let report = &mut tallies.entry(user_id).or_default().report;
+                    report.translations += 1;
+                    report.words_translated += text::word_count(&row.content);
+                    if reviewed {
+                        report.rework_count += 1;
+                    }
+                }
+                author = row.user_id;
+            }
+
+            if status_changed {
+                let approved = prev.is_some_and(|p| {
+                    matches!(p.status, TranslationStatus::Reviewed | TranslationStatus::Published)
+                });
+                match row.status {
+                    TranslationStatus::Reviewed | TranslationStatus::Published => {
+                        if let (true, Some(user_id)) = (in_range, row.user_id) {
+                            tallies.entry(user_id).or_default().report.reviews += 1;
+                        }
+                        reviewed = true;
+                    }
+                    TranslationStatus::Pending if approved => {
+                        if let (true, Some(user_id)) = (in_range, row.user_id) {
+                            tallies.entry(user_id).or_default().report.reviews += 1;
+                        }
+                        if let (true, Some(author)) = (in_range, author) {
+                            tallies.entry(author).or_default().report.rejections += 1;
+                        }
+                        reviewed = true;
+                    }
+                    // 原文修改导致的重新翻译不算返工
+                    TranslationStatus::Outdated => reviewed = false,
+                    TranslationStatus::Pending => {}
+                }
+            }
+
+            if row.status == TranslationStatus::Published && !published {
+                published = true;
+                if let (true, Some(author)) = (in_range, author) {
+                    let hours = (row.crt_at - created_at).num_seconds() as f64 / 3600.0;
+                    tallies.entry(author).or_default().publish_hours.push(hours);
+                }
+            }
+            prev = Some(row);
+        }
+    }
+    tallies
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
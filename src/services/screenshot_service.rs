@@ -4,20 +4,43 @@ use async_trait::async_trait;
 use sqlx::MySqlPool;
 
 use crate::{
-    dtos::screenshot::{CreateScreenshotDto, UpdateScreenshotDto}, errors::AppError, models::screenshot::Screenshot, repositories::{base_repo::BaseRepository, screenshot_repo::ScreenshotRepository}, services::BaseService
+    dtos::screenshot::{CreateScreenshotDto, ScreenshotQuery, UpdateScreenshotDto}, errors::AppError, models::screenshot::Screenshot, repositories::{base_repo::BaseRepository, query::{Filter, PageQuery, Sort}, screenshot_repo::ScreenshotRepository, PhraseRepository}, services::BaseService, utils::api_token::{check_scope, ApiTokenScope}
 };
 
 pub struct ScreenshotService {
     repo: Arc<ScreenshotRepository>,
+    phrase_repo: PhraseRepository,
 }
 
 impl ScreenshotService {
     pub fn new(pool: Arc<MySqlPool>) -> Self {
         Self {
-            repo: Arc::new(ScreenshotRepository::new(pool)),
+            repo: Arc::new(ScreenshotRepository::new(pool.clone())),
+            phrase_repo: PhraseRepository::new(pool),
         }
     }
 
+    /// 按词条、词条所属项目过滤的分页查询
+    pub async fn select_by_page(
+        &self,
+        req: &ScreenshotQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Screenshot>, i64), AppError> {
+        let mut filter = Filter::new().eq_opt("phrase_id", req.phrase_id);
+        if let Some(project_id) = req.project_id {
+            let (condition, params) = self.phrase_repo.project_condition("phrase_id", &[project_id]);
+            filter = filter.sql(&condition, params);
+        }
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            let (condition, params) = self.phrase_repo.project_condition("phrase_id", &project_ids);
+            filter = filter.sql(&condition, params);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        self.repo.select_page(&query).await
+    }
+
     pub async fn insert(&self, screenshot: &CreateScreenshotDto) -> Result<u64, AppError> {
         self.repo.insert(&Screenshot::from(screenshot)).await
     }
@@ -25,6 +48,28 @@ impl ScreenshotService {
     pub async fn update_by_id(&self, id: u64, screenshot: &UpdateScreenshotDto) -> Result<bool, AppError> {
         self.repo.update_by_id(id, &Screenshot::from(screenshot)).await
     }
+
+    /// 校验令牌范围能否访问截图所属的项目，JWT 认证或截图不存在时不校验
+    pub async fn check_access(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.repo.find_project_id(id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
+
+    /// 校验令牌范围能否访问词条所属的项目，JWT 认证或词条不存在时不校验
+    pub async fn check_phrase_access(&self, phrase_id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.phrase_repo.find_project_id(phrase_id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
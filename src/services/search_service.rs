@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use sqlx::MySqlPool;
+
+use crate::{
+    dtos::search::{SearchHitVo, SearchKind, SearchQuery},
+    errors::AppError,
+    repositories::{
+        query::{self, PageQuery},
+        search_repo::SearchCriteria,
+        ModuleRepository, SearchRepository,
+    },
+    utils::{api_token::ApiTokenScope, module_tree::PathFilter, search},
+};
+
+// 高亮片段的最大字符数
+const SNIPPET_MAX_CHARS: usize = 160;
+
+pub struct SearchService {
+    repo: SearchRepository,
+    module_repo: ModuleRepository,
+}
+
+impl SearchService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: SearchRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool),
+        }
+    }
+
+    /// 跨词条、翻译和术语全文检索，按相关度排序并高亮命中片段
+    pub async fn search(
+        &self,
+        req: &SearchQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<SearchHitVo>, i64), AppError> {
+        let terms = search::terms(&req.q);
+        let boolean_query = search::boolean_query(&terms)
+            .ok_or_else(|| AppError::BadRequest("Search keyword is required".into()))?;
+
+        let kinds = match req.types.as_deref() {
+            Some(_) => query::parse_csv(req.types.as_deref())
+                .iter()
+                .map(|t| {
+                    SearchKind::parse(t)
+                        .ok_or_else(|| AppError::BadRequest(format!("Invalid search type: {}", t)))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => SearchKind::ALL.to_vec(),
+        };
+
+        let project_ids = match req.project_id {
+            Some(project_id) => Some(vec![project_id]),
+            None => scope.and_then(|s| s.project_ids.clone()),
+        };
+        let criteria = SearchCriteria {
+            query: &boolean_query,
+            kinds: &kinds,
+            project_ids: project_ids.as_deref(),
+            language: req.language.as_deref(),
+            module_condition: req
+                .module
+                .as_deref()
+                .and_then(PathFilter::parse)
+                .map(|module| self.module_repo.path_condition("p.module_id", &module)),
+        };
+
+        let (offset, limit) = PageQuery::new(req.page, req.size).limit();
+        let (hits, total) = self.repo.search(&criteria, offset, limit).await?;
+        let hits = hits
+            .into_iter()
+            .map(|hit| {
+                let key = hit
+                    .key
+                    .as_deref()
+                    .map(|key| search::highlight(key, &terms, SNIPPET_MAX_CHARS));
+                let snippet = search::highlight(&hit.content, &terms, SNIPPET_MAX_CHARS);
+                SearchHitVo::new(hit, key, snippet)
+            })
+            .collect();
+        Ok((hits, total))
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use chrono::{Duration, NaiveDate, Utc};
+use serde_json::Value;
+use sqlx::MySqlPool;
+use tracing::warn;
+
+use crate::{
+    constants::{STATS_DEFAULT_DAYS, STATS_MAX_DAYS},
+    dtos::stats::{LanguageStatsVo, ProjectStatsVo, StatsBreakdownVo, StatsTimelineVo},
+    errors::AppError,
+    models::{enums::TranslationStatus, phrase::Phrase, translation::Translation},
+    repositories::{
+        BaseRepository, ModuleRepository, PhraseRepository, ProjectLanguageRepository,
+        ProjectRepository, TranslationRepository,
+    },
+    utils::{stats_cache::StatsCache, text},
+};
+
+pub struct StatsService {
+    project_repo: ProjectRepository,
+    module_repo: ModuleRepository,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    project_language_repo: ProjectLanguageRepository,
+    cache: StatsCache,
+}
+
+impl StatsService {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
+        Self {
+            project_repo: ProjectRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool),
+            cache: StatsCache::new(redis_client),
+        }
+    }
+
+    /// 项目各目标语言的翻译进度，结果缓存到 Redis，翻译变更时失效
+    pub async fn project_stats(
+        &self,
+        project_id: u64,
+        days: Option<u32>,
+    ) -> Result<ProjectStatsVo, AppError> {
+        let days = days.unwrap_or(STATS_DEFAULT_DAYS).clamp(1, STATS_MAX_DAYS);
+        if let Some(cached) = self.cache.get(project_id, days).await? {
+            match serde_json::from_str(&cached) {
+                Ok(stats) => return Ok(stats),
+                Err(e) => warn!("项目统计缓存解析失败: {}", e),
+            }
+        }
+
+        let stats = self.compute(project_id, days).await?;
+        let payload = serde_json::to_string(&stats).map_err(|e| AppError::SerdeError(e.to_string()))?;
+        self.cache.set(project_id, days, &payload).await?;
+        Ok(stats)
+    }
+
+    async fn compute(&self, project_id: u64, days: u32) -> Result<ProjectStatsVo, AppError> {
+        let project = self
+            .project_repo
+            .select_by_id(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("project not found".into()))?;
+        let languages: Vec<String> = self
+            .project_language_repo
+            .find_by_project(project_id)
+            .await?
+            .into_iter()
+            .map(|l| l.language)
+            .collect();
+        let module_names: HashMap<u64, String> = self
+            .module_repo
+            .find_by_project_id(project_id)
+            .await?
+            .into_iter()
+            .map(|m| (m.id, m.name))
+            .collect();
+        let phrases = self.phrase_repo.find_by_project_id(project_id).await?;
+        let translations = self
+            .translation_repo
+            .find_by_project_and_languages(project_id, &languages)
+            .await?;
+
+        // (词条ID, 语言) → 翻译
+        let translations: HashMap<(u64, &str), &Translation> = translations
+            .iter()
+            .map(|t| ((t.phrase_id, t.language.as_str()), t))
+            .collect();
+        let today = Utc::now().date_naive();
+
+        let language_stats = languages
+            .iter()
+            .map(|language| {
+                let mut stats = LanguageStatsVo {
+                    language: language.clone(),
+                    total: phrases.len() as u64,
+                    untranslated: 0,
+                    pending: 0,
+                    reviewed: 0,
+                    published: 0,
+                    outdated: 0,
+                    translated_words: 0,
+                    translated_chars: 0,
+                    percent_complete: 0.0,
+                    modules: vec![],
+                    platforms: vec![],
+                    timeline: vec![],
+                };
+                let mut modules: BTreeMap<Option<u64>, StatsBreakdownVo> = BTreeMap::new();
+                let mut platforms: BTreeMap<String, StatsBreakdownVo> = BTreeMap::new();
+
+                for phrase in &phrases {
+                    let translation = translations.get(&(phrase.id, language.as_str()));
+                    let status = translation.map(|t| &t.status);
+                    match status {
+                        Some(TranslationStatus::Pending) => stats.pending += 1,
+                        Some(TranslationStatus::Reviewed) => stats.reviewed += 1,
+                        Some(TranslationStatus::Published) => stats.published += 1,
+                        Some(TranslationStatus::Outdated) => stats.outdated += 1,
+                        None => stats.untranslated += 1,
+                    }
+                    if let Some(translation) = translation {
+                        stats.translated_words += text::word_count(&translation.content);
+                        stats.translated_chars += text::char_count(&translation.content);
+                    }
+
+                    let module = modules.entry(phrase.module_id).or_insert_with(|| StatsBreakdownVo {
+                        id: phrase.module_id,
+                        name: phrase
+                            .module_id
+                            .and_then(|id| module_names.get(&id).cloned())
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    });
+                    count(module, status);
+                    for platform in phrase_platforms(phrase) {
+                        let breakdown = platforms.entry(platform.clone()).or_insert_with(|| {
+                            StatsBreakdownVo {
+                                name: platform,
+                                ..Default::default()
+                            }
+                        });
+                        count(breakdown, status);
+                    }
+                }
+
+                stats.percent_complete = percent(stats.published, stats.total);
+                stats.modules = modules.into_values().map(finish).collect();
+                stats.platforms = platforms.into_values().map(finish).collect();
+                stats.timeline = timeline(&phrases, &translations, language, today, days);
+                stats
+            })
+            .collect();
+
+        Ok(ProjectStatsVo {
+            project_id,
+            base_language: project.base_language,
+            total_phrases: phrases.len() as u64,
+            source_words: phrases.iter().map(|p| text::word_count(&p.base_content)).sum(),
+            source_chars: phrases.iter().map(|p| text::char_count(&p.base_content)).sum(),
+            languages: language_stats,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+/// 每天结束时的完成度，按词条创建时间和已发布翻译的最后更新时间近似计算
+fn timeline(
+    phrases: &[Phrase],
+    translations: &HashMap<(u64, &str), &Translation>,
+    language: &str,
+    today: NaiveDate,
+    days: u32,
+) -> Vec<StatsTimelineVo> {
+    (0..days as i64)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset);
+            let live: Vec<&Phrase> = phrases.iter().filter(|p| p.crt_at.date() <= date).collect();
+            let published = live
+                .iter()
+                .filter(|p| {
+                    translations.get(&(p.id, language)).is_some_and(|t| {
+                        matches!(t.status, TranslationStatus::Published) && t.upt_at.date() <= date
+                    })
+                })
+                .count() as u64;
+            StatsTimelineVo {
+                date,
+                total: live.len() as u64,
+                published,
+                percent_complete: percent(published, live.len() as u64),
+            }
+        })
+        .collect()
+}
+
+fn phrase_platforms(phrase: &Phrase) -> Vec<String> {
+    phrase
+        .platforms
+        .as_array()
+        .map(|list| list.iter().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+fn count(breakdown: &mut StatsBreakdownVo, status: Option<&TranslationStatus>) {
+    breakdown.total += 1;
+    match status {
+        Some(TranslationStatus::Pending) => breakdown.pending += 1,
+        Some(TranslationStatus::Reviewed) => breakdown.reviewed += 1,
+        Some(TranslationStatus::Published) => breakdown.published += 1,
+        Some(TranslationStatus::Outdated) => breakdown.outdated += 1,
+        None => {}
+    }
+}
+
+fn finish(mut breakdown: StatsBreakdownVo) -> StatsBreakdownVo {
+    breakdown.percent_complete = percent(breakdown.published, breakdown.total);
+    breakdown
+}
+
+/// 百分比，保留两位小数
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 * 10000.0 / total as f64).round() / 100.0
+}
@@ -0,0 +1,468 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Local;
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::{
+    constants::{NOTIFICATION_TARGET_TASK, TASK_MAX_PHRASES, TASK_NAME_MAX_CHARS},
+    dtos::task::{
+        CreateTaskDto, MyTaskQuery, OverdueTaskQuery, TaskPhraseQuery, TaskQuery, TaskScopeDto,
+        TaskVo, UpdateTaskDto,
+    },
+    errors::AppError,
+    models::{
+        enums::{NotificationKind, TaskStatus},
+        notification::Notification,
+        task::{Task, TaskPhrase},
+    },
+    repositories::{
+        query::{Filter, PageQuery, Sort},
+        BaseRepository, PhraseRepository, ProjectLanguageRepository, ProjectRepository,
+        TaskRepository, TranslationRepository, UserRepository,
+    },
+    services::{notification_service::Notifier, BaseService},
+    utils::{
+        api_token::{check_scope, ApiTokenScope},
+        locale,
+    },
+};
+
+pub struct TaskService {
+    repo: Arc<TaskRepository>,
+    project_repo: ProjectRepository,
+    project_language_repo: ProjectLanguageRepository,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    user_repo: UserRepository,
+    tracker: TaskTracker,
+    notifier: Notifier,
+}
+
+impl TaskService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: Arc::new(TaskRepository::new(pool.clone())),
+            project_repo: ProjectRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool.clone()),
+            tracker: TaskTracker::new(pool.clone()),
+            notifier: Notifier::new(pool),
+        }
+    }
+
+    /// 多条件分页查询，令牌限定了项目时只查询可访问的项目
+    pub async fn select_page(
+        &self,
+        req: &TaskQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<TaskVo>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq_opt("project_id", req.project_id)
+            .eq_opt("language", req.language.clone())
+            .eq_opt("status", req.status.map(|status| status.to_string()));
+        if let Some(assignee_id) = req.assignee_id {
+            filter = assigned_to(filter, assignee_id);
+        }
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            filter = filter.is_in("project_id", project_ids);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::desc("id")]);
+        self.page(&query).await
+    }
+
+    /// 指派给当前用户（作为译者或审核人）的任务，截止时间早的在前
+    pub async fn select_mine(
+        &self,
+        user_id: u64,
+        req: &MyTaskQuery,
+    ) -> Result<(Vec<TaskVo>, i64), AppError> {
+        let filter = assigned_to(Filter::new(), user_id)
+            .eq_opt("status", req.status.map(|status| status.to_string()));
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::asc("due_at"), Sort::asc("id")]);
+        self.page(&query).await
+    }
+
+    /// 已过截止时间仍未完成的任务，逾期最久的在前
+    pub async fn select_overdue(
+        &self,
+        req: &OverdueTaskQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<TaskVo>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq("status", TaskStatus::Open.to_string())
+            .sql("due_at < ?", vec![Local::now().naive_local().into()])
+            .eq_opt("project_id", req.project_id);
+        if let Some(assignee_id) = req.assignee_id {
+            filter = assigned_to(filter, assignee_id);
+        }
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            filter = filter.is_in("project_id", project_ids);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::asc("due_at"), Sort::asc("id")]);
+        self.page(&query).await
+    }
+
+    pub async fn select_task(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<TaskVo, AppError> {
+        Ok(TaskVo::from(&self.find(id, scope).await?))
+    }
+
+    /// 任务中的词条及其在目标语言下的翻译状态
+    pub async fn select_phrases(
+        &self,
+        id: u64,
+        req: &TaskPhraseQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<TaskPhrase>, i64), AppError> {
+        let task = self.find(id, scope).await?;
+        self.repo
+            .find_phrases(&task, &PageQuery::new(req.page, req.size))
+            .await
+    }
+
+    /// 创建任务：按范围选出词条，统计初始进度并通知译者和审核人
+    pub async fn insert(
+        &self,
+        dto: &CreateTaskDto,
+        scope: Option<&ApiTokenScope>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<TaskVo, AppError> {
+        check_scope(scope, dto.project_id)?;
+        self.user_repo.require_manager(operator, "manage tasks").await?;
+        let name = check_name(&dto.name)?;
+        self.project_repo
+            .select_by_id(dto.project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".into()))?;
+        let language = locale::canonicalize(&dto.language).map_err(AppError::BadRequest)?;
+        if !self.project_language_repo.exists(dto.project_id, &language).await? {
+            return Err(AppError::BusinessError(format!(
+                "Language {} is not a target language of the project",
+                language
+            )));
+        }
+        self.check_user(dto.translator_id).await?;
+        if let Some(reviewer_id) = dto.reviewer_id {
+            self.check_user(reviewer_id).await?;
+        }
+        let now = Local::now().naive_local();
+        if dto.due_at <= now {
+            return Err(AppError::BadRequest("Due date must be in the future".into()));
+        }
+
+        let phrase_ids = self.resolve_phrases(dto.project_id, &language, &dto.scope).await?;
+        if phrase_ids.is_empty() {
+            return Err(AppError::BusinessError("No phrases match the task scope".into()));
+        }
+        if phrase_ids.len() > TASK_MAX_PHRASES {
+            return Err(AppError::BadRequest(format!(
+                "A task can contain at most {} phrases",
+                TASK_MAX_PHRASES
+            )));
+        }
+
+        let mut task = Task {
+            id: 0,
+            project_id: dto.project_id,
+            language,
+            name,
+            description: dto.description.clone(),
+            translator_id: dto.translator_id,
+            reviewer_id: dto.reviewer_id,
+            due_at: dto.due_at,
+            status: TaskStatus::Open,
+            total: 0,
+            translated: 0,
+            reviewed: 0,
+            completed_at: None,
+            crt_by: operator.to_string(),
+            crt_at: now,
+            upt_by: None,
+            upt_at: now,
+        };
+        let phrase_ids = &phrase_ids;
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                task.id = self.repo.insert_with(conn, &task).await?;
+                self.repo.insert_phrases_with(conn, task.id, phrase_ids).await?;
+                self.tracker.refresh_with(conn, &mut task).await?;
+                self.notify_assignees(conn, &task, None, None, operator_id, operator)
+                    .await?;
+                Ok(TaskVo::from(&task))
+            })
+        })
+        .await
+    }
+
+    /// 修改任务：更换译者或审核人时通知新的指派人，取消或重新打开后重新统计进度
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        dto: &UpdateTaskDto,
+        scope: Option<&ApiTokenScope>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<TaskVo, AppError> {
+        let mut exist = self.find(id, scope).await?;
+        self.user_repo.require_manager(operator, "manage tasks").await?;
+        let (translator_id, reviewer_id) = (exist.translator_id, exist.reviewer_id);
+        if let Some(name) = &dto.name {
+            exist.name = check_name(name)?;
+        }
+        if let Some(description) = &dto.description {
+            exist.description = Some(description.clone());
+        }
+        if let Some(translator_id) = dto.translator_id {
+            self.check_user(translator_id).await?;
+            exist.translator_id = translator_id;
+        }
+        if let Some(reviewer_id) = dto.reviewer_id {
+            self.check_user(reviewer_id).await?;
+            exist.reviewer_id = Some(reviewer_id);
+        }
+        if let Some(due_at) = dto.due_at {
+            if due_at <= Local::now().naive_local() {
+                return Err(AppError::BadRequest("Due date must be in the future".into()));
+            }
+            exist.due_at = due_at;
+        }
+        match dto.status {
+            Some(TaskStatus::Cancelled) => {
+                exist.status = TaskStatus::Cancelled;
+                exist.completed_at = None;
+            }
+            // 重新打开后由进度决定是否已完成
+            Some(TaskStatus::Open) if exist.status == TaskStatus::Cancelled => {
+                exist.status = TaskStatus::Open;
+            }
+            Some(TaskStatus::Completed) => {
+                return Err(AppError::BadRequest(
+                    "A task is completed automatically when all its phrases are done".into(),
+                ));
+            }
+            _ => {}
+        }
+        exist.upt_by = Some(operator.to_string());
+        exist.upt_at = Local::now().naive_local();
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.repo.update_by_id_with(conn, id, &exist).await?;
+                self.tracker.refresh_with(conn, &mut exist).await?;
+                self.notify_assignees(
+                    conn,
+                    &exist,
+                    Some(translator_id),
+                    reviewer_id,
+                    operator_id,
+                    operator,
+                )
+                .await?;
+                Ok(TaskVo::from(&exist))
+            })
+        })
+        .await
+    }
+
+    /// 删除任务，不影响词条和翻译
+    pub async fn delete(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        self.find(id, scope).await?;
+        self.user_repo.require_manager(operator, "manage tasks").await?;
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.repo.delete_phrases_with(conn, id).await?;
+                self.repo.delete_by_id_with(conn, id).await
+            })
+        })
+        .await
+    }
+
+    async fn page(&self, query: &PageQuery) -> Result<(Vec<TaskVo>, i64), AppError> {
+        let (tasks, total) = self.repo.select_page(query).await?;
+        Ok((tasks.iter().map(TaskVo::from).collect(), total))
+    }
+
+    async fn find(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<Task, AppError> {
+        let task = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".into()))?;
+        check_scope(scope, task.project_id)?;
+        Ok(task)
+    }
+
+    async fn check_user(&self, user_id: u64) -> Result<(), AppError> {
+        self.user_repo
+            .select_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("User {} not found", user_id)))?;
+        Ok(())
+    }
+
+    /// 按范围选出项目中的词条id
+    async fn resolve_phrases(
+        &self,
+        project_id: u64,
+        language: &str,
+        scope: &TaskScopeDto,
+    ) -> Result<Vec<u64>, AppError> {
+        let mut filter = Filter::new()
+            .eq("project_id", project_id)
+            .eq_opt("module_id", scope.module_id)
+            .eq_opt("type_id", scope.type_id)
+            .json_contains_any("tags", &scope.tags)
+            .search_opt(&["key", "base_content"], scope.search_key.as_deref());
+        if !scope.phrase_ids.is_empty() {
+            filter = filter.is_in("id", scope.phrase_ids.clone());
+        }
+        if scope.untranslated_only {
+            filter = filter.sql(
+                &format!(
+                    "NOT EXISTS (SELECT 1 FROM {} t WHERE t.phrase_id = {}.id AND t.language = ? AND t.deleted_at IS NULL)",
+                    self.translation_repo.get_table_name(),
+                    self.phrase_repo.get_table_name()
+                ),
+                vec![language.into()],
+            );
+        }
+        self.phrase_repo.find_ids(&filter).await
+    }
+
+    /// 通知新的译者和审核人，原本就是指派人的不再通知
+    async fn notify_assignees(
+        &self,
+        conn: &mut MySqlConnection,
+        task: &Task,
+        translator_id: Option<u64>,
+        reviewer_id: Option<u64>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<(), AppError> {
+        if task.status == TaskStatus::Cancelled {
+            return Ok(());
+        }
+        if translator_id != Some(task.translator_id) {
+            let template = assignment(task, "translate", operator_id, operator);
+            self.notifier
+                .notify_with(conn, &[task.translator_id], &template)
+                .await?;
+        }
+        if let Some(reviewer) = task.reviewer_id.filter(|id| reviewer_id != Some(*id)) {
+            let template = assignment(task, "review", operator_id, operator);
+            self.notifier.notify_with(conn, &[reviewer], &template).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseService<Task> for TaskService {
+    type Repository = TaskRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
+
+fn check_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("Task name is required".into()));
+    }
+    if name.chars().count() > TASK_NAME_MAX_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Task name must be at most {} characters",
+            TASK_NAME_MAX_CHARS
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// 作为译者或审核人
+fn assigned_to(filter: Filter, user_id: u64) -> Filter {
+    filter.sql(
+        "translator_id = ? OR reviewer_id = ?",
+        vec![user_id.into(), user_id.into()],
+    )
+}
+
+fn assignment(task: &Task, role: &str, operator_id: u64, operator: &str) -> Notification {
+    let mut template = Notification::new(
+        NotificationKind::Assignment,
+        format!(
+            "{} assigned you to {} {} ({}), due {}",
+            operator,
+            role,
+            task.name,
+            task.language,
+            task.due_at.format("%Y-%m-%d %H:%M")
+        ),
+        NOTIFICATION_TARGET_TASK,
+        task.id,
+    );
+    template.content = task.description.clone();
+    template.project_id = Some(task.project_id);
+    template.actor_id = Some(operator_id);
+    template.actor = Some(operator.to_string());
+    template
+}
+
+/// 翻译或原文变化后刷新相关任务的进度，与业务数据在同一事务中
+pub struct TaskTracker {
+    repo: TaskRepository,
+}
+
+impl TaskTracker {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: TaskRepository::new(pool),
+        }
+    }
+
+    /// 刷新包含该词条且未取消的任务，指定语言时只刷新该语言的任务
+    pub async fn refresh_by_phrase_with(
+        &self,
+        conn: &mut MySqlConnection,
+        phrase_id: u64,
+        language: Option<&str>,
+    ) -> Result<(), AppError> {
+        for mut task in self
+            .repo
+            .find_active_by_phrase_with(conn, phrase_id, language)
+            .await?
+        {
+            self.refresh_with(conn, &mut task).await?;
+        }
+        Ok(())
+    }
+
+    /// 重新统计任务进度，全部完成时标记为已完成，否则保持进行中
+    pub async fn refresh_with(
+        &self,
+        conn: &mut MySqlConnection,
+        task: &mut Task,
+    ) -> Result<(), AppError> {
+        let progress = self.repo.find_progress_with(conn, task.id).await?;
+        task.apply_progress(&progress, Local::now().naive_local());
+        self.repo.update_progress_with(conn, task).await?;
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use sqlx::MySqlPool;
 
 use crate::{
-    dtos::term::{CreateTermDto, UpdateTermDto}, errors::AppError, models::term::Term, repositories::{base_repo::BaseRepository, term_repo::TermRepository}, services::BaseService
+    dtos::term::{CreateTermDto, TermQuery, UpdateTermDto}, errors::AppError, models::term::Term, repositories::{base_repo::BaseRepository, query::{Filter, PageQuery, Sort}, term_repo::TermRepository}, services::BaseService, utils::api_token::{check_scope, ApiTokenScope}
 };
 
 pub struct TermService {
@@ -18,6 +18,25 @@ impl TermService {
         }
     }
 
+    /// 按项目、语言过滤并按术语搜索的分页查询
+    pub async fn select_by_page(
+        &self,
+        req: &TermQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Term>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq_opt("project_id", req.project_id)
+            .eq_opt("language", req.language.clone())
+            .search_opt(&["source_term", "target_term"], req.search_key.as_deref());
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            filter = filter.is_in("project_id", project_ids);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        self.repo.select_page(&query).await
+    }
+
     pub async fn insert(&self, term: &CreateTermDto) -> Result<u64, AppError> {
         self.repo.insert(&Term::from(term)).await
     }
@@ -25,6 +44,17 @@ impl TermService {
     pub async fn update_by_id(&self, id: u64, term: &UpdateTermDto) -> Result<bool, AppError> {
         self.repo.update_by_id(id, &Term::from(term)).await
     }
+
+    /// 校验令牌范围能否访问术语所属的项目，JWT 认证或术语不存在时不校验
+    pub async fn check_access(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.repo.find_project_id(id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
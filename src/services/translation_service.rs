@@ -1,29 +1,390 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::MySqlPool;
+use chrono::Local;
+use sqlx::{MySqlConnection, MySqlPool};
+use serde_json::json;
+use tracing::warn;
 
 use crate::{
-    dtos::translation::{CreateTranslationDto, UpdateTranslationDto}, errors::AppError, models::translation::Translation, repositories::{base_repo::BaseRepository, translation_repo::TranslationRepository}, services::BaseService
+    constants::{NOTIFICATION_TARGET_TRANSLATION, WEBHOOK_EVENT_TRANSLATION_PUBLISHED}, dtos::{collab::CollabEvent, translation::{CreateTranslationDto, TranslationQuery, TranslationSourceVo, UpdateTranslationDto}}, errors::AppError, models::{enums::{NotificationKind, TranslationStatus}, notification::Notification, phrase::Phrase, translation::Translation, translation_history::TranslationHistory}, repositories::{base_repo::BaseRepository, query::{Filter, PageQuery, Sort}, translation_repo::TranslationRepository, CommentRepository, PhraseRepository, PhraseSourceRepository, ProjectLanguageRepository, TranslationHistoryRepository}, services::{collab_service::CollabPublisher, notification_service::Notifier, task_service::TaskTracker, webhook_service::WebhookDispatcher, BaseService}, utils::{api_token::{check_scope, ApiTokenScope}, locale, stats_cache::StatsCache}
 };
 
 pub struct TranslationService {
     repo: Arc<TranslationRepository>,
+    phrase_repo: PhraseRepository,
+    project_language_repo: ProjectLanguageRepository,
+    history_repo: TranslationHistoryRepository,
+    source_repo: PhraseSourceRepository,
+    comment_repo: CommentRepository,
+    stats_cache: StatsCache,
+    webhooks: WebhookDispatcher,
+    collab: CollabPublisher,
+    notifier: Notifier,
+    tasks: TaskTracker,
 }
 
 impl TranslationService {
-    pub fn new(pool: Arc<MySqlPool>) -> Self {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
         Self {
-            repo: Arc::new(TranslationRepository::new(pool)),
+            repo: Arc::new(TranslationRepository::new(pool.clone())),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool.clone()),
+            history_repo: TranslationHistoryRepository::new(pool.clone()),
+            source_repo: PhraseSourceRepository::new(pool.clone()),
+            comment_repo: CommentRepository::new(pool.clone()),
+            stats_cache: StatsCache::new(redis_client.clone()),
+            webhooks: WebhookDispatcher::new(pool.clone()),
+            collab: CollabPublisher::new(redis_client),
+            notifier: Notifier::new(pool.clone()),
+            tasks: TaskTracker::new(pool),
         }
     }
 
-    pub async fn insert(&self, translation: &CreateTranslationDto) -> Result<u64, AppError> {
-        self.repo.insert(&Translation::from(translation)).await
+    /// 按词条、词条所属项目、语言和状态过滤的分页查询
+    pub async fn select_by_page(
+        &self,
+        req: &TranslationQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<Translation>, i64), AppError> {
+        let mut filter = Filter::new()
+            .eq_opt("phrase_id", req.phrase_id)
+            .eq_opt("language", req.language.clone())
+            .eq_opt("status", req.status.as_ref().map(ToString::to_string));
+        if let Some(project_id) = req.project_id {
+            let (condition, params) = self.phrase_repo.project_condition("phrase_id", &[project_id]);
+            filter = filter.sql(&condition, params);
+        }
+        if let Some(project_ids) = scope.and_then(|s| s.project_ids.clone()) {
+            let (condition, params) = self.phrase_repo.project_condition("phrase_id", &project_ids);
+            filter = filter.sql(&condition, params);
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        self.repo.select_page(&query).await
+    }
+
+    /// 校验令牌范围能否访问翻译所属的项目，JWT 认证或翻译不存在时不校验
+    pub async fn check_access(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.repo.find_project_id(id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
+
+    /// 校验令牌范围能否访问词条所属的项目，JWT 认证或词条不存在时不校验
+    pub async fn check_phrase_access(&self, phrase_id: u64, scope: Option<&ApiTokenScope>) -> Result<(), AppError> {
+        if scope.is_none() {
+            return Ok(());
+        }
+        match self.phrase_repo.find_project_id(phrase_id).await? {
+            Some(project_id) => check_scope(scope, project_id),
+            None => Ok(()),
+        }
+    }
+
+    /// 创建翻译，操作人记为译者，同时写入第一条历史
+    pub async fn insert(
+        &self,
+        translation: &CreateTranslationDto,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<u64, AppError> {
+        let mut entity = Translation::from(translation);
+        entity.language = locale::canonicalize(&entity.language).map_err(AppError::BadRequest)?;
+        entity.translated_by = Some(operator_id);
+        entity.crt_by = operator.to_string();
+        let phrase = self.check_language(entity.phrase_id, &entity.language).await?;
+        entity.source_version = phrase.source_version;
+        let mut created = entity.clone();
+        let id = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    entity.id = self.repo.insert_with(conn, &entity).await?;
+                    let history = TranslationHistory::new(&entity, operator_id, operator);
+                    self.history_repo.insert_with(conn, &history).await?;
+                    self.tasks
+                        .refresh_by_phrase_with(conn, entity.phrase_id, Some(&entity.language))
+                        .await?;
+                    Ok(entity.id)
+                })
+            })
+            .await?;
+        self.invalidate_stats(phrase.project_id).await;
+        created.id = id;
+        self.broadcast(phrase.project_id, CollabEvent::TranslationEdited { translation: created })
+            .await;
+        Ok(id)
+    }
+
+    /// 翻译语言必须是词条所属项目的目标语言
+    async fn check_language(&self, phrase_id: u64, language: &str) -> Result<Phrase, AppError> {
+        let phrase = self
+            .phrase_repo
+            .select_by_id(phrase_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        if !self.project_language_repo.exists(phrase.project_id, language).await? {
+            return Err(AppError::BusinessError(format!(
+                "Language {} is not a target language of the project",
+                language
+            )));
+        }
+        Ok(phrase)
+    }
+
+    /// 更新翻译，携带版本号时与当前版本不一致将返回冲突
+    ///
+    /// 内容或状态变化时写入历史；未指定译者、审核人时分别记为修改内容、审核通过的操作人
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        translation: &UpdateTranslationDto,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        let mut exist = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Translation not found".into()))?;
+        let content_changed = translation
+            .content
+            .as_ref()
+            .is_some_and(|content| *content != exist.content);
+        let status_changed = translation
+            .status
+            .as_ref()
+            .is_some_and(|status| *status != exist.status);
+        let translator = exist.translated_by;
+        let mut language_changed = false;
+        if let Some(language_code) = &translation.language_code {
+            let language_code = locale::canonicalize(language_code).map_err(AppError::BadRequest)?;
+            if language_code != exist.language {
+                self.check_language(exist.phrase_id, &language_code).await?;
+                language_changed = true;
+            }
+            exist.language = language_code;
+        }
+        if let Some(content) = &translation.content {
+            exist.content = content.clone();
+        }
+        if let Some(status) = &translation.status {
+            exist.status = status.clone();
+        }
+        if translation.translated_by.is_some() {
+            exist.translated_by = translation.translated_by;
+        } else if content_changed {
+            exist.translated_by = Some(operator_id);
+        }
+        if translation.reviewed_by.is_some() {
+            exist.reviewed_by = translation.reviewed_by;
+        } else if status_changed
+            && matches!(exist.status, TranslationStatus::Reviewed | TranslationStatus::Published)
+        {
+            exist.reviewed_by = Some(operator_id);
+        }
+        // 修改内容或确认状态后，翻译即依据当前原文
+        if exist.follows_current_source(content_changed, status_changed) {
+            if let Some(phrase) = self.phrase_repo.select_by_id(exist.phrase_id).await? {
+                exist.source_version = phrase.source_version;
+            }
+        }
+        exist.version = translation.version.unwrap_or(exist.version);
+        exist.upt_by = Some(operator.to_string());
+        exist.upt_at = Local::now().naive_local();
+        let phrase_id = exist.phrase_id;
+        let published = status_changed && exist.status == TranslationStatus::Published;
+        let translation = &exist;
+        let updated = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    let updated = self.repo.update_by_id_with(conn, id, translation).await?;
+                    if content_changed || status_changed {
+                        let mut history = TranslationHistory::new(translation, operator_id, operator);
+                        history.version += 1;
+                        self.history_repo.insert_with(conn, &history).await?;
+                    }
+                    if published {
+                        self.notify_published(conn, translation).await?;
+                    }
+                    if status_changed {
+                        self.notify_review_result(conn, translation, translator, operator_id, operator)
+                            .await?;
+                    }
+                    // 改变语言时原语言和新语言的任务都受影响
+                    if language_changed {
+                        self.tasks.refresh_by_phrase_with(conn, phrase_id, None).await?;
+                    } else if status_changed {
+                        self.tasks
+                            .refresh_by_phrase_with(conn, phrase_id, Some(&translation.language))
+                            .await?;
+                    }
+                    Ok(updated)
+                })
+            })
+            .await?;
+        if let Some(phrase) = self.phrase_repo.select_by_id(phrase_id).await? {
+            self.invalidate_stats(phrase.project_id).await;
+            if status_changed {
+                let event = CollabEvent::TranslationStatusChanged {
+                    translation_id: id,
+                    phrase_id,
+                    language: exist.language.clone(),
+                    status: exist.status.clone(),
+                };
+                self.broadcast(phrase.project_id, event).await;
+            }
+            if content_changed && updated {
+                // 更新成功后版本号已递增，推送新版本号供其他成员继续编辑
+                exist.version += 1;
+                let event = CollabEvent::TranslationEdited { translation: exist };
+                self.broadcast(phrase.project_id, event).await;
+            }
+        }
+        Ok(updated)
+    }
+
+    /// 审核人修改了翻译状态后通知原译者
+    async fn notify_review_result(
+        &self,
+        conn: &mut MySqlConnection,
+        translation: &Translation,
+        translator: Option<u64>,
+        operator_id: u64,
+        operator: &str,
+    ) -> Result<(), AppError> {
+        let Some(translator) = translator else {
+            return Ok(());
+        };
+        if translation.status == TranslationStatus::Outdated {
+            return Ok(());
+        }
+        let Some(phrase) = self.phrase_repo.select_by_id_with(conn, translation.phrase_id).await? else {
+            return Ok(());
+        };
+        let mut template = Notification::new(
+            NotificationKind::ReviewResult,
+            format!(
+                "{} marked your {} translation of {} as {}",
+                operator,
+                translation.language,
+                phrase.key,
+                translation.status.to_string()
+            ),
+            NOTIFICATION_TARGET_TRANSLATION,
+            translation.id,
+        );
+        template.project_id = Some(phrase.project_id);
+        template.actor_id = Some(operator_id);
+        template.actor = Some(operator.to_string());
+        self.notifier.notify_with(conn, &[translator], &template).await?;
+        Ok(())
+    }
+
+    /// 翻译发布后通知订阅了 translation.published 的 Webhook
+    async fn notify_published(
+        &self,
+        conn: &mut MySqlConnection,
+        translation: &Translation,
+    ) -> Result<(), AppError> {
+        let Some(phrase) = self.phrase_repo.select_by_id_with(conn, translation.phrase_id).await? else {
+            return Ok(());
+        };
+        let data = json!({
+            "translation": translation,
+            "phrase": { "id": phrase.id, "key": phrase.key },
+        });
+        self.webhooks
+            .dispatch_with(conn, phrase.project_id, WEBHOOK_EVENT_TRANSLATION_PUBLISHED, data)
+            .await?;
+        Ok(())
     }
 
-    pub async fn update_by_id(&self, id: u64, translation: &UpdateTranslationDto) -> Result<bool, AppError> {
-        self.repo.update_by_id(id, &Translation::from(translation)).await
+    /// 翻译依据的原文与当前原文，供工作台对比
+    pub async fn select_source(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<TranslationSourceVo, AppError> {
+        let translation = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Translation not found".into()))?;
+        let phrase = self
+            .phrase_repo
+            .select_by_id(translation.phrase_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Phrase not found".into()))?;
+        check_scope(scope, phrase.project_id)?;
+        let previous_source = if translation.source_version < phrase.source_version {
+            self.source_repo
+                .find_by_phrase_and_version(phrase.id, translation.source_version)
+                .await?
+                .map(|source| source.base_content)
+        } else {
+            None
+        };
+        Ok(TranslationSourceVo {
+            translation_id: translation.id,
+            phrase_id: phrase.id,
+            status: translation.status,
+            source_version: phrase.source_version,
+            current_source: phrase.base_content,
+            translated_source_version: translation.source_version,
+            previous_source,
+        })
+    }
+
+    /// 删除翻译，连同历史和评论一起物理删除
+    ///
+    /// 单个翻译没有回收站，软删除会一直占用 (phrase_id, language) 唯一键，导致无法重新创建
+    pub async fn delete_by_id(&self, id: u64) -> Result<bool, AppError> {
+        let Some(exist) = self.repo.select_by_id(id).await? else {
+            return Ok(false);
+        };
+        let translation = &exist;
+        let deleted = self
+            .with_transaction(|conn, _| {
+                Box::pin(async move {
+                    self.history_repo.delete_by_translation_with(conn, id).await?;
+                    self.comment_repo.delete_by_translation_with(conn, id).await?;
+                    let deleted = self.repo.purge_by_ids_with(conn, &[id]).await? > 0;
+                    self.tasks
+                        .refresh_by_phrase_with(conn, translation.phrase_id, Some(&translation.language))
+                        .await?;
+                    Ok(deleted)
+                })
+            })
+            .await?;
+        self.invalidate_phrase_stats(exist.phrase_id).await?;
+        Ok(deleted)
+    }
+
+    async fn invalidate_phrase_stats(&self, phrase_id: u64) -> Result<(), AppError> {
+        if let Some(phrase) = self.phrase_repo.select_by_id(phrase_id).await? {
+            self.invalidate_stats(phrase.project_id).await;
+        }
+        Ok(())
+    }
+
+    /// 推送给项目协作通道中的成员，失败只记录日志，不影响已提交的修改
+    async fn broadcast(&self, project_id: u64, event: CollabEvent) {
+        if let Err(e) = self.collab.publish(project_id, &event).await {
+            warn!("协作消息发布失败: {}", e);
+        }
+    }
+
+    /// 翻译变更后使项目统计缓存失效，失败只记录日志，不影响已提交的修改
+    async fn invalidate_stats(&self, project_id: u64) {
+        if let Err(e) = self.stats_cache.invalidate(project_id).await {
+            warn!("项目统计缓存失效失败: {}", e);
+        }
     }
 }
 
@@ -35,3 +396,42 @@ impl BaseService<Translation> for TranslationService {
         &self.repo
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{database, redis};
+
+    async fn setup() -> TranslationService {
+        dotenv::dotenv().ok();
+        let pool = database::init().await.expect("Failed to connect to MySQL");
+        TranslationService::new(Arc::new(pool), redis::init().await)
+    }
+
+    fn translation(phrase_id: u64) -> Translation {
+        Translation::from(&CreateTranslationDto {
+            phrase_id,
+            language_code: "zh-CN".into(),
+            translated_text: "测试".into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_delete_then_recreate() {
+        let service = setup().await;
+        // 不存在的词条，测试数据不影响真实数据
+        let phrase_id = u32::MAX as u64;
+
+        // 删除后同一词条、同一语言的翻译可以重新创建
+        let id = service.repo.insert(&translation(phrase_id)).await.unwrap();
+        assert!(service.delete_by_id(id).await.unwrap());
+        assert!(service.repo.select_deleted_by_id(id).await.unwrap().is_none());
+
+        let recreated = service
+            .repo
+            .insert(&translation(phrase_id))
+            .await
+            .expect("translation should be re-created after delete");
+        assert!(service.delete_by_id(recreated).await.unwrap());
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, Utc};
+use sqlx::MySqlPool;
+use tracing::{error, info, warn};
+
+use crate::{
+    config::SETTINGS,
+    errors::AppError,
+    repositories::{
+        BaseRepository, CommentRepository, KeyRuleRepository, ModuleRepository,
+        NotificationRepository, PhraseRepository, PhraseSourceRepository,
+        ProjectLanguageRepository, ProjectRepository, ScreenshotRepository, TaskRepository,
+        TranslationHistoryRepository, TranslationRepository, WebhookDeliveryRepository,
+        WebhookRepository,
+    },
+    utils::storage,
+};
+
+/// 回收站清理：物理删除超过保留期的软删除数据
+pub struct TrashService {
+    project_repo: ProjectRepository,
+    module_repo: ModuleRepository,
+    phrase_repo: PhraseRepository,
+    translation_repo: TranslationRepository,
+    screenshot_repo: ScreenshotRepository,
+    history_repo: TranslationHistoryRepository,
+    source_repo: PhraseSourceRepository,
+    comment_repo: CommentRepository,
+    notification_repo: NotificationRepository,
+    task_repo: TaskRepository,
+    project_language_repo: ProjectLanguageRepository,
+    key_rule_repo: KeyRuleRepository,
+    webhook_repo: WebhookRepository,
+    delivery_repo: WebhookDeliveryRepository,
+}
+
+impl TrashService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            project_repo: ProjectRepository::new(pool.clone()),
+            module_repo: ModuleRepository::new(pool.clone()),
+            phrase_repo: PhraseRepository::new(pool.clone()),
+            translation_repo: TranslationRepository::new(pool.clone()),
+            screenshot_repo: ScreenshotRepository::new(pool.clone()),
+            history_repo: TranslationHistoryRepository::new(pool.clone()),
+            source_repo: PhraseSourceRepository::new(pool.clone()),
+            comment_repo: CommentRepository::new(pool.clone()),
+            notification_repo: NotificationRepository::new(pool.clone()),
+            task_repo: TaskRepository::new(pool.clone()),
+            project_language_repo: ProjectLanguageRepository::new(pool.clone()),
+            key_rule_repo: KeyRuleRepository::new(pool.clone()),
+            webhook_repo: WebhookRepository::new(pool.clone()),
+            delivery_repo: WebhookDeliveryRepository::new(pool),
+        }
+    }
+
+    /// 按配置的间隔循环执行清理
+    pub async fn run_purge_schedule(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(SETTINGS.trash.purge_interval));
+        loop {
+            interval.tick().await;
+            match self.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => info!("回收站清理完成，共删除 {} 条数据", purged),
+                Err(e) => error!("回收站清理失败: {}", e),
+            }
+        }
+    }
+
+    /// 物理删除超过保留期的数据，在同一事务中先删除依赖数据再删除父数据
+    pub async fn purge_expired(&self) -> Result<u64, AppError> {
+        let before = Utc::now().naive_utc() - chrono::Duration::days(SETTINGS.trash.retention_days);
+        let mut purged = self.purge_screenshots(before).await?;
+
+        let mut tx = self.project_repo.begin().await?;
+        // 通知和评论需根据词条、翻译是否过期判断，先于它们删除
+        purged += self.notification_repo.purge_by_expired_targets_with(&mut tx, before).await?;
+        purged += self.comment_repo.purge_expired_with(&mut tx, before).await?;
+        purged += self.task_repo.purge_phrases_by_deleted_phrases_with(&mut tx, before).await?;
+        purged += self.history_repo.purge_by_deleted_translations_with(&mut tx, before).await?;
+        purged += self.source_repo.purge_by_deleted_phrases_with(&mut tx, before).await?;
+        purged += self.translation_repo.purge_deleted_before_with(&mut tx, before).await?;
+        purged += self.phrase_repo.purge_deleted_before_with(&mut tx, before).await?;
+        purged += self.module_repo.purge_deleted_before_with(&mut tx, before).await?;
+
+        // 项目级数据
+        purged += self.project_language_repo.purge_by_deleted_projects_with(&mut tx, before).await?;
+        purged += self.key_rule_repo.purge_by_deleted_projects_with(&mut tx, before).await?;
+        purged += self.delivery_repo.purge_by_deleted_projects_with(&mut tx, before).await?;
+        purged += self.webhook_repo.purge_by_deleted_projects_with(&mut tx, before).await?;
+        purged += self.task_repo.purge_by_deleted_projects_with(&mut tx, before).await?;
+        purged += self.project_repo.purge_deleted_before_with(&mut tx, before).await?;
+        tx.commit().await?;
+        Ok(purged)
+    }
+
+    /// 先删除存储中的截图文件，删除失败的记录保留到下次重试
+    async fn purge_screenshots(&self, before: NaiveDateTime) -> Result<u64, AppError> {
+        let screenshots = self.screenshot_repo.find_deleted_before(before).await?;
+        if screenshots.is_empty() {
+            return Ok(0);
+        }
+
+        let client = storage::init_s3_client().await?;
+        let bucket = &SETTINGS.minio.bucket;
+        let mut ids = Vec::with_capacity(screenshots.len());
+        for screenshot in &screenshots {
+            if let Some(key) = storage::object_key(&screenshot.image_url, bucket) {
+                if let Err(e) = storage::delete_file(&client, bucket, key).await {
+                    warn!("删除截图文件失败 {}: {}", screenshot.image_url, e);
+                    continue;
+                }
+            }
+            ids.push(screenshot.id);
+        }
+
+        let mut conn = self.screenshot_repo.get_pool().acquire().await?;
+        self.screenshot_repo.purge_by_ids_with(&mut conn, &ids).await
+    }
+}
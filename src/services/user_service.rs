@@ -1,22 +1,43 @@
+use crate::constants::{
+    AUDIT_ACTION_ACCOUNT_LOCKED, AUDIT_ACTION_ACCOUNT_UNLOCKED, AUDIT_ACTION_LOGIN_FAILED,
+    AUDIT_ACTION_LOGIN_SUCCESS, AUDIT_TARGET_USER, LOGIN_INVALID_CREDENTIALS,
+    LOGIN_TOO_MANY_ATTEMPTS,
+};
 use crate::dtos::user::{CreateUserDto, PageUserRequest, UpdateUserDto, UserVo};
 use crate::errors::AppError;
+use crate::models::enums::UserRole;
+use crate::models::operation_log::OperationLog;
 use crate::models::user::User;
+use crate::repositories::operation_log_repo::OperationLogRepository;
+use crate::repositories::query::{Filter, PageQuery, Sort};
 use crate::repositories::user_repo::UserRepository;
 use crate::repositories::BaseRepository;
 use crate::services::BaseService;
+use crate::utils::login_guard::{self, LoginGuard};
 use crate::utils::password;
 use chrono::Utc;
+use serde_json::json;
 use sqlx::MySqlPool;
 use std::sync::Arc;
 
+lazy_static::lazy_static! {
+    // 用户不存在时也执行一次哈希校验，避免通过响应时间枚举用户
+    static ref DUMMY_PASSWORD_HASH: String =
+        password::hash_password("dummy-password-for-timing").expect("hash dummy password");
+}
+
 pub struct UserService {
     repo: UserRepository,
+    log_repo: OperationLogRepository,
+    login_guard: LoginGuard,
 }
 
 impl UserService {
-    pub fn new(pool: Arc<MySqlPool>) -> Self {
+    pub fn new(pool: Arc<MySqlPool>, redis_client: redis::Client) -> Self {
         Self {
-            repo: UserRepository::new(pool),
+            repo: UserRepository::new(pool.clone()),
+            log_repo: OperationLogRepository::new(pool),
+            login_guard: LoginGuard::new(redis_client),
         }
     }
 
@@ -24,37 +45,123 @@ impl UserService {
         &self,
         req: &PageUserRequest,
     ) -> Result<(Vec<UserVo>, i64), AppError> {
-        if let Some(search_key) = &req.search_key {
-            let users = self
-                .repo
-                .select_page_by_key(req.page, req.size, search_key)
-                .await?;
-            let list = users.0.iter().map(|user| UserVo::from(user)).collect();
-            Ok((list, users.1))
-        } else {
-            let users = self.repo.select_by_page(req.page, req.size).await?;
-            let list = users.0.iter().map(|user| UserVo::from(user)).collect();
-            Ok((list, users.1))
-        }
+        let filter = Filter::new()
+            .search_opt(
+                &["username", "email", "phone", "realname", "nickname"],
+                req.search_key.as_deref(),
+            )
+            .eq_opt("status", req.status);
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(Sort::parse_list(req.sort.as_deref()));
+        let users = self.repo.select_page(&query).await?;
+        let list = users.0.iter().map(|user| UserVo::from(user)).collect();
+        Ok((list, users.1))
     }
 
+    /// 用户名密码登录校验
+    ///
+    /// 用户不存在与密码错误返回相同的错误；失败次数按账号和IP记录在 Redis 中，
+    /// 超过上限后临时锁定，并同步写入用户表与操作日志
     pub async fn verify_password(
         &self,
         username: &str,
         password: &str,
+        ip: &str,
     ) -> Result<Option<User>, AppError> {
-        let user = self.repo.find_by_username(username).await?;
+        self.login_guard.check(username, ip).await?;
 
-        match user {
-            Some(user) => {
-                if password::verify_password(password, &user.password)? {
-                    Ok(Some(user))
-                } else {
-                    Err(AppError::Auth("Invalid password".into()))
+        let user = self.repo.find_by_username(username).await?;
+        if let Some(user) = &user {
+            if let Some(locked_until) = user.locked_until {
+                if locked_until > Utc::now().naive_utc() {
+                    return Err(AppError::Auth(LOGIN_TOO_MANY_ATTEMPTS.into()));
                 }
             }
-            None => Err(AppError::NotFound("User not found".into())),
         }
+
+        let verified = match &user {
+            Some(user) => password::verify_password(password, &user.password)?,
+            None => {
+                password::verify_password(password, &DUMMY_PASSWORD_HASH)?;
+                false
+            }
+        };
+
+        if verified {
+            let user = user.unwrap();
+            self.login_guard.reset(username).await?;
+            self.repo.record_login_success(user.id).await?;
+            self.audit(user.id, AUDIT_ACTION_LOGIN_SUCCESS, user.id, None, ip)
+                .await?;
+            return Ok(Some(user));
+        }
+
+        let failures = self.login_guard.record_failure(username, ip).await?;
+        let user_id = user.as_ref().map(|u| u.id).unwrap_or(0);
+        let lock = login_guard::lock_duration(failures);
+        if let Some(user) = &user {
+            let locked_until = lock
+                .and_then(|d| chrono::Duration::from_std(d).ok())
+                .map(|d| Utc::now().naive_utc() + d);
+            self.repo.record_login_failure(user.id, locked_until).await?;
+        }
+        let details = json!({ "username": username, "failures": failures });
+        self.audit(user_id, AUDIT_ACTION_LOGIN_FAILED, user_id, Some(details.clone()), ip)
+            .await?;
+        if lock.is_some() {
+            self.audit(user_id, AUDIT_ACTION_ACCOUNT_LOCKED, user_id, Some(details), ip)
+                .await?;
+        }
+
+        tokio::time::sleep(login_guard::progressive_delay(failures)).await;
+        Err(AppError::Auth(LOGIN_INVALID_CREDENTIALS.into()))
+    }
+
+    /// 管理员解锁账号
+    pub async fn unlock(
+        &self,
+        id: u64,
+        operator_id: u64,
+        operator: &str,
+        ip: &str,
+    ) -> Result<bool, AppError> {
+        self.check_admin(operator).await?;
+        let user = self.select_by_id(id).await?;
+        if let Some(username) = &user.username {
+            self.login_guard.reset(username).await?;
+        }
+        let result = self.repo.unlock(id).await?;
+        self.audit(operator_id, AUDIT_ACTION_ACCOUNT_UNLOCKED, id, None, ip)
+            .await?;
+        Ok(result)
+    }
+
+    async fn check_admin(&self, username: &str) -> Result<(), AppError> {
+        if !self.repo.has_any_role(username, &[UserRole::Admin]).await? {
+            return Err(AppError::Forbidden("only admins can unlock accounts".into()));
+        }
+        Ok(())
+    }
+
+    async fn audit(
+        &self,
+        user_id: u64,
+        action: &str,
+        target_id: u64,
+        details: Option<serde_json::Value>,
+        ip: &str,
+    ) -> Result<(), AppError> {
+        let log = OperationLog::new(
+            user_id,
+            action,
+            AUDIT_TARGET_USER,
+            target_id.to_string(),
+            details,
+            ip,
+        );
+        self.log_repo.insert(&log).await?;
+        Ok(())
     }
 
     pub async fn verify_email_code(
@@ -103,6 +210,11 @@ impl UserService {
             birthday: None,
             status: user.status.clone().unwrap_or(true),
             last_login: None,
+            failed_attempts: 0,
+            locked_until: None,
+            totp_secret: None,
+            totp_enabled: false,
+            totp_last_step: None,
             crt_by: user.crt_by.clone().unwrap_or("register".to_owned()),
             crt_at: Utc::now(),
             upt_by: None,
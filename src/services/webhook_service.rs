@@ -0,0 +1,438 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{Local, Utc};
+use futures::future::join_all;
+use reqwest::{header::CONTENT_TYPE, redirect, Url};
+use serde_json::{json, Value};
+use sqlx::{MySqlConnection, MySqlPool};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    constants::{
+        WEBHOOK_BATCH_SIZE, WEBHOOK_EVENTS, WEBHOOK_LEASE_SECONDS, WEBHOOK_MAX_ATTEMPTS,
+        WEBHOOK_POLL_SECONDS, WEBHOOK_RESPONSE_MAX_CHARS, WEBHOOK_TIMEOUT_SECONDS,
+    },
+    dtos::webhook::{
+        CreateWebhookDto, DeliveryQuery, UpdateWebhookDto, WebhookDeliveryVo, WebhookSecretVo,
+        WebhookVo,
+    },
+    errors::AppError,
+    models::{enums::DeliveryStatus, webhook::Webhook, webhook_delivery::WebhookDelivery},
+    repositories::{
+        query::{Filter, PageQuery, Sort},
+        BaseRepository, ProjectRepository, UserRepository, WebhookDeliveryRepository,
+        WebhookRepository,
+    },
+    services::BaseService,
+    utils::{
+        api_token::{check_scope, ApiTokenScope},
+        oidc, text,
+        webhook::{self, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+    },
+};
+
+pub struct WebhookService {
+    repo: Arc<WebhookRepository>,
+    delivery_repo: WebhookDeliveryRepository,
+    project_repo: ProjectRepository,
+    user_repo: UserRepository,
+}
+
+impl WebhookService {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            repo: Arc::new(WebhookRepository::new(pool.clone())),
+            delivery_repo: WebhookDeliveryRepository::new(pool.clone()),
+            project_repo: ProjectRepository::new(pool.clone()),
+            user_repo: UserRepository::new(pool),
+        }
+    }
+
+    /// 项目的 Webhook 列表
+    pub async fn select_by_project(&self, project_id: u64) -> Result<Vec<WebhookVo>, AppError> {
+        let webhooks = self.repo.find_by_project(project_id).await?;
+        Ok(webhooks.iter().map(WebhookVo::from).collect())
+    }
+
+    pub async fn select_webhook(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<WebhookVo, AppError> {
+        Ok(WebhookVo::from(&self.find(id, scope).await?))
+    }
+
+    /// 创建 Webhook，返回签名密钥
+    pub async fn insert(
+        &self,
+        dto: &CreateWebhookDto,
+        operator: &str,
+    ) -> Result<WebhookSecretVo, AppError> {
+        self.user_repo.require_manager(operator, "manage webhooks").await?;
+        self.project_repo
+            .select_by_id(dto.project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("project not found".into()))?;
+        let now = Local::now().naive_local();
+        let mut entity = Webhook {
+            id: 0,
+            project_id: dto.project_id,
+            url: check_url(&dto.url).await?,
+            secret: oidc::random_token(),
+            events: json!(check_events(&dto.events)?),
+            is_active: dto.is_active.unwrap_or(true),
+            crt_by: operator.to_string(),
+            crt_at: now,
+            upt_by: None,
+            upt_at: now,
+        };
+        entity.id = self.repo.insert(&entity).await?;
+        Ok(WebhookSecretVo {
+            secret: entity.secret.clone(),
+            info: WebhookVo::from(&entity),
+        })
+    }
+
+    pub async fn update_by_id(
+        &self,
+        id: u64,
+        dto: &UpdateWebhookDto,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        let mut exist = self.find(id, scope).await?;
+        self.user_repo.require_manager(operator, "manage webhooks").await?;
+        if let Some(url) = &dto.url {
+            exist.url = check_url(url).await?;
+        }
+        if let Some(events) = &dto.events {
+            exist.events = json!(check_events(events)?);
+        }
+        if let Some(is_active) = dto.is_active {
+            exist.is_active = is_active;
+        }
+        exist.upt_by = Some(operator.to_string());
+        exist.upt_at = Local::now().naive_local();
+        self.repo.update_by_id(id, &exist).await
+    }
+
+    /// 重置签名密钥，旧密钥立即失效
+    pub async fn rotate_secret(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<WebhookSecretVo, AppError> {
+        let mut exist = self.find(id, scope).await?;
+        self.user_repo.require_manager(operator, "manage webhooks").await?;
+        exist.secret = oidc::random_token();
+        exist.upt_by = Some(operator.to_string());
+        exist.upt_at = Local::now().naive_local();
+        self.repo.update_by_id(id, &exist).await?;
+        Ok(WebhookSecretVo {
+            secret: exist.secret.clone(),
+            info: WebhookVo::from(&exist),
+        })
+    }
+
+    /// 删除 Webhook 及其投递记录
+    pub async fn delete_webhook(
+        &self,
+        id: u64,
+        scope: Option<&ApiTokenScope>,
+        operator: &str,
+    ) -> Result<bool, AppError> {
+        self.find(id, scope).await?;
+        self.user_repo.require_manager(operator, "manage webhooks").await?;
+        self.with_transaction(|conn, _| {
+            Box::pin(async move {
+                self.delivery_repo.delete_by_webhook_with(conn, id).await?;
+                self.repo.delete_by_id_with(conn, id).await
+            })
+        })
+        .await
+    }
+
+    /// 投递记录，最新的在前
+    pub async fn select_deliveries(
+        &self,
+        id: u64,
+        req: &DeliveryQuery,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<(Vec<WebhookDeliveryVo>, i64), AppError> {
+        self.find(id, scope).await?;
+        let mut filter = Filter::new().eq("webhook_id", id);
+        if let Some(status) = req.status {
+            filter = filter.eq("status", status.to_string());
+        }
+        let query = PageQuery::new(req.page, req.size)
+            .filter(filter)
+            .sort(vec![Sort::desc("id")]);
+        let deliveries = self.delivery_repo.select_page(&query).await?;
+        let list = deliveries.0.iter().map(WebhookDeliveryVo::from).collect();
+        Ok((list, deliveries.1))
+    }
+
+    /// 重新投递：以原请求体新建一条待投递记录，事件id不变，接收方可据此去重
+    pub async fn redeliver(
+        &self,
+        delivery_id: u64,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<u64, AppError> {
+        let original = self
+            .delivery_repo
+            .select_by_id(delivery_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("webhook delivery not found".into()))?;
+        let webhook = self.find(original.webhook_id, scope).await?;
+        if !webhook.is_active {
+            return Err(AppError::BusinessError("webhook is disabled".into()));
+        }
+        let mut delivery = WebhookDelivery::pending(
+            original.webhook_id,
+            &original.event_id,
+            &original.event,
+            original.payload,
+        );
+        delivery.redelivery_of = Some(original.id);
+        self.delivery_repo.insert(&delivery).await
+    }
+
+    async fn find(&self, id: u64, scope: Option<&ApiTokenScope>) -> Result<Webhook, AppError> {
+        let webhook = self
+            .repo
+            .select_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("webhook not found".into()))?;
+        check_scope(scope, webhook.project_id)?;
+        Ok(webhook)
+    }
+
+    /// 按固定间隔投递到期的记录
+    pub async fn run_delivery_schedule(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(WEBHOOK_POLL_SECONDS));
+        loop {
+            interval.tick().await;
+            match self.deliver_due().await {
+                Ok(0) => {}
+                Ok(count) => info!("Webhook 投递完成，共 {} 条", count),
+                Err(e) => error!("Webhook 投递失败: {}", e),
+            }
+        }
+    }
+
+    /// 领取并并发投递到期的记录，返回投递条数
+    pub async fn deliver_due(&self) -> Result<usize, AppError> {
+        let now = Local::now().naive_local();
+        let lease_until = now + chrono::Duration::seconds(WEBHOOK_LEASE_SECONDS);
+        let mut claimed = Vec::new();
+        for delivery in self.delivery_repo.find_due(now, WEBHOOK_BATCH_SIZE).await? {
+            if self.delivery_repo.claim(delivery.id, now, lease_until).await? {
+                claimed.push(delivery);
+            }
+        }
+        let count = claimed.len();
+        for result in join_all(claimed.into_iter().map(|d| self.deliver(d))).await {
+            if let Err(e) = result {
+                warn!("Webhook 投递结果保存失败: {}", e);
+            }
+        }
+        Ok(count)
+    }
+
+    /// 发送一次并记录结果：成功、按指数退避安排重试，或重试次数用尽后标记失败
+    async fn deliver(&self, mut delivery: WebhookDelivery) -> Result<(), AppError> {
+        delivery.attempts += 1;
+        delivery.response_status = None;
+        delivery.response_body = None;
+        delivery.error = None;
+        match self.repo.select_by_id(delivery.webhook_id).await? {
+            Some(webhook) if webhook.is_active => self.send(&webhook, &mut delivery).await,
+            _ => {
+                // Webhook 已删除或停用，不再重试
+                delivery.error = Some("webhook is deleted or disabled".into());
+                delivery.attempts = delivery.attempts.max(WEBHOOK_MAX_ATTEMPTS);
+            }
+        }
+
+        let now = Local::now().naive_local();
+        if delivery.error.is_none() {
+            delivery.status = DeliveryStatus::Delivered;
+            delivery.delivered_at = Some(now);
+            delivery.next_attempt_at = None;
+        } else if delivery.attempts >= WEBHOOK_MAX_ATTEMPTS {
+            delivery.status = DeliveryStatus::Failed;
+            delivery.next_attempt_at = None;
+        } else {
+            let backoff = webhook::backoff_seconds(delivery.attempts) as i64;
+            delivery.next_attempt_at = Some(now + chrono::Duration::seconds(backoff));
+        }
+        delivery.upt_at = now;
+        self.delivery_repo.record_attempt(&delivery).await?;
+        Ok(())
+    }
+
+    async fn send(&self, webhook: &Webhook, delivery: &mut WebhookDelivery) {
+        // 每次发送前重新解析并校验地址，连接固定到校验过的地址
+        let client = match http_client(&webhook.url).await {
+            Ok(client) => client,
+            Err(e) => {
+                delivery.error = Some(text::truncate(&e.to_string(), 1000));
+                return;
+            }
+        };
+        let timestamp = Utc::now().timestamp();
+        let result = client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                webhook::sign(&webhook.secret, timestamp, &delivery.payload),
+            )
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                delivery.response_status = Some(i32::from(status.as_u16()));
+                delivery.response_body = Some(read_excerpt(response).await);
+                if !status.is_success() {
+                    delivery.error = Some(format!("unexpected response status {}", status));
+                }
+            }
+            Err(e) => delivery.error = Some(text::truncate(&e.to_string(), 1000)),
+        }
+    }
+}
+
+/// 只读取响应内容的开头部分，避免接收方返回超大响应占用内存
+async fn read_excerpt(mut response: reqwest::Response) -> String {
+    // UTF-8 字符最多 4 字节
+    let limit = WEBHOOK_RESPONSE_MAX_CHARS * 4;
+    let mut body = Vec::with_capacity(limit);
+    while body.len() < limit {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                let take = chunk.len().min(limit - body.len());
+                body.extend_from_slice(&chunk[..take]);
+            }
+            Ok(None) | Err(_) => break,
+        }
+    }
+    webhook::excerpt(&String::from_utf8_lossy(&body), WEBHOOK_RESPONSE_MAX_CHARS)
+}
+
+#[async_trait]
+impl BaseService<Webhook> for WebhookService {
+    type Repository = WebhookRepository;
+
+    fn get_repository(&self) -> &Self::Repository {
+        &self.repo
+    }
+}
+
+/// 在业务事务中写入项目事件的投递记录，事务提交后由投递任务异步发送，回滚时一并撤销
+pub struct WebhookDispatcher {
+    webhook_repo: WebhookRepository,
+    delivery_repo: WebhookDeliveryRepository,
+}
+
+impl WebhookDispatcher {
+    pub fn new(pool: Arc<MySqlPool>) -> Self {
+        Self {
+            webhook_repo: WebhookRepository::new(pool.clone()),
+            delivery_repo: WebhookDeliveryRepository::new(pool),
+        }
+    }
+
+    /// 为订阅了该事件的 Webhook 各写入一条投递记录，返回记录条数
+    pub async fn dispatch_with(
+        &self,
+        conn: &mut MySqlConnection,
+        project_id: u64,
+        event: &str,
+        data: Value,
+    ) -> Result<usize, AppError> {
+        let webhooks: Vec<Webhook> = self
+            .webhook_repo
+            .find_active_by_project_with(conn, project_id)
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.subscribes(event))
+            .collect();
+        if webhooks.is_empty() {
+            return Ok(0);
+        }
+        let event_id = Uuid::new_v4().to_string();
+        let payload = json!({
+            "id": event_id,
+            "event": event,
+            "project_id": project_id,
+            "occurred_at": Utc::now().to_rfc3339(),
+            "data": data,
+        })
+        .to_string();
+        for webhook in &webhooks {
+            let delivery = WebhookDelivery::pending(webhook.id, &event_id, event, payload.clone());
+            self.delivery_repo.insert_with(conn, &delivery).await?;
+        }
+        Ok(webhooks.len())
+    }
+}
+
+/// 接收地址必须是 http(s) 地址，且主机只解析到公网地址
+async fn check_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim();
+    let parsed = parse_url(url)?;
+    webhook::resolve_public(&parsed).await?;
+    Ok(url.to_string())
+}
+
+fn parse_url(url: &str) -> Result<Url, AppError> {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some() => {
+            Ok(parsed)
+        }
+        _ => Err(AppError::BadRequest(format!("Invalid webhook url: {}", url))),
+    }
+}
+
+/// 发送用的客户端：主机固定解析到校验过的公网地址，不跟随重定向，非 2xx 响应都视为投递失败
+async fn http_client(url: &str) -> Result<reqwest::Client, AppError> {
+    let url = parse_url(url)?;
+    let addrs = webhook::resolve_public(&url).await?;
+    let host = url.host_str().unwrap_or_default();
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+        .redirect(redirect::Policy::none())
+        .no_proxy()
+        .resolve_to_addrs(host, &addrs)
+        .build()
+        .map_err(|e| AppError::Internal(format!("failed to build webhook http client: {}", e)))
+}
+
+/// 事件类型必须受支持，去重后保持原有顺序
+fn check_events(events: &[String]) -> Result<Vec<String>, AppError> {
+    let mut checked: Vec<String> = Vec::with_capacity(events.len());
+    for event in events {
+        if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported webhook event: {}",
+                event
+            )));
+        }
+        if !checked.contains(event) {
+            checked.push(event.clone());
+        }
+    }
+    if checked.is_empty() {
+        return Err(AppError::BadRequest("At least one event is required".into()));
+    }
+    Ok(checked)
+}
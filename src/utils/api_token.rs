@@ -0,0 +1,135 @@
+use actix_web::{HttpMessage, HttpRequest};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+use crate::errors::AppError;
+use crate::models::enums::TokenPermission;
+
+const TOKEN_SCHEME: &str = "i18n";
+const PREFIX_LENGTH: usize = 8;
+const SECRET_BYTES: usize = 32;
+
+/// 生成新的 API 令牌，返回 (前缀, 完整令牌)
+///
+/// 令牌格式为 `i18n_<前缀>_<密钥>`，前缀明文保存用于查找，密钥只保存哈希
+pub fn generate() -> (String, String) {
+    let mut prefix_bytes = [0u8; PREFIX_LENGTH / 2];
+    OsRng.fill_bytes(&mut prefix_bytes);
+    let prefix: String = prefix_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let mut secret_bytes = [0u8; SECRET_BYTES];
+    OsRng.fill_bytes(&mut secret_bytes);
+    let secret = URL_SAFE_NO_PAD.encode(secret_bytes);
+
+    let token = format!("{}_{}_{}", TOKEN_SCHEME, prefix, secret);
+    (prefix, token)
+}
+
+/// 拆分令牌，返回 (前缀, 密钥)
+pub fn parse(token: &str) -> Option<(&str, &str)> {
+    let rest = token.strip_prefix(TOKEN_SCHEME)?.strip_prefix('_')?;
+    let (prefix, secret) = rest.split_once('_')?;
+    if prefix.len() != PREFIX_LENGTH || secret.is_empty() {
+        return None;
+    }
+    Some((prefix, secret))
+}
+
+/// API 令牌的访问范围，令牌认证时由中间件写入请求扩展
+#[derive(Debug, Clone)]
+pub struct ApiTokenScope {
+    pub token_id: u64,
+    pub project_ids: Option<Vec<u64>>,
+    pub permission: TokenPermission,
+}
+
+impl ApiTokenScope {
+    pub fn allows_project(&self, project_id: u64) -> bool {
+        match &self.project_ids {
+            Some(ids) => ids.contains(&project_id),
+            None => true,
+        }
+    }
+
+    pub fn allows_write(&self) -> bool {
+        self.permission == TokenPermission::Write
+    }
+}
+
+/// 获取当前请求的令牌范围，JWT 认证的请求返回 None
+pub fn get_scope(req: &HttpRequest) -> Option<ApiTokenScope> {
+    req.extensions().get::<ApiTokenScope>().cloned()
+}
+
+/// 校验当前请求能否访问指定项目
+pub fn check_project(req: &HttpRequest, project_id: u64) -> Result<(), AppError> {
+    check_scope(get_scope(req).as_ref(), project_id)
+}
+
+/// 语言、用户等全局资源不属于任何项目，不允许通过 API 令牌访问
+pub fn check_global(req: &HttpRequest) -> Result<(), AppError> {
+    match get_scope(req) {
+        Some(_) => Err(AppError::Forbidden(
+            "API tokens cannot access global resources".into(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 校验令牌范围能否访问指定项目，None 表示 JWT 认证，不限制
+pub fn check_scope(scope: Option<&ApiTokenScope>, project_id: u64) -> Result<(), AppError> {
+    match scope {
+        Some(scope) if !scope.allows_project(project_id) => Err(AppError::Forbidden(
+            "API token is not allowed to access this project".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_and_parse() {
+        let (prefix, token) = generate();
+        assert_eq!(prefix.len(), PREFIX_LENGTH);
+
+        let (parsed_prefix, secret) = parse(&token).unwrap();
+        assert_eq!(parsed_prefix, prefix);
+        assert!(!secret.is_empty());
+
+        // 两次生成的令牌不同
+        let (_, other) = generate();
+        assert_ne!(token, other);
+    }
+
+    #[test]
+    fn test_scope() {
+        let scope = ApiTokenScope {
+            token_id: 1,
+            project_ids: Some(vec![1, 2]),
+            permission: TokenPermission::Read,
+        };
+        assert!(scope.allows_project(1));
+        assert!(!scope.allows_project(3));
+        assert!(!scope.allows_write());
+
+        let scope = ApiTokenScope {
+            token_id: 1,
+            project_ids: None,
+            permission: TokenPermission::Write,
+        };
+        assert!(scope.allows_project(3));
+        assert!(scope.allows_write());
+    }
+
+    #[test]
+    fn test_parse_invalid_token() {
+        assert!(parse("").is_none());
+        assert!(parse("Bearer abc").is_none());
+        assert!(parse("i18n_short_secret").is_none());
+        assert!(parse("i18n_0123abcd_").is_none());
+        assert!(parse("xxxx_0123abcd_secret").is_none());
+    }
+}
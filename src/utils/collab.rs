@@ -0,0 +1,51 @@
+use crate::constants::{COLLAB_CHANNEL_PREFIX, COLLAB_LOCK_PREFIX, COLLAB_PRESENCE_PREFIX};
+
+/// 项目协作消息的 Redis 发布订阅频道
+pub fn channel(project_id: u64) -> String {
+    format!("{}{}", COLLAB_CHANNEL_PREFIX, project_id)
+}
+
+/// 订阅全部项目频道的模式
+pub fn channel_pattern() -> String {
+    format!("{}*", COLLAB_CHANNEL_PREFIX)
+}
+
+/// 从频道名解析项目ID
+pub fn project_id_of(channel: &str) -> Option<u64> {
+    channel.strip_prefix(COLLAB_CHANNEL_PREFIX)?.parse().ok()
+}
+
+/// 项目在线状态的 Hash，字段为会话ID
+pub fn presence_key(project_id: u64) -> String {
+    format!("{}{}", COLLAB_PRESENCE_PREFIX, project_id)
+}
+
+/// 词条软锁
+pub fn lock_key(project_id: u64, phrase_id: u64) -> String {
+    format!("{}{}_{}", COLLAB_LOCK_PREFIX, project_id, phrase_id)
+}
+
+/// 匹配项目全部软锁的模式
+pub fn lock_pattern(project_id: u64) -> String {
+    format!("{}{}_*", COLLAB_LOCK_PREFIX, project_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel() {
+        assert_eq!(project_id_of(&channel(42)), Some(42));
+        assert_eq!(project_id_of("collab_channel_x"), None);
+        assert_eq!(project_id_of("other_42"), None);
+    }
+
+    #[test]
+    fn test_lock_key() {
+        assert_eq!(lock_key(1, 23), "collab_lock_1_23");
+        assert_eq!(lock_pattern(1), "collab_lock_1_*");
+        // 项目 1 的模式不会匹配项目 12 的软锁
+        assert!(!lock_key(12, 3).starts_with(lock_pattern(1).trim_end_matches('*')));
+    }
+}
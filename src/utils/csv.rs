@@ -0,0 +1,50 @@
+/// 生成 CSV 文本（RFC 4180），首行为表头，行尾使用 CRLF
+pub fn to_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    write_row(&mut out, headers.iter().copied());
+    for row in rows {
+        write_row(&mut out, row.iter().map(String::as_str));
+    }
+    out
+}
+
+fn write_row<'a>(out: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&escape(field));
+    }
+    out.push_str("\r\n");
+}
+
+/// 转义单个字段；以 = + - @ 开头的文本加前缀 '，防止在表格软件中被当作公式执行
+fn escape(field: &str) -> String {
+    let field = match field.chars().next() {
+        Some('=' | '+' | '-' | '@') if field.parse::<f64>().is_err() => format!("'{}", field),
+        _ => field.to_string(),
+    };
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv() {
+        let rows = vec![
+            vec!["alice".to_string(), "12".to_string()],
+            vec!["bob, \"jr\"".to_string(), "-1.5".to_string()],
+            vec!["=SUM(A1)".to_string(), "line\nbreak".to_string()],
+        ];
+        assert_eq!(
+            to_csv(&["user", "value"], &rows),
+            "user,value\r\nalice,12\r\n\"bob, \"\"jr\"\"\",-1.5\r\n'=SUM(A1),\"line\nbreak\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use actix_web::{http::header, HttpRequest};
+
+use crate::errors::AppError;
+
+/// 根据版本号生成 ETag，格式为 `"<版本号>"`
+pub fn from_version(version: u32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// 解析 ETag 中的版本号，兼容弱校验前缀 `W/`
+pub fn parse_version(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .parse()
+        .ok()
+}
+
+/// 读取请求中的 If-Match 版本号，未携带时返回 None
+pub fn if_match(req: &HttpRequest) -> Result<Option<u32>, AppError> {
+    let value = match req.headers().get(header::IF_MATCH) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".into()))?;
+    if value.trim() == "*" {
+        return Ok(None);
+    }
+    parse_version(value)
+        .map(Some)
+        .ok_or_else(|| AppError::BadRequest("Invalid If-Match header".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version(&from_version(7)), Some(7));
+        assert_eq!(parse_version("W/\"3\""), Some(3));
+        assert_eq!(parse_version(" 12 "), Some(12));
+        assert_eq!(parse_version("\"abc\""), None);
+        assert_eq!(parse_version(""), None);
+    }
+}
@@ -0,0 +1,220 @@
+use regex::Regex;
+use serde::Serialize;
+
+/// 词条标识符违反的规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyViolation {
+    Pattern,
+    ModulePrefix,
+    MaxDepth,
+    ForbiddenChars,
+}
+
+impl KeyViolation {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            KeyViolation::Pattern => "does not match the key pattern",
+            KeyViolation::ModulePrefix => "must start with the module prefix",
+            KeyViolation::MaxDepth => "exceeds the maximum depth",
+            KeyViolation::ForbiddenChars => "contains forbidden characters",
+        }
+    }
+}
+
+/// 编译后的词条标识符规则
+pub struct KeyChecker {
+    // 已加上首尾锚点，要求完整匹配
+    pattern: Option<Regex>,
+    separator: String,
+    max_depth: Option<usize>,
+    forbidden_chars: Vec<char>,
+}
+
+impl KeyChecker {
+    pub fn new(
+        pattern: Option<&str>,
+        separator: &str,
+        max_depth: Option<u32>,
+        forbidden_chars: Option<&str>,
+    ) -> Result<Self, regex::Error> {
+        let pattern = pattern
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| Regex::new(&format!("^(?:{})$", pattern)))
+            .transpose()?;
+        Ok(Self {
+            pattern,
+            separator: separator.to_string(),
+            max_depth: max_depth.map(|depth| depth as usize),
+            forbidden_chars: forbidden_chars.unwrap_or_default().chars().collect(),
+        })
+    }
+
+    /// 检查标识符，prefix 为要求的模块前缀，返回违反的规则
+    pub fn check(&self, key: &str, prefix: Option<&str>) -> Vec<KeyViolation> {
+        let mut violations = Vec::new();
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(key) {
+                violations.push(KeyViolation::Pattern);
+            }
+        }
+        if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+            let rest = key
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix(self.separator.as_str()));
+            if rest.is_none_or(str::is_empty) {
+                violations.push(KeyViolation::ModulePrefix);
+            }
+        }
+        if self.max_depth.is_some_and(|max| self.depth(key) > max) {
+            violations.push(KeyViolation::MaxDepth);
+        }
+        if key.chars().any(|c| self.forbidden_chars.contains(&c)) {
+            violations.push(KeyViolation::ForbiddenChars);
+        }
+        violations
+    }
+
+    /// 建议的新标识符：补全模块前缀、替换禁用字符、折叠超出的层级，
+    /// 仍不符合正则时再尝试转为小写下划线形式；都不符合规则时返回 None
+    pub fn suggest(&self, key: &str, prefix: Option<&str>) -> Option<String> {
+        let prefix = prefix.filter(|prefix| !prefix.is_empty());
+        let rest = self.strip_prefix(key.trim(), prefix);
+        [false, true]
+            .into_iter()
+            .map(|snake_case| self.join(prefix, &self.normalize(&rest, prefix, snake_case)))
+            .find(|candidate| candidate != key && self.check(candidate, prefix).is_empty())
+    }
+
+    fn depth(&self, key: &str) -> usize {
+        key.split(self.separator.as_str()).count()
+    }
+
+    /// 去掉标识符开头与模块前缀相同（忽略大小写）的层级
+    fn strip_prefix(&self, key: &str, prefix: Option<&str>) -> String {
+        let mut segments: Vec<&str> = key.split(self.separator.as_str()).collect();
+        if let Some(prefix) = prefix {
+            for expected in prefix.split(self.separator.as_str()) {
+                if segments.len() > 1 && segments[0].eq_ignore_ascii_case(expected) {
+                    segments.remove(0);
+                } else {
+                    break;
+                }
+            }
+        }
+        segments.join(&self.separator)
+    }
+
+    fn normalize(&self, key: &str, prefix: Option<&str>, snake_case: bool) -> String {
+        let mut segments: Vec<String> = key
+            .split(self.separator.as_str())
+            .map(|segment| self.normalize_segment(segment, snake_case))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        if let Some(max) = self.max_depth {
+            let keep = max.saturating_sub(prefix.map_or(0, |prefix| self.depth(prefix)));
+            if keep > 0 && segments.len() > keep {
+                let tail = segments.split_off(keep - 1).join("_");
+                segments.push(tail);
+            }
+        }
+        segments.join(&self.separator)
+    }
+
+    fn normalize_segment(&self, segment: &str, snake_case: bool) -> String {
+        let replacement = (!self.forbidden_chars.contains(&'_')).then_some('_');
+        let mut result = String::new();
+        let mut previous: Option<char> = None;
+        for c in segment.chars() {
+            if snake_case && c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric()) {
+                result.extend(replacement);
+            }
+            if self.forbidden_chars.contains(&c) || (snake_case && !c.is_alphanumeric() && c != '_') {
+                result.extend(replacement);
+            } else if snake_case {
+                result.extend(c.to_lowercase());
+            } else {
+                result.push(c);
+            }
+            previous = Some(c);
+        }
+        // 合并连续的下划线并去掉首尾的下划线
+        let mut collapsed = String::with_capacity(result.len());
+        for c in result.chars() {
+            if !(c == '_' && collapsed.ends_with('_')) {
+                collapsed.push(c);
+            }
+        }
+        collapsed.trim_matches('_').to_string()
+    }
+
+    fn join(&self, prefix: Option<&str>, rest: &str) -> String {
+        match prefix {
+            Some(prefix) => format!("{}{}{}", prefix, self.separator, rest),
+            None => rest.to_string(),
+        }
+    }
+}
+
+/// 模块要求的标识符前缀：模块路径（未设置时使用模块名称）按 "/" 切分后用分隔符连接
+pub fn module_prefix(path: Option<&str>, name: &str, separator: &str) -> String {
+    path.filter(|path| !path.trim().is_empty())
+        .unwrap_or(name)
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAKE_PATTERN: &str = r"[a-z0-9_]+(\.[a-z0-9_]+)*";
+
+    #[test]
+    fn test_check() {
+        let checker = KeyChecker::new(Some(SNAKE_PATTERN), ".", Some(3), Some(" -")).unwrap();
+        assert!(checker.check("home.title", None).is_empty());
+        assert!(checker.check("home.title", Some("home")).is_empty());
+        assert_eq!(checker.check("Home.Title", None), vec![KeyViolation::Pattern]);
+        assert_eq!(checker.check("btn_ok", Some("home")), vec![KeyViolation::ModulePrefix]);
+        assert_eq!(checker.check("home.", Some("home")), vec![
+            KeyViolation::Pattern,
+            KeyViolation::ModulePrefix
+        ]);
+        assert_eq!(checker.check("a.b.c.d", None), vec![KeyViolation::MaxDepth]);
+        assert_eq!(checker.check("home-title", None), vec![
+            KeyViolation::Pattern,
+            KeyViolation::ForbiddenChars
+        ]);
+        // 正则要求完整匹配
+        assert_eq!(checker.check("title!", None), vec![KeyViolation::Pattern]);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let checker = KeyChecker::new(Some(SNAKE_PATTERN), ".", Some(3), Some(" -")).unwrap();
+        assert_eq!(checker.suggest("Home.Title", Some("home")).as_deref(), Some("home.title"));
+        assert_eq!(checker.suggest("btn_ok", Some("home")).as_deref(), Some("home.btn_ok"));
+        assert_eq!(checker.suggest("home-title", None).as_deref(), Some("home_title"));
+        assert_eq!(checker.suggest("saveButton", None).as_deref(), Some("save_button"));
+        assert_eq!(checker.suggest("home.a.b.c", Some("home")).as_deref(), Some("home.a.b_c"));
+        // 前缀本身不符合规则时无法给出建议
+        assert_eq!(checker.suggest("title", Some("Home")), None);
+
+        // 以下划线为分隔符时无法折叠层级
+        let checker = KeyChecker::new(None, "_", Some(2), None).unwrap();
+        assert_eq!(checker.check("btn_ok_cancel", None), vec![KeyViolation::MaxDepth]);
+        assert_eq!(checker.suggest("btn_ok_cancel", None), None);
+    }
+
+    #[test]
+    fn test_module_prefix() {
+        assert_eq!(module_prefix(Some("checkout/cart"), "Cart", "."), "checkout.cart");
+        assert_eq!(module_prefix(Some("/checkout/ "), "Checkout", "."), "checkout");
+        assert_eq!(module_prefix(None, "home", "."), "home");
+        assert_eq!(module_prefix(Some(""), "home", "_"), "home");
+    }
+}
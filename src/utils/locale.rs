@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+use std::fmt;
+
+// 数据库中语言代码列的长度
+pub const LANGUAGE_TAG_MAX_LENGTH: usize = 35;
+
+// 从右向左书写的语言和文字
+const RTL_LANGUAGES: &[&str] = &["ar", "ckb", "dv", "fa", "he", "ps", "sd", "ug", "ur", "yi"];
+const RTL_SCRIPTS: &[&str] = &["Adlm", "Arab", "Hebr", "Nkoo", "Rohg", "Syrc", "Thaa"];
+
+// 已废弃的语言子标签
+const DEPRECATED_LANGUAGES: &[(&str, &str)] = &[("in", "id"), ("iw", "he"), ("ji", "yi"), ("jw", "jv")];
+
+/// BCP-47 语言标签，只支持 language[-script][-region]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageTag {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+}
+
+impl LanguageTag {
+    /// 解析并规范化大小写：`ZH_hant_tw` → `zh-Hant-TW`
+    pub fn parse(code: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid language tag: {}", code);
+        if code.is_empty() || code.len() > LANGUAGE_TAG_MAX_LENGTH {
+            return Err(invalid());
+        }
+        let mut subtags = code.split(['-', '_']);
+
+        let language = subtags.next().unwrap_or_default();
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(invalid());
+        }
+        let mut language = language.to_ascii_lowercase();
+        if let Some((_, preferred)) = DEPRECATED_LANGUAGES.iter().find(|(old, _)| *old == language) {
+            language = preferred.to_string();
+        }
+
+        let mut tag = LanguageTag {
+            language,
+            script: None,
+            region: None,
+        };
+        for subtag in subtags {
+            let alphabetic = subtag.chars().all(|c| c.is_ascii_alphabetic());
+            let numeric = subtag.chars().all(|c| c.is_ascii_digit());
+            if subtag.len() == 4 && alphabetic && tag.script.is_none() && tag.region.is_none() {
+                let mut script = subtag.to_ascii_lowercase();
+                script[..1].make_ascii_uppercase();
+                tag.script = Some(script);
+            } else if ((subtag.len() == 2 && alphabetic) || (subtag.len() == 3 && numeric))
+                && tag.region.is_none()
+            {
+                tag.region = Some(subtag.to_ascii_uppercase());
+            } else {
+                return Err(invalid());
+            }
+        }
+        Ok(tag)
+    }
+
+    /// 文字方向：ltr / rtl
+    pub fn direction(&self) -> &'static str {
+        let rtl = match self.script.as_deref() {
+            Some(script) => RTL_SCRIPTS.contains(&script),
+            None => RTL_LANGUAGES.contains(&self.language.as_str()),
+        };
+        if rtl {
+            "rtl"
+        } else {
+            "ltr"
+        }
+    }
+
+    /// CLDR 基数复数类别
+    pub fn plural_categories(&self) -> &'static [&'static str] {
+        match self.language.as_str() {
+            "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" | "lo" | "my" | "km" | "yue" => &["other"],
+            "fr" | "pt" | "it" | "es" | "ca" => &["one", "many", "other"],
+            "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "lt" => &["one", "few", "many", "other"],
+            "hr" | "sr" | "bs" | "ro" => &["one", "few", "other"],
+            "lv" => &["zero", "one", "other"],
+            "sl" => &["one", "two", "few", "other"],
+            "he" => &["one", "two", "other"],
+            "ga" => &["one", "two", "few", "many", "other"],
+            "cy" | "ar" => &["zero", "one", "two", "few", "many", "other"],
+            _ => &["one", "other"],
+        }
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        if let Some(script) = &self.script {
+            write!(f, "-{}", script)?;
+        }
+        if let Some(region) = &self.region {
+            write!(f, "-{}", region)?;
+        }
+        Ok(())
+    }
+}
+
+/// 规范化语言代码，无法解析时返回错误
+pub fn canonicalize(code: &str) -> Result<String, String> {
+    LanguageTag::parse(code).map(|tag| tag.to_string())
+}
+
+/// BCP-47 父级区域设置，去掉最后一个子标签：`zh-Hant-TW` → `zh-Hant`，`pt` → None
+pub fn parent(code: &str) -> Option<String> {
+    code.rsplit_once('-').map(|(parent, _)| parent.to_string())
+}
+
+/// 展开语言的回退链：依次为语言本身、各回退语言（递归展开各自的回退链）、最后是基础语言
+///
+/// `fallbacks` 返回语言直接配置的回退语言，未配置时应返回 BCP-47 父级；出现循环时返回错误
+pub fn fallback_chain<F>(language: &str, base_language: &str, fallbacks: F) -> Result<Vec<String>, String>
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    fn visit<F: Fn(&str) -> Vec<String>>(
+        language: &str,
+        fallbacks: &F,
+        path: &mut Vec<String>,
+        chain: &mut Vec<String>,
+        seen: &mut HashSet<String>,
+    ) -> Result<(), String> {
+        if path.iter().any(|l| l == language) {
+            path.push(language.to_string());
+            return Err(format!("Fallback cycle detected: {}", path.join(" → ")));
+        }
+        if seen.insert(language.to_string()) {
+            chain.push(language.to_string());
+        }
+        path.push(language.to_string());
+        for fallback in fallbacks(language) {
+            visit(&fallback, fallbacks, path, chain, seen)?;
+        }
+        path.pop();
+        Ok(())
+    }
+
+    let mut chain = Vec::new();
+    let mut seen = HashSet::new();
+    visit(language, &fallbacks, &mut Vec::new(), &mut chain, &mut seen)?;
+    if seen.insert(base_language.to_string()) {
+        chain.push(base_language.to_string());
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults(language: &str) -> Vec<String> {
+        parent(language).into_iter().collect()
+    }
+
+    #[test]
+    fn test_parse_language_tag() {
+        assert_eq!(canonicalize("ZH_hant_tw").unwrap(), "zh-Hant-TW");
+        assert_eq!(canonicalize("sr-latn").unwrap(), "sr-Latn");
+        assert_eq!(canonicalize("es-419").unwrap(), "es-419");
+        assert_eq!(canonicalize("iw").unwrap(), "he");
+        assert!(canonicalize("english").is_err());
+        assert!(canonicalize("en-US-Latn").is_err());
+        assert!(canonicalize("en-").is_err());
+        assert!(canonicalize("").is_err());
+    }
+
+    #[test]
+    fn test_language_metadata() {
+        let tag = |code| LanguageTag::parse(code).unwrap();
+        assert_eq!(tag("ar-EG").direction(), "rtl");
+        assert_eq!(tag("az-Arab").direction(), "rtl");
+        assert_eq!(tag("ku-Latn").direction(), "ltr");
+        assert_eq!(tag("en").direction(), "ltr");
+        assert_eq!(tag("zh-Hant-TW").plural_categories(), &["other"]);
+        assert_eq!(tag("ru").plural_categories(), &["one", "few", "many", "other"]);
+        assert_eq!(tag("en-GB").plural_categories(), &["one", "other"]);
+    }
+
+    #[test]
+    fn test_parent() {
+        assert_eq!(parent("zh-Hant-TW").as_deref(), Some("zh-Hant"));
+        assert_eq!(parent("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(parent("pt"), None);
+    }
+
+    #[test]
+    fn test_default_chain() {
+        assert_eq!(
+            fallback_chain("zh-Hant-TW", "en", defaults).unwrap(),
+            vec!["zh-Hant-TW", "zh-Hant", "zh", "en"]
+        );
+        assert_eq!(fallback_chain("en-GB", "en", defaults).unwrap(), vec!["en-GB", "en"]);
+    }
+
+    #[test]
+    fn test_configured_chain() {
+        let configured = |language: &str| match language {
+            "pt-BR" => vec!["pt".to_string(), "es".to_string()],
+            "es" => vec!["en".to_string()],
+            other => defaults(other),
+        };
+        assert_eq!(
+            fallback_chain("pt-BR", "zh", configured).unwrap(),
+            vec!["pt-BR", "pt", "es", "en", "zh"]
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let configured = |language: &str| match language {
+            "pt-BR" => vec!["pt".to_string()],
+            "pt" => vec!["pt-BR".to_string()],
+            _ => vec![],
+        };
+        assert_eq!(
+            fallback_chain("pt-BR", "en", configured).unwrap_err(),
+            "Fallback cycle detected: pt-BR → pt → pt-BR"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use redis::AsyncCommands;
+
+use crate::constants::{
+    LOGIN_DELAY_BASE_MS, LOGIN_DELAY_MAX_MS, LOGIN_FAIL_IP_PREFIX, LOGIN_FAIL_USER_PREFIX,
+    LOGIN_IP_MAX_FAILED_ATTEMPTS, LOGIN_LOCK_SECONDS, LOGIN_MAX_FAILED_ATTEMPTS,
+    LOGIN_TOO_MANY_ATTEMPTS,
+};
+use crate::errors::AppError;
+
+/// 基于 Redis 的登录失败计数器，按账号和IP分别计数
+#[derive(Clone)]
+pub struct LoginGuard {
+    client: redis::Client,
+}
+
+impl LoginGuard {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+
+    /// 账号或IP已被锁定时返回错误
+    pub async fn check(&self, account: &str, ip: &str) -> Result<(), AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let account_failures: Option<u64> = conn.get(account_key(account)).await?;
+        let ip_failures: Option<u64> = conn.get(ip_key(ip)).await?;
+        if account_failures.unwrap_or(0) >= LOGIN_MAX_FAILED_ATTEMPTS
+            || ip_failures.unwrap_or(0) >= LOGIN_IP_MAX_FAILED_ATTEMPTS
+        {
+            return Err(AppError::Auth(LOGIN_TOO_MANY_ATTEMPTS.into()));
+        }
+        Ok(())
+    }
+
+    /// 记录一次失败，返回该账号当前的连续失败次数
+    pub async fn record_failure(&self, account: &str, ip: &str) -> Result<u64, AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let account_failures: u64 = conn.incr(account_key(account), 1).await?;
+        conn.expire::<_, ()>(account_key(account), LOGIN_LOCK_SECONDS as i64)
+            .await?;
+        let _: u64 = conn.incr(ip_key(ip), 1).await?;
+        conn.expire::<_, ()>(ip_key(ip), LOGIN_LOCK_SECONDS as i64)
+            .await?;
+        Ok(account_failures)
+    }
+
+    /// 清除账号的失败计数（登录成功或管理员解锁）
+    pub async fn reset(&self, account: &str) -> Result<(), AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        conn.del::<_, ()>(account_key(account)).await?;
+        Ok(())
+    }
+}
+
+fn account_key(account: &str) -> String {
+    format!("{}{}", LOGIN_FAIL_USER_PREFIX, account.to_lowercase())
+}
+
+fn ip_key(ip: &str) -> String {
+    format!("{}{}", LOGIN_FAIL_IP_PREFIX, ip)
+}
+
+/// 失败后的递增延迟：基础延迟按失败次数翻倍，不超过上限
+pub fn progressive_delay(failures: u64) -> Duration {
+    if failures == 0 {
+        return Duration::from_millis(0);
+    }
+    let shift = (failures - 1).min(16) as u32;
+    let delay = LOGIN_DELAY_BASE_MS.saturating_mul(1u64 << shift);
+    Duration::from_millis(delay.min(LOGIN_DELAY_MAX_MS))
+}
+
+/// 账号达到失败上限后应锁定的时长
+pub fn lock_duration(failures: u64) -> Option<Duration> {
+    if failures >= LOGIN_MAX_FAILED_ATTEMPTS {
+        Some(Duration::from_secs(LOGIN_LOCK_SECONDS))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_delay() {
+        assert_eq!(progressive_delay(0), Duration::from_millis(0));
+        assert_eq!(progressive_delay(1), Duration::from_millis(LOGIN_DELAY_BASE_MS));
+        assert_eq!(progressive_delay(2), Duration::from_millis(LOGIN_DELAY_BASE_MS * 2));
+        assert_eq!(progressive_delay(3), Duration::from_millis(LOGIN_DELAY_BASE_MS * 4));
+        // 超过上限后保持最大延迟
+        assert_eq!(progressive_delay(100), Duration::from_millis(LOGIN_DELAY_MAX_MS));
+    }
+
+    #[test]
+    fn test_lock_duration() {
+        assert!(lock_duration(LOGIN_MAX_FAILED_ATTEMPTS - 1).is_none());
+        assert_eq!(
+            lock_duration(LOGIN_MAX_FAILED_ATTEMPTS),
+            Some(Duration::from_secs(LOGIN_LOCK_SECONDS))
+        );
+    }
+
+    #[test]
+    fn test_account_key_is_case_insensitive() {
+        assert_eq!(account_key("Admin"), account_key("admin"));
+        assert_ne!(account_key("admin"), ip_key("admin"));
+    }
+}
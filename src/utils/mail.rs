@@ -0,0 +1,133 @@
+use std::{path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Local;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{config::Mail, errors::AppError};
+
+/// 纯文本邮件
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// 邮件发送方式，由 MAIL_SENDER 配置选择
+#[async_trait]
+pub trait MailSender: Send + Sync {
+    async fn send(&self, email: &Email) -> Result<(), AppError>;
+}
+
+/// 按配置创建邮件发送方式
+pub fn build_sender(config: &Mail) -> Result<Arc<dyn MailSender>, AppError> {
+    match config.sender.as_str() {
+        "log" => Ok(Arc::new(LogMailSender)),
+        "file" => Ok(Arc::new(FileMailSender {
+            from: config.from.clone(),
+            dir: PathBuf::from(&config.file_dir),
+        })),
+        "smtp" => Ok(Arc::new(SmtpMailSender::new(config)?)),
+        other => Err(AppError::Internal(format!("unknown mail sender: {}", other))),
+    }
+}
+
+fn build_message(from: &str, email: &Email) -> Result<Message, AppError> {
+    let from: Mailbox = from
+        .parse()
+        .map_err(|e| AppError::Internal(format!("invalid mail sender address: {}", e)))?;
+    let to: Mailbox = email
+        .to
+        .parse()
+        .map_err(|e| AppError::BadRequest(format!("invalid mail recipient address: {}", e)))?;
+    Message::builder()
+        .from(from)
+        .to(to)
+        .subject(email.subject.clone())
+        .header(ContentType::TEXT_PLAIN)
+        .body(email.body.clone())
+        .map_err(|e| AppError::Internal(format!("failed to build mail: {}", e)))
+}
+
+/// 只写日志，用于本地开发
+pub struct LogMailSender;
+
+#[async_trait]
+impl MailSender for LogMailSender {
+    async fn send(&self, email: &Email) -> Result<(), AppError> {
+        info!("邮件 -> {}: {}\n{}", email.to, email.subject, email.body);
+        Ok(())
+    }
+}
+
+/// 将邮件写入目录中的 .eml 文件，可用邮件客户端打开检查
+pub struct FileMailSender {
+    from: String,
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl MailSender for FileMailSender {
+    async fn send(&self, email: &Email) -> Result<(), AppError> {
+        let message = build_message(&self.from, email)?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("failed to create mail directory: {}", e)))?;
+        let file_name = format!(
+            "{}_{}.eml",
+            Local::now().format("%Y%m%d%H%M%S"),
+            Uuid::new_v4().simple()
+        );
+        tokio::fs::write(self.dir.join(file_name), message.formatted())
+            .await
+            .map_err(|e| AppError::Internal(format!("failed to write mail file: {}", e)))
+    }
+}
+
+/// 通过 SMTP 服务器发送，连接由连接池复用
+pub struct SmtpMailSender {
+    from: String,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailSender {
+    pub fn new(config: &Mail) -> Result<Self, AppError> {
+        let host = config
+            .smtp_host
+            .as_deref()
+            .ok_or_else(|| AppError::Internal("MAIL_SMTP_HOST is required for the smtp mail sender".into()))?;
+        let mut builder = if config.smtp_starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| AppError::Internal(format!("invalid smtp relay: {}", e)))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+        };
+        builder = builder.port(config.smtp_port);
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(Self {
+            from: config.from.clone(),
+            transport: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl MailSender for SmtpMailSender {
+    async fn send(&self, email: &Email) -> Result<(), AppError> {
+        let message = build_message(&self.from, email)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::Internal(format!("failed to send mail: {}", e)))?;
+        Ok(())
+    }
+}
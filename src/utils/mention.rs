@@ -0,0 +1,36 @@
+use regex::Regex;
+
+lazy_static::lazy_static! {
+    // @ 前不能是字母数字，避免把邮箱地址识别为提及
+    static ref MENTION_REGEX: Regex = Regex::new(r"(?:^|[^\w@])@([A-Za-z0-9_][A-Za-z0-9_.-]*)").unwrap();
+}
+
+/// 提取评论中 @ 提及的用户名，去重并保持出现顺序
+pub fn extract_mentions(content: &str) -> Vec<String> {
+    let mut usernames: Vec<String> = Vec::new();
+    for captures in MENTION_REGEX.captures_iter(content) {
+        // 句末的标点不属于用户名
+        let username = captures[1].trim_end_matches(['.', '-']);
+        if !username.is_empty() && !usernames.iter().any(|u| u == username) {
+            usernames.push(username.to_string());
+        }
+    }
+    usernames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_mentions() {
+        assert_eq!(
+            extract_mentions("@alice 请确认，抄送 @bob.li 和 @alice。"),
+            vec!["alice", "bob.li"]
+        );
+        assert_eq!(extract_mentions("Thanks @carol."), vec!["carol"]);
+        assert_eq!(extract_mentions("(@dave) 看一下"), vec!["dave"]);
+        assert!(extract_mentions("mail me at dev@example.com").is_empty());
+        assert!(extract_mentions("@@ @ 没有提及").is_empty());
+    }
+}
@@ -1,10 +1,27 @@
+pub mod api_token;
 pub mod jwt;
 pub mod kafka;
+pub mod login_guard;
+pub mod oidc;
 pub mod password;
+pub mod totp;
 pub mod database;
 pub mod redis;
 pub mod storage;
 pub mod response;
+pub mod search;
+pub mod stats_cache;
+pub mod text;
 pub mod base64;
+pub mod collab;
+pub mod csv;
+pub mod etag;
+pub mod key_rule;
+pub mod locale;
+pub mod mail;
+pub mod mention;
+pub mod module_tree;
+pub mod notification;
+pub mod webhook;
 
 pub use response::*;
\ No newline at end of file
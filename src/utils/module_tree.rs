@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::dtos::module::ModuleTreeNodeVo;
+
+/// 模块路径过滤条件："checkout" 只匹配该模块，"checkout/*" 匹配该模块及其所有子模块
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathFilter {
+    pub path: String,
+    pub subtree: bool,
+}
+
+impl PathFilter {
+    /// 解析过滤条件，路径为空时返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (value, subtree) = match value.strip_suffix("/*") {
+            Some(value) => (value, true),
+            None => (value, false),
+        };
+        let path = normalize(value);
+        (!path.is_empty()).then_some(Self { path, subtree })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        path == self.path || (self.subtree && is_descendant(path, &self.path))
+    }
+}
+
+/// 去掉路径中多余的 "/" 和空白
+pub fn normalize(path: &str) -> String {
+    path.split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn join(parent_path: Option<&str>, name: &str) -> String {
+    match parent_path {
+        Some(parent_path) => format!("{}/{}", parent_path, name),
+        None => name.to_string(),
+    }
+}
+
+/// path 是否为 ancestor 的子孙模块（不含自身）
+pub fn is_descendant(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// 将 path 中的 old_prefix 替换为 new_prefix，path 不在 old_prefix 下时返回 None
+pub fn rebase(path: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    if path == old_prefix {
+        return Some(new_prefix.to_string());
+    }
+    is_descendant(path, old_prefix).then(|| format!("{}{}", new_prefix, &path[old_prefix.len()..]))
+}
+
+/// 按 parent_id 组装模块树，汇总子模块的词条数和各语言进度；父模块不在列表中的作为根节点
+///
+/// 输入节点的 phrase_count 和 progress 为直接归属该模块的数据，各节点 progress 的语言顺序相同
+pub fn build_tree(nodes: Vec<ModuleTreeNodeVo>) -> Vec<ModuleTreeNodeVo> {
+    let ids: HashSet<u64> = nodes.iter().map(|node| node.id).collect();
+    let mut children: HashMap<Option<u64>, Vec<ModuleTreeNodeVo>> = HashMap::new();
+    for node in nodes {
+        let parent_id = node.parent_id.filter(|parent_id| ids.contains(parent_id));
+        children.entry(parent_id).or_default().push(node);
+    }
+    let roots = children.remove(&None).unwrap_or_default();
+    attach_all(roots, &mut children)
+}
+
+fn attach_all(
+    mut nodes: Vec<ModuleTreeNodeVo>,
+    children: &mut HashMap<Option<u64>, Vec<ModuleTreeNodeVo>>,
+) -> Vec<ModuleTreeNodeVo> {
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    nodes.into_iter().map(|node| attach(node, children)).collect()
+}
+
+fn attach(
+    mut node: ModuleTreeNodeVo,
+    children: &mut HashMap<Option<u64>, Vec<ModuleTreeNodeVo>>,
+) -> ModuleTreeNodeVo {
+    let own = children.remove(&Some(node.id)).unwrap_or_default();
+    node.children = attach_all(own, children);
+    node.total_phrase_count = node.phrase_count;
+    for child in &node.children {
+        node.total_phrase_count += child.total_phrase_count;
+        for (progress, child_progress) in node.progress.iter_mut().zip(&child.progress) {
+            progress.translated += child_progress.translated;
+            progress.reviewed += child_progress.reviewed;
+        }
+    }
+    for progress in &mut node.progress {
+        progress.percent = percent(progress.translated, node.total_phrase_count);
+    }
+    node
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 * 10000.0 / total as f64).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::module::ModuleProgressVo;
+
+    fn node(id: u64, parent_id: Option<u64>, name: &str, phrases: u64, translated: u64) -> ModuleTreeNodeVo {
+        ModuleTreeNodeVo {
+            id,
+            parent_id,
+            name: name.to_string(),
+            path: name.to_string(),
+            phrase_count: phrases,
+            total_phrase_count: 0,
+            progress: vec![ModuleProgressVo {
+                language: "en".to_string(),
+                translated,
+                reviewed: 0,
+                percent: 0.0,
+            }],
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn test_path_filter() {
+        let filter = PathFilter::parse(" checkout/* ").unwrap();
+        assert!(filter.subtree);
+        assert!(filter.matches("checkout"));
+        assert!(filter.matches("checkout/cart"));
+        assert!(!filter.matches("checkout2"));
+
+        let filter = PathFilter::parse("/checkout//cart").unwrap();
+        assert_eq!(filter.path, "checkout/cart");
+        assert!(!filter.subtree);
+        assert!(!filter.matches("checkout/cart/item"));
+
+        assert_eq!(PathFilter::parse("/*"), None);
+        assert_eq!(PathFilter::parse(""), None);
+    }
+
+    #[test]
+    fn test_rebase() {
+        assert_eq!(rebase("checkout", "checkout", "shop/checkout").as_deref(), Some("shop/checkout"));
+        assert_eq!(rebase("checkout/cart", "checkout", "pay").as_deref(), Some("pay/cart"));
+        assert_eq!(rebase("checkout2/cart", "checkout", "pay"), None);
+        assert!(is_descendant("a/b/c", "a/b"));
+        assert!(!is_descendant("a/b", "a/b"));
+        assert_eq!(join(Some("a/b"), "c"), "a/b/c");
+        assert_eq!(join(None, "c"), "c");
+    }
+
+    #[test]
+    fn test_build_tree() {
+        let tree = build_tree(vec![
+            node(3, Some(1), "cart", 4, 2),
+            node(1, None, "checkout", 2, 2),
+            node(4, Some(3), "item", 4, 4),
+            node(2, None, "account", 1, 0),
+            // 父模块不存在时作为根节点
+            node(5, Some(99), "orphan", 0, 0),
+        ]);
+        let names: Vec<&str> = tree.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, vec!["account", "checkout", "orphan"]);
+
+        let checkout = &tree[1];
+        assert_eq!(checkout.phrase_count, 2);
+        assert_eq!(checkout.total_phrase_count, 10);
+        assert_eq!(checkout.progress[0].translated, 8);
+        assert_eq!(checkout.progress[0].percent, 80.0);
+        let cart = &checkout.children[0];
+        assert_eq!(cart.total_phrase_count, 8);
+        assert_eq!(cart.progress[0].percent, 75.0);
+        assert_eq!(cart.children[0].progress[0].percent, 100.0);
+        assert_eq!(tree[0].progress[0].percent, 0.0);
+    }
+}
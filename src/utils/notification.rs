@@ -0,0 +1,96 @@
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+use crate::{
+    constants::NOTIFICATION_DIGEST_MAX_ITEMS,
+    models::{enums::NotificationKind, notification::Notification},
+};
+
+/// 今天是否应发送摘要：已到发送时间且今天尚未发送
+pub fn digest_due(now: NaiveDateTime, last_sent: Option<NaiveDate>, hour: u32) -> bool {
+    now.hour() >= hour && last_sent != Some(now.date())
+}
+
+fn kind_label(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::Assignment => "Assignment",
+        NotificationKind::ReviewResult => "Review",
+        NotificationKind::Mention => "Mention",
+        NotificationKind::Outdated => "Outdated",
+    }
+}
+
+/// 每日摘要邮件的标题和正文，通知按时间倒序，超出上限的只列出数量
+pub fn render_digest(username: &str, notifications: &[Notification]) -> (String, String) {
+    let subject = match notifications.len() {
+        1 => "You have 1 unread notification".to_string(),
+        n => format!("You have {} unread notifications", n),
+    };
+    let mut body = format!("Hi {},\n\nHere is what happened since your last digest:\n\n", username);
+    for notification in notifications.iter().take(NOTIFICATION_DIGEST_MAX_ITEMS) {
+        body.push_str(&format!(
+            "[{}] {} ({})\n",
+            kind_label(notification.kind),
+            notification.title,
+            notification.crt_at.format("%Y-%m-%d %H:%M")
+        ));
+        if let Some(content) = &notification.content {
+            body.push_str(&format!("    {}\n", content));
+        }
+    }
+    if notifications.len() > NOTIFICATION_DIGEST_MAX_ITEMS {
+        body.push_str(&format!(
+            "...and {} more\n",
+            notifications.len() - NOTIFICATION_DIGEST_MAX_ITEMS
+        ));
+    }
+    body.push_str("\nYou can turn off the daily digest in your notification preferences.\n");
+    (subject, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_digest_due() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2025, 7, 21).unwrap();
+        assert!(!digest_due(at("2025-07-22 08:59"), None, 9));
+        assert!(digest_due(at("2025-07-22 09:00"), None, 9));
+        assert!(digest_due(at("2025-07-22 15:30"), Some(yesterday), 9));
+        assert!(!digest_due(at("2025-07-22 15:30"), Some(today), 9));
+    }
+
+    #[test]
+    fn test_render_digest() {
+        let mut mention = Notification::new(
+            NotificationKind::Mention,
+            "alice mentioned you in a comment".into(),
+            "comment",
+            1,
+        );
+        mention.content = Some("@bob 请确认".into());
+        mention.crt_at = at("2025-07-22 10:05");
+        let (subject, body) = render_digest("bob", &[mention]);
+        assert_eq!(subject, "You have 1 unread notification");
+        assert!(body.starts_with("Hi bob,"));
+        assert!(body.contains("[Mention] alice mentioned you in a comment (2025-07-22 10:05)\n    @bob 请确认\n"));
+
+        let many: Vec<Notification> = (0..NOTIFICATION_DIGEST_MAX_ITEMS + 2)
+            .map(|i| {
+                Notification::new(NotificationKind::Outdated, format!("phrase {}", i), "phrase", i as u64)
+            })
+            .collect();
+        let (subject, body) = render_digest("bob", &many);
+        assert_eq!(
+            subject,
+            format!("You have {} unread notifications", NOTIFICATION_DIGEST_MAX_ITEMS + 2)
+        );
+        assert!(body.contains("...and 2 more\n"));
+        assert!(!body.contains(&format!("phrase {}", NOTIFICATION_DIGEST_MAX_ITEMS)));
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
  * 响应码定义
  */
 pub const SUCCESS_CODE: i32 = 200;
+pub const CONFLICT_CODE: i32 = 409;
 pub const SERVER_ERROR_CODE: i32 = 500;
 
 /**
@@ -0,0 +1,149 @@
+const SNIPPET_ELLIPSIS: &str = "…";
+const HIGHLIGHT_OPEN: &str = "<em>";
+const HIGHLIGHT_CLOSE: &str = "</em>";
+
+/// 拆分搜索关键字，去掉全文检索的运算符
+pub fn terms(input: &str) -> Vec<String> {
+    input
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| !matches!(c, '"' | '+' | '-' | '<' | '>' | '(' | ')' | '~' | '*' | '@'))
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// 生成 BOOLEAN MODE 查询串，每个词按短语匹配且必须出现，如 `+"登录" +"button"`
+pub fn boolean_query(terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|term| format!("+\"{}\"", term))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// 截取包含首个匹配的片段并高亮所有匹配（忽略大小写），文本会做 HTML 转义
+pub fn highlight(text: &str, terms: &[String], max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| fold(*c)).collect();
+    let terms: Vec<Vec<char>> = terms
+        .iter()
+        .map(|term| term.chars().map(fold).collect::<Vec<_>>())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    // 从左到右找出不重叠的匹配区间，同一位置优先取最长的词
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i < lower.len() {
+        match terms
+            .iter()
+            .filter(|term| lower[i..].starts_with(term))
+            .map(Vec::len)
+            .max()
+        {
+            Some(len) => {
+                matches.push((i, i + len));
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+
+    // 以首个匹配为中心截取
+    let (start, end) = if chars.len() <= max_chars {
+        (0, chars.len())
+    } else {
+        let center = matches.first().map(|(s, e)| (s + e) / 2).unwrap_or(0);
+        let start = center.saturating_sub(max_chars / 2).min(chars.len() - max_chars);
+        (start, start + max_chars)
+    };
+
+    let mut result = String::new();
+    if start > 0 {
+        result.push_str(SNIPPET_ELLIPSIS);
+    }
+    let mut pending = matches
+        .iter()
+        .filter(|(s, e)| *e > start && *s < end)
+        .map(|(s, e)| ((*s).max(start), (*e).min(end)))
+        .peekable();
+    let mut open_until = None;
+    for (pos, c) in chars.iter().enumerate().take(end).skip(start) {
+        if let Some((s, e)) = pending.peek().copied() {
+            if pos == s {
+                result.push_str(HIGHLIGHT_OPEN);
+                open_until = Some(e);
+                pending.next();
+            }
+        }
+        push_escaped(&mut result, *c);
+        if open_until == Some(pos + 1) {
+            result.push_str(HIGHLIGHT_CLOSE);
+            open_until = None;
+        }
+    }
+    if end < chars.len() {
+        result.push_str(SNIPPET_ELLIPSIS);
+    }
+    result
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        _ => out.push(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms_and_boolean_query() {
+        let terms = terms(r#" +登录 "按钮" -a*  "#);
+        assert_eq!(terms, vec!["登录", "按钮", "a"]);
+        assert_eq!(
+            boolean_query(&terms).unwrap(),
+            r#"+"登录" +"按钮" +"a""#
+        );
+        assert!(boolean_query(&[]).is_none());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let terms = vec!["login".to_string()];
+        assert_eq!(
+            highlight("Click LOGIN to <continue>", &terms, 100),
+            "Click <em>LOGIN</em> to &lt;continue&gt;"
+        );
+        assert_eq!(
+            highlight("点击登录按钮后登录", &["登录".to_string()], 100),
+            "点击<em>登录</em>按钮后<em>登录</em>"
+        );
+        assert_eq!(highlight("no match", &terms, 100), "no match");
+    }
+
+    #[test]
+    fn test_highlight_snippet_window() {
+        let text = format!("{}login{}", "a".repeat(50), "b".repeat(50));
+        let snippet = highlight(&text, &["login".to_string()], 15);
+        assert_eq!(snippet, "…aaaaa<em>login</em>bbbbb…");
+    }
+}
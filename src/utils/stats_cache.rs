@@ -0,0 +1,58 @@
+use redis::AsyncCommands;
+
+use crate::constants::{PROJECT_STATS_PREFIX, PROJECT_STATS_VERSION_PREFIX, STATS_CACHE_SECONDS};
+use crate::errors::AppError;
+
+/// 项目统计的 Redis 缓存
+///
+/// 缓存键包含项目的统计版本号，翻译变更时递增版本号即可让旧缓存失效，无需逐个删除
+#[derive(Clone)]
+pub struct StatsCache {
+    client: redis::Client,
+}
+
+impl StatsCache {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get(&self, project_id: u64, days: u32) -> Result<Option<String>, AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = self.cache_key(&mut conn, project_id, days).await?;
+        Ok(conn.get(key).await?)
+    }
+
+    pub async fn set(&self, project_id: u64, days: u32, value: &str) -> Result<(), AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = self.cache_key(&mut conn, project_id, days).await?;
+        conn.set_ex::<_, _, ()>(key, value, STATS_CACHE_SECONDS).await?;
+        Ok(())
+    }
+
+    /// 使项目的统计缓存失效
+    pub async fn invalidate(&self, project_id: u64) -> Result<(), AppError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        conn.incr::<_, _, ()>(version_key(project_id), 1).await?;
+        Ok(())
+    }
+
+    async fn cache_key(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        project_id: u64,
+        days: u32,
+    ) -> Result<String, AppError> {
+        let version: Option<u64> = conn.get(version_key(project_id)).await?;
+        Ok(format!(
+            "{}{}_{}_{}",
+            PROJECT_STATS_PREFIX,
+            project_id,
+            version.unwrap_or(0),
+            days
+        ))
+    }
+}
+
+fn version_key(project_id: u64) -> String {
+    format!("{}{}", PROJECT_STATS_VERSION_PREFIX, project_id)
+}
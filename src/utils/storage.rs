@@ -128,6 +128,13 @@ pub async fn delete_file(client: &Client, bucket: &str, key: &str) -> Result<(),
     Ok(())
 }
 
+/// 从 upload_file 返回的地址中解析对象 key，不属于该存储桶时返回 None
+pub fn object_key<'a>(url: &'a str, bucket: &str) -> Option<&'a str> {
+    url.split_once(&format!("/{}/", bucket))
+        .map(|(_, key)| key)
+        .filter(|key| !key.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +161,14 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_object_key() {
+        let url = "http://localhost:9000/i18n/screenshots/a.png";
+        assert_eq!(object_key(url, "i18n"), Some("screenshots/a.png"));
+        assert_eq!(object_key(url, "other"), None);
+        assert_eq!(object_key("http://localhost:9000/i18n/", "i18n"), None);
+    }
+
     async fn setup() -> Client {
         dotenv::dotenv().ok();
         init_s3_client().await.expect("Failed to init S3 client")
@@ -0,0 +1,67 @@
+/// 统计字数：连续的字母数字算一个词，中日韩等表意文字每个字算一个词
+pub fn word_count(text: &str) -> u64 {
+    let mut count = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_ideographic(c) {
+            count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() || (in_word && matches!(c, '\'' | '-' | '_')) {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+        }
+    }
+    count
+}
+
+/// 统计字符数（Unicode 标量值），不含空白
+pub fn char_count(text: &str) -> u64 {
+    text.chars().filter(|c| !c.is_whitespace()).count() as u64
+}
+
+/// 按字符截断，避免切断多字节字符
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+fn is_ideographic(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF   // 平假名、片假名
+        | 0x3400..=0x4DBF // CJK 扩展 A
+        | 0x4E00..=0x9FFF // CJK 统一表意文字
+        | 0xAC00..=0xD7AF // 韩文音节
+        | 0xF900..=0xFAFF // CJK 兼容表意文字
+        | 0x20000..=0x2FFFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(word_count("Don't save the log-in form"), 5);
+        assert_eq!(word_count("点击登录按钮"), 6);
+        assert_eq!(word_count("共 {count} 条"), 3);
+        assert_eq!(word_count("  "), 0);
+    }
+
+    #[test]
+    fn test_char_count() {
+        assert_eq!(char_count("登录 now"), 5);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("hello", 10), "hello");
+        assert_eq!(truncate("hello", 2), "he");
+        assert_eq!(truncate("你好世界", 2), "你好");
+    }
+}
@@ -0,0 +1,155 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base32::Alphabet;
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+const SECRET_BYTES: usize = 20;
+const DIGITS: u32 = 6;
+const PERIOD_SECONDS: i64 = 30;
+// 允许前后各一个时间窗口的时钟偏差
+const ALLOWED_SKEW: i64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_BYTES: usize = 5;
+
+const BASE32: Alphabet = Alphabet::RFC4648 { padding: false };
+
+/// 生成 TOTP 密钥（Base32 编码）
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    base32::encode(BASE32, &bytes)
+}
+
+/// 验证器 App 扫码使用的 otpauth 地址
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let mut url = Url::parse("otpauth://totp/").expect("valid otpauth base");
+    url.path_segments_mut()
+        .expect("otpauth url has path")
+        .pop_if_empty()
+        .push(&format!("{}:{}", issuer, account));
+    url.query_pairs_mut()
+        .append_pair("secret", secret)
+        .append_pair("issuer", issuer)
+        .append_pair("algorithm", "SHA1")
+        .append_pair("digits", &DIGITS.to_string())
+        .append_pair("period", &PERIOD_SECONDS.to_string());
+    url.to_string()
+}
+
+/// RFC 4226 HOTP
+fn hotp(key: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// 校验验证码，返回匹配的时间窗口序号
+///
+/// last_step 为上次使用过的窗口，不大于它的窗口不再接受，防止验证码重放
+pub fn verify(secret: &str, code: &str, timestamp: i64, last_step: Option<u64>) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let key = base32::decode(BASE32, secret)?;
+    let current = timestamp / PERIOD_SECONDS;
+    (-ALLOWED_SKEW..=ALLOWED_SKEW)
+        .map(|offset| current + offset)
+        .filter(|step| *step >= 0)
+        .map(|step| step as u64)
+        .filter(|step| last_step.map_or(true, |last| *step > last))
+        .find(|step| format!("{:0width$}", hotp(&key, *step), width = DIGITS as usize) == code)
+}
+
+/// 生成一组一次性恢复码，格式为 xxxx-xxxx
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; RECOVERY_CODE_BYTES];
+            OsRng.fill_bytes(&mut bytes);
+            let code = base32::encode(BASE32, &bytes).to_lowercase();
+            format!("{}-{}", &code[..4], &code[4..])
+        })
+        .collect()
+}
+
+/// 恢复码哈希，忽略大小写、空格和连字符
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 附录 B 的 SHA1 密钥 "12345678901234567890"
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_rfc_vectors() {
+        let key = base32::decode(BASE32, RFC_SECRET).unwrap();
+        assert_eq!(hotp(&key, 0), 755224);
+        assert_eq!(hotp(&key, 1), 287082);
+        assert_eq!(verify(RFC_SECRET, "287082", 59, None), Some(1));
+        assert_eq!(verify(RFC_SECRET, "081804", 1111111109, None), Some(37037036));
+        assert_eq!(verify(RFC_SECRET, "005924", 1234567890, None), Some(41152263));
+    }
+
+    #[test]
+    fn test_skew_and_replay() {
+        // 上一个窗口的验证码仍可用
+        assert_eq!(verify(RFC_SECRET, "287082", 89, None), Some(1));
+        // 超出偏差范围
+        assert_eq!(verify(RFC_SECRET, "287082", 150, None), None);
+        // 已使用过的窗口不能再次使用
+        assert_eq!(verify(RFC_SECRET, "287082", 59, Some(1)), None);
+        assert_eq!(verify(RFC_SECRET, "abcdef", 59, None), None);
+        assert_eq!(verify(RFC_SECRET, "28708", 59, None), None);
+    }
+
+    #[test]
+    fn test_generated_secret_round_trip() {
+        let secret = generate_secret();
+        assert_eq!(base32::decode(BASE32, &secret).unwrap().len(), SECRET_BYTES);
+        let key = base32::decode(BASE32, &secret).unwrap();
+        let now = 1_700_000_000;
+        let code = format!("{:06}", hotp(&key, (now / PERIOD_SECONDS) as u64));
+        assert!(verify(&secret, &code, now, None).is_some());
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let uri = otpauth_uri("i18n platform", "alice@example.com", "ABC");
+        assert!(uri.starts_with("otpauth://totp/i18n%20platform:alice@example.com?"));
+        assert!(uri.contains("secret=ABC"));
+        assert!(uri.contains("issuer=i18n+platform"));
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert_eq!(codes[0].len(), 9);
+        assert_ne!(codes[0], codes[1]);
+        assert_eq!(hash_recovery_code(&codes[0]), hash_recovery_code(&codes[0].to_uppercase().replace('-', " ")));
+        assert_ne!(hash_recovery_code(&codes[0]), hash_recovery_code(&codes[1]));
+    }
+}
@@ -0,0 +1,166 @@
+use std::net::{IpAddr, SocketAddr};
+
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use sha2::Sha256;
+
+use crate::constants::{WEBHOOK_BACKOFF_BASE_SECONDS, WEBHOOK_BACKOFF_MAX_SECONDS};
+use crate::errors::AppError;
+use crate::utils::text::truncate;
+
+// 请求头
+pub const SIGNATURE_HEADER: &str = "X-I18n-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-I18n-Timestamp";
+pub const EVENT_HEADER: &str = "X-I18n-Event";
+pub const DELIVERY_HEADER: &str = "X-I18n-Delivery";
+
+/// 签名：HMAC-SHA256(secret, "{timestamp}.{body}")，格式为 "sha256=<hex>"
+///
+/// 签名内容包含时间戳，接收方可据此拒绝重放的旧请求
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// 第 attempts 次投递失败后的重试间隔（秒），指数退避并有上限
+pub fn backoff_seconds(attempts: u32) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(20);
+    WEBHOOK_BACKOFF_BASE_SECONDS
+        .saturating_mul(1 << exponent)
+        .min(WEBHOOK_BACKOFF_MAX_SECONDS)
+}
+
+/// 截取响应内容的开头作为投递日志，控制字符替换为空格
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    truncate(text.trim(), max_chars)
+}
+
+/// 是否为公网地址：内网、回环、链路本地（含 169.254.169.254 元数据地址）、组播、未指定等地址都不允许作为接收地址
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_multicast()
+                || ip.is_broadcast()
+                || ip.is_unspecified()
+                || ip.is_documentation()
+                || a == 0
+                // 100.64.0.0/10 运营商级 NAT
+                || (a == 100 && (64..128).contains(&b))
+                // 198.18.0.0/15 基准测试
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ipv4) = ip.to_ipv4_mapped() {
+                return is_public_ip(&IpAddr::V4(ipv4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // fc00::/7 唯一本地地址
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10 链路本地地址
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// 解析接收地址的主机，返回可连接的地址；解析失败或任一地址不是公网地址时返回 BadRequest
+///
+/// 发送时使用这里返回的地址建立连接，避免校验后 DNS 记录被改为内网地址
+pub async fn resolve_public(url: &Url) -> Result<Vec<SocketAddr>, AppError> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| AppError::BadRequest("Webhook url has no host".into()))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| AppError::BadRequest("Webhook url has no port".into()))?;
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host(format!("{}:{}", host, port))
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Cannot resolve webhook host {}: {}", host, e)))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(AppError::BadRequest(format!("Cannot resolve webhook host {}", host)));
+    }
+    if addrs.iter().any(|addr| !is_public_ip(&addr.ip())) {
+        return Err(AppError::BadRequest(format!(
+            "Webhook host {} must resolve to a public address",
+            host
+        )));
+    }
+    Ok(addrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1700000000, r#"{"a":1}"#),
+            "sha256=49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+        assert_ne!(sign("secret", 1700000001, "{}"), sign("secret", 1700000000, "{}"));
+        assert_ne!(sign("other", 1700000000, "{}"), sign("secret", 1700000000, "{}"));
+    }
+
+    #[test]
+    fn test_backoff_seconds() {
+        assert_eq!(backoff_seconds(1), 30);
+        assert_eq!(backoff_seconds(2), 60);
+        assert_eq!(backoff_seconds(5), 480);
+        assert_eq!(backoff_seconds(20), WEBHOOK_BACKOFF_MAX_SECONDS);
+        assert_eq!(backoff_seconds(u32::MAX), WEBHOOK_BACKOFF_MAX_SECONDS);
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt(" ok\r\n\tdone\n", 10), "ok   done");
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        let public = ["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"];
+        for ip in public {
+            assert!(is_public_ip(&ip.parse().unwrap()), "{}", ip);
+        }
+        let internal = [
+            "127.0.0.1", "10.0.0.1", "172.16.5.4", "192.168.1.1", "169.254.169.254", "0.0.0.0",
+            "100.64.0.1", "224.0.0.1", "255.255.255.255", "::1", "::", "fe80::1", "fd00::1",
+            "ff02::1", "::ffff:127.0.0.1", "::ffff:169.254.169.254",
+        ];
+        for ip in internal {
+            assert!(!is_public_ip(&ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_public() {
+        let url = Url::parse("https://8.8.8.8/hook").unwrap();
+        assert_eq!(resolve_public(&url).await.unwrap(), vec!["8.8.8.8:443".parse().unwrap()]);
+        for url in ["http://127.0.0.1:8080/hook", "http://169.254.169.254/latest", "http://[::1]/hook"] {
+            assert!(resolve_public(&Url::parse(url).unwrap()).await.is_err(), "{}", url);
+        }
+    }
+}